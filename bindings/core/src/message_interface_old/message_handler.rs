@@ -20,7 +20,7 @@ use iota_sdk::{
         constants::SHIMMER_TESTNET_BECH32_HRP,
         request_funds_from_faucet,
         secret::SecretManage,
-        utils, Client, NodeInfoWrapper,
+        utils, NodeInfoWrapper,
     },
     types::{
         block::{
@@ -282,7 +282,7 @@ impl WalletMessageHandler {
                 convert_async_panics(|| async {
                     match url {
                         Some(url) => {
-                            let node_info = Client::get_node_info(&url, auth).await?;
+                            let node_info = self.wallet.client().get_info_from(&url, auth).await?;
                             Ok(Response::NodeInfo(NodeInfoWrapper { node_info, url }))
                         }
                         None => Ok(self.wallet.client().get_info().await.map(Response::NodeInfo)?),
@@ -19,8 +19,8 @@ use iota_sdk::{
     },
     wallet::{
         account::{
-            ConsolidationParams, CreateAliasParams, CreateNativeTokenParams, FilterOptions, MintNftParams,
-            OutputParams, OutputsToClaim, SyncOptions, TransactionOptionsDto,
+            ConsolidationParams, ConsolidationPolicy, CreateAliasParams, CreateNativeTokenParams, FilterOptions,
+            MintNftParams, OutputParams, OutputsToClaim, SyncOptions, TransactionOptionsDto,
         },
         SendNativeTokensParams, SendNftParams, SendParams,
     },
@@ -63,6 +63,9 @@ pub enum AccountMethod {
     /// Get account balance information.
     /// Expected response: [`Balance`](crate::Response::Balance)
     GetBalance,
+    /// Returns the consolidation policy applied by the background syncing.
+    /// Expected response: [`ConsolidationPolicy`](crate::Response::ConsolidationPolicy)
+    GetConsolidationPolicy,
     /// Get the [`Output`](iota_sdk::types::block::output::Output) that minted a native token by its TokenId
     /// Expected response: [`Output`](crate::Response::Output)
     #[serde(rename_all = "camelCase")]
@@ -287,6 +290,10 @@ pub enum AccountMethod {
     /// Set the alias of the account.
     /// Expected response: [`Ok`](crate::Response::Ok)
     SetAlias { alias: String },
+    /// Set or, if `None`, remove the consolidation policy applied by the background syncing.
+    /// If storage is enabled, will persist during restarts.
+    /// Expected response: [`Ok`](crate::Response::Ok)
+    SetConsolidationPolicy { policy: Option<ConsolidationPolicy> },
     /// Set the fallback SyncOptions for account syncing.
     /// If storage is enabled, will persist during restarts.
     /// Expected response: [`Ok`](crate::Response::Ok)
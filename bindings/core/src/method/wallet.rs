@@ -1,6 +1,7 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 #[cfg(feature = "stronghold")]
 use std::path::PathBuf;
 
@@ -9,7 +10,10 @@ use derivative::Derivative;
 use iota_sdk::wallet::events::types::{WalletEvent, WalletEventType};
 use iota_sdk::{
     client::{node_manager::node::NodeAuth, secret::GenerateAddressOptions},
-    types::block::address::Hrp,
+    types::block::{
+        address::Hrp,
+        output::{feature::Irc30Metadata, TokenId},
+    },
     wallet::{
         account::{
             types::{AccountAddress, AccountIdentifier},
@@ -219,4 +223,22 @@ pub enum WalletMethod {
         /// Authentication options
         auth: Option<NodeAuth>,
     },
+    /// Returns the native token registry.
+    /// Expected response: [`TokenRegistry`](crate::Response::TokenRegistry)
+    GetTokenRegistry,
+    /// Sets or, if `None`, removes the override of the metadata of a native token.
+    /// Expected response: [`Ok`](crate::Response::Ok)
+    #[serde(rename_all = "camelCase")]
+    SetTokenMetadataOverride {
+        /// The native token
+        token_id: TokenId,
+        /// The metadata overriding the one of the token foundry
+        metadata: Option<Irc30Metadata>,
+    },
+    /// Replaces the list of verified native tokens.
+    /// Expected response: [`Ok`](crate::Response::Ok)
+    SetVerifiedTokens {
+        /// The verified native tokens with their metadata
+        tokens: HashMap<TokenId, Irc30Metadata>,
+    },
 }
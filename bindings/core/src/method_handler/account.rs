@@ -47,6 +47,7 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
             Response::GeneratedAccountAddresses(address)
         }
         AccountMethod::GetBalance => Response::Balance(account.balance().await?),
+        AccountMethod::GetConsolidationPolicy => Response::ConsolidationPolicy(account.consolidation_policy().await),
         AccountMethod::GetFoundryOutput { token_id } => {
             let output = account.get_foundry_output(token_id).await?;
             Response::Output(OutputDto::from(&output))
@@ -294,6 +295,10 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
             account.set_alias(&alias).await?;
             Response::Ok
         }
+        AccountMethod::SetConsolidationPolicy { policy } => {
+            account.set_consolidation_policy(policy).await?;
+            Response::Ok
+        }
         AccountMethod::SetDefaultSyncOptions { options } => {
             account.set_default_sync_options(options).await?;
             Response::Ok
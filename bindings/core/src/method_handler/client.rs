@@ -44,12 +44,16 @@ where
                 MqttPayload::Block(block) => {
                     serde_json::to_string(block).expect("failed to serialize MqttPayload::Block")
                 }
-                MqttPayload::MilestonePayload(ms) => {
-                    serde_json::to_string(ms).expect("failed to serialize MqttPayload::MilestonePayload")
-                }
+                MqttPayload::MilestonePayload(ms) => serde_json::to_string(&MilestonePayloadDto::from(ms))
+                    .expect("failed to serialize MqttPayload::MilestonePayload"),
                 MqttPayload::Receipt(receipt) => {
                     serde_json::to_string(receipt).expect("failed to serialize MqttPayload::Receipt")
                 }
+                MqttPayload::Output(output) => serde_json::to_string(&OutputWithMetadataResponse::from(output))
+                    .expect("failed to serialize MqttPayload::Output"),
+                MqttPayload::BlockMetadata(metadata) => {
+                    serde_json::to_string(metadata).expect("failed to serialize MqttPayload::BlockMetadata")
+                }
                 e => panic!("received unknown mqtt type: {e:?}"),
             };
             let response = MqttResponse {
@@ -262,7 +266,7 @@ pub(crate) async fn call_client_method_internal(client: &Client, method: ClientM
         #[cfg(not(target_family = "wasm"))]
         ClientMethod::UnhealthyNodes => Response::UnhealthyNodes(client.unhealthy_nodes().await.into_iter().collect()),
         ClientMethod::GetHealth { url } => Response::Bool(client.get_health(&url).await?),
-        ClientMethod::GetNodeInfo { url, auth } => Response::NodeInfo(Client::get_node_info(&url, auth).await?),
+        ClientMethod::GetNodeInfo { url, auth } => Response::NodeInfo(client.get_info_from(&url, auth).await?),
         ClientMethod::GetInfo => Response::Info(client.get_info().await?),
         ClientMethod::GetPeers => Response::Peers(client.get_peers().await?),
         ClientMethod::GetTips => Response::Tips(client.get_tips().await?),
@@ -200,6 +200,15 @@ pub(crate) async fn call_wallet_method_internal(wallet: &Wallet, method: WalletM
             wallet.update_node_auth(url, auth).await?;
             Response::Ok
         }
+        WalletMethod::GetTokenRegistry => Response::TokenRegistry(wallet.token_registry().await),
+        WalletMethod::SetTokenMetadataOverride { token_id, metadata } => {
+            wallet.set_token_metadata_override(token_id, metadata).await?;
+            Response::Ok
+        }
+        WalletMethod::SetVerifiedTokens { tokens } => {
+            wallet.set_verified_tokens(tokens).await?;
+            Response::Ok
+        }
     };
     Ok(response)
 }
@@ -37,9 +37,12 @@ use iota_sdk::{
             BlockDto, BlockId,
         },
     },
-    wallet::account::{
-        types::{AccountAddress, AddressWithUnspentOutputs, Balance, OutputDataDto, TransactionDto},
-        AccountDetailsDto, PreparedCreateNativeTokenTransactionDto,
+    wallet::{
+        account::{
+            types::{AccountAddress, AddressWithUnspentOutputs, Balance, OutputDataDto, TransactionDto},
+            AccountDetailsDto, ConsolidationPolicy, PreparedCreateNativeTokenTransactionDto,
+        },
+        core::TokenRegistry,
     },
 };
 use serde::Serialize;
@@ -311,6 +314,9 @@ pub enum Response {
     /// - [`GetChrysalisData`](crate::method::WalletMethod::GetChrysalisData)
     ChrysalisData(Option<HashMap<String, String>>),
     /// Response for:
+    /// - [`GetTokenRegistry`](crate::method::WalletMethod::GetTokenRegistry)
+    TokenRegistry(TokenRegistry),
+    /// Response for:
     /// - [`MinimumRequiredStorageDeposit`](crate::method::ClientMethod::MinimumRequiredStorageDeposit)
     /// - [`ComputeStorageDeposit`](crate::method::UtilsMethod::ComputeStorageDeposit)
     MinimumRequiredStorageDeposit(String),
@@ -364,6 +370,9 @@ pub enum Response {
     /// - [`Sync`](crate::method::AccountMethod::Sync)
     Balance(Balance),
     /// Response for:
+    /// - [`GetConsolidationPolicy`](crate::method::AccountMethod::GetConsolidationPolicy)
+    ConsolidationPolicy(Option<ConsolidationPolicy>),
+    /// Response for:
     /// - [`ClaimOutputs`](crate::method::AccountMethod::ClaimOutputs)
     /// - [`Send`](crate::method::AccountMethod::Send)
     /// - [`SendOutputs`](crate::method::AccountMethod::SendOutputs)
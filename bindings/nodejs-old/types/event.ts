@@ -38,6 +38,7 @@ enum WalletEventType {
     SpentOutput = 3,
     TransactionInclusion = 4,
     TransactionProgress = 5,
+    TransactionReissue = 6,
 }
 
 abstract class WalletEvent {
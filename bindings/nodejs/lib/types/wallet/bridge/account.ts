@@ -29,7 +29,10 @@ import type {
     ParticipationEventRegistrationOptions,
     ParticipationEventType,
 } from '../participation';
-import type { ConsolidationParams } from '../consolidation-params';
+import type {
+    ConsolidationParams,
+    ConsolidationPolicy,
+} from '../consolidation-params';
 import { HexEncodedAmount, Output } from '../../';
 
 export type __BuildAliasOutputMethod__ = {
@@ -110,6 +113,10 @@ export type __GetBalanceMethod__ = {
     name: 'getBalance';
 };
 
+export type __GetConsolidationPolicyMethod__ = {
+    name: 'getConsolidationPolicy';
+};
+
 export type __GetIncomingTransactionMethod__ = {
     name: 'getIncomingTransaction';
     data: {
@@ -292,6 +299,13 @@ export type __SetAliasMethod__ = {
     };
 };
 
+export type __SetConsolidationPolicyMethod__ = {
+    name: 'setConsolidationPolicy';
+    data: {
+        policy?: ConsolidationPolicy;
+    };
+};
+
 export type __SetDefaultSyncOptionsMethod__ = {
     name: 'setDefaultSyncOptions';
     data: {
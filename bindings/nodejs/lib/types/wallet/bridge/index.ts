@@ -11,6 +11,7 @@ import type {
     __DeregisterParticipationEventMethod__,
     __GenerateEd25519AddressesMethod__,
     __GetBalanceMethod__,
+    __GetConsolidationPolicyMethod__,
     __GetOutputMethod__,
     __GetFoundryOutputMethod__,
     __ClaimableOutputsMethod__,
@@ -37,6 +38,7 @@ import type {
     __PrepareSendNftMethod__,
     __SendOutputsMethod__,
     __SetAliasMethod__,
+    __SetConsolidationPolicyMethod__,
     __SetDefaultSyncOptionsMethod__,
     __SignTransactionEssenceMethod__,
     __SignAndSubmitTransactionMethod__,
@@ -92,6 +94,7 @@ export type __AccountMethod__ =
     | __DeregisterParticipationEventMethod__
     | __GenerateEd25519AddressesMethod__
     | __GetBalanceMethod__
+    | __GetConsolidationPolicyMethod__
     | __GetOutputMethod__
     | __GetIncomingTransactionMethod__
     | __GetFoundryOutputMethod__
@@ -123,6 +126,7 @@ export type __AccountMethod__ =
     | __PrepareSendNftMethod__
     | __SendOutputsMethod__
     | __SetAliasMethod__
+    | __SetConsolidationPolicyMethod__
     | __SetDefaultSyncOptionsMethod__
     | __SignTransactionEssenceMethod__
     | __SignAndSubmitTransactionMethod__
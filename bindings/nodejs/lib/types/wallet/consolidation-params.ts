@@ -10,3 +10,15 @@ export interface ConsolidationParams {
     /** Address to which the consolidated output should be sent. */
     targetAddress?: string;
 }
+
+/** Consolidation applied automatically by the background syncing */
+export interface ConsolidationPolicy {
+    /** Consolidates if the output number is >= the output threshold. */
+    outputThreshold?: number;
+    /** Minimum time between two consolidations in milliseconds, one hour by default. */
+    minInterval?: number;
+    /** Address to which the consolidated outputs should be sent. */
+    targetAddress?: string;
+    /** Also consolidates outputs holding native tokens. */
+    mergeNativeTokens?: boolean;
+}
@@ -51,6 +51,8 @@ enum WalletEventType {
     TransactionInclusion = 4,
     /** A progress update while submitting a transaction. */
     TransactionProgress = 5,
+    /** A conflicting transaction was reissued. */
+    TransactionReissue = 6,
 }
 
 /**
@@ -149,6 +151,27 @@ class TransactionInclusionWalletEvent extends WalletEvent {
     }
 }
 
+/**
+ * A 'transaction reissue' wallet event.
+ */
+class TransactionReissueWalletEvent extends WalletEvent {
+    transactionId: TransactionId;
+    reissuedTransactionId: TransactionId;
+
+    /**
+     * @param transactionId The ID of the conflicting transaction.
+     * @param reissuedTransactionId The ID of the transaction sending its outputs again.
+     */
+    constructor(
+        transactionId: TransactionId,
+        reissuedTransactionId: TransactionId,
+    ) {
+        super(WalletEventType.TransactionReissue);
+        this.transactionId = transactionId;
+        this.reissuedTransactionId = reissuedTransactionId;
+    }
+}
+
 /**
  * All of the transaction progress types.
  */
@@ -299,6 +322,7 @@ export {
     SpentOutputWalletEvent,
     TransactionInclusionWalletEvent,
     TransactionProgressWalletEvent,
+    TransactionReissueWalletEvent,
     TransactionProgress,
     SelectingInputsProgress,
     GeneratingRemainderDepositAddressProgress,
@@ -37,6 +37,7 @@ import {
     PreparedTransaction,
     PreparedCreateNativeTokenTransactionData,
     ConsolidationParams,
+    ConsolidationPolicy,
 } from '../types/wallet';
 import { INode, Burn, PreparedTransactionData } from '../client';
 import {
@@ -453,6 +454,21 @@ export class Account {
         return this.adjustBalancePayload(payload);
     }
 
+    /**
+     * Get the consolidation policy applied by the background syncing.
+     *
+     * @returns The consolidation policy, if one is set.
+     */
+    async getConsolidationPolicy(): Promise<ConsolidationPolicy | undefined> {
+        const response = await this.methodHandler.callAccountMethod(
+            this.meta.index,
+            {
+                name: 'getConsolidationPolicy',
+            },
+        );
+        return JSON.parse(response).payload ?? undefined;
+    }
+
     /**
      * Converts hex encoded or decimal strings of amounts to `bigint`
      * for the balance payload.
@@ -1211,6 +1227,21 @@ export class Account {
         });
     }
 
+    /**
+     * Set or, if `undefined`, remove the consolidation policy applied by the background syncing.
+     * If storage is enabled, will persist during restarts.
+     *
+     * @param policy The consolidation policy to set.
+     */
+    async setConsolidationPolicy(policy?: ConsolidationPolicy): Promise<void> {
+        await this.methodHandler.callAccountMethod(this.meta.index, {
+            name: 'setConsolidationPolicy',
+            data: {
+                policy,
+            },
+        });
+    }
+
     /**
      * Set the fallback SyncOptions for account syncing.
      * If storage is enabled, will persist during restarts.
@@ -21,3 +21,24 @@ class ConsolidationParams:
     force: bool
     outputThreshold: Optional[int] = None
     targetAddress: Optional[str] = None
+
+
+@dataclass
+class ConsolidationPolicy:
+    """Consolidation applied automatically by the background syncing.
+
+        Attributes:
+        outputThreshold (Optional[int]):
+            Consolidates if the output number is >= the output_threshold.
+        minInterval (int):
+            Minimum time between two consolidations in milliseconds.
+        targetAddress (Optional[str]):
+            Address to which the consolidated outputs should be sent.
+        mergeNativeTokens (bool):
+            Also consolidates outputs holding native tokens.
+    """
+
+    outputThreshold: Optional[int] = None
+    minInterval: int = 3600000
+    targetAddress: Optional[str] = None
+    mergeNativeTokens: bool = False
@@ -14,6 +14,7 @@ class WalletEventType(IntEnum):
         SpentOutput (3): An output was spent.
         TransactionInclusion (4): A transaction was included into the ledger.
         TransactionProgress (5): A progress update while submitting a transaction.
+        TransactionReissue (6): A conflicting transaction was reissued.
     """
     ConsolidationRequired = 0,
     LedgerAddressGeneration = 1,
@@ -21,3 +22,4 @@ class WalletEventType(IntEnum):
     SpentOutput = 3,
     TransactionInclusion = 4,
     TransactionProgress = 5,
+    TransactionReissue = 6,
@@ -18,7 +18,7 @@ from iota_sdk.types.transaction_data import PreparedTransactionData, SignedTrans
 from iota_sdk.types.send_params import CreateAliasOutputParams, CreateNativeTokenParams, MintNftParams, SendNativeTokensParams, SendNftParams, SendParams
 from iota_sdk.types.transaction import Transaction
 from iota_sdk.types.transaction_options import TransactionOptions
-from iota_sdk.types.consolidation_params import ConsolidationParams
+from iota_sdk.types.consolidation_params import ConsolidationParams, ConsolidationPolicy
 from typing import List, Optional
 from dacite import from_dict
 from dataclasses import dataclass
@@ -333,6 +333,14 @@ class Account:
             'getBalance'
         ))
 
+    def get_consolidation_policy(self) -> Optional[ConsolidationPolicy]:
+        """Get the consolidation policy applied by the background syncing.
+        """
+        policy = self._call_account_method(
+            'getConsolidationPolicy'
+        )
+        return from_dict(ConsolidationPolicy, policy) if policy else None
+
     def prepare_output(self, params: OutputParams,
                        transaction_options: Optional[TransactionOptions] = None) -> BasicOutput | NftOutput:
         """Prepare an output for sending.
@@ -453,6 +461,16 @@ class Account:
             }
         )
 
+    def set_consolidation_policy(self, policy: Optional[ConsolidationPolicy] = None):
+        """Set or, if `None`, remove the consolidation policy applied by the background syncing.
+        If storage is enabled, will persist during restarts.
+        """
+        return self._call_account_method(
+            'setConsolidationPolicy', {
+                'policy': policy
+            }
+        )
+
     def set_default_sync_options(self, options: SyncOptions):
         """Set the fallback SyncOptions for account syncing.
         If storage is enabled, will persist during restarts.
@@ -269,6 +269,27 @@ pub async fn balance_command(account: &Account, addresses: Option<Vec<Bech32Addr
     };
     println_log_info!("{balance:#?}");
 
+    if !balance.native_tokens().is_empty() {
+        println_log_info!("Native tokens:");
+        for native_token in balance.native_tokens() {
+            let name = native_token
+                .irc30_metadata()
+                .map(|metadata| {
+                    format!(
+                        "{}{}",
+                        metadata.name(),
+                        if native_token.verified() { " (verified)" } else { "" }
+                    )
+                })
+                .unwrap_or_else(|| native_token.token_id().to_string());
+            println_log_info!(
+                "  - {name}: {} available, {} total",
+                native_token.format_amount(native_token.available()),
+                native_token.format_amount(native_token.total())
+            );
+        }
+    }
+
     Ok(())
 }
 
@@ -10,7 +10,7 @@
 
 use iota_sdk::{
     client::{
-        mqtt::{BrokerOptions, MqttEvent, MqttPayload, Topic},
+        mqtt::{BrokerOptions, MqttEvent, MqttPayload, MqttTopic, Topic},
         Client, Result,
     },
     types::block::address::Bech32Address,
@@ -33,6 +33,8 @@ async fn main() -> Result<()> {
         .finish()
         .await?;
 
+    let address_topic: Topic = MqttTopic::address_outputs(address).try_into()?;
+
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
 
     client
@@ -40,7 +42,7 @@ async fn main() -> Result<()> {
             [
                 Topic::new("milestone-info/latest")?,
                 Topic::new("blocks")?,
-                Topic::new(format!("outputs/unlock/address/{address}"))?,
+                address_topic.clone(),
             ],
             move |event| {
                 println!("> Topic: {}", event.topic);
@@ -49,6 +51,7 @@ async fn main() -> Result<()> {
                     MqttPayload::Block(block) => println!("{block:?}"),
                     MqttPayload::MilestonePayload(ms) => println!("{ms:?}"),
                     MqttPayload::Receipt(receipt) => println!("{receipt:?}"),
+                    MqttPayload::Output(output) => println!("{output:?}"),
                     e => println!("unknown event received: {e:?}"),
                 }
                 tx.send(()).unwrap();
@@ -64,7 +67,7 @@ async fn main() -> Result<()> {
                 if event_count == num_events {
                     client.unsubscribe([Topic::new("milestone-info/latest")?]).await?;
                     client.unsubscribe([Topic::new("blocks")?]).await?;
-                    client.unsubscribe([Topic::new(format!("outputs/unlock/address/{address}"))?]).await?;
+                    client.unsubscribe([address_topic]).await?;
                     break;
                 }
             }
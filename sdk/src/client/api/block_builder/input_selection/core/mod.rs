@@ -41,6 +41,7 @@ pub struct InputSelection {
     outputs: Vec<Output>,
     addresses: HashSet<Address>,
     burn: Option<Burn>,
+    allow_empty_outputs: bool,
     remainder_address: Option<Address>,
     protocol_parameters: ProtocolParameters,
     timestamp: u32,
@@ -186,6 +187,7 @@ impl InputSelection {
             outputs: outputs.into(),
             addresses,
             burn: None,
+            allow_empty_outputs: false,
             remainder_address: None,
             protocol_parameters,
             timestamp: unix_timestamp_now().as_secs() as u32,
@@ -212,6 +214,13 @@ impl InputSelection {
         self
     }
 
+    /// Sets whether an [`InputSelection`] without outputs is allowed, for example to send the value of the required
+    /// inputs back to the remainder address.
+    pub fn allow_empty_outputs(mut self, allow_empty_outputs: bool) -> Self {
+        self.allow_empty_outputs = allow_empty_outputs;
+        self
+    }
+
     /// Sets the remainder address of an [`InputSelection`].
     pub fn remainder_address(mut self, address: impl Into<Option<Address>>) -> Self {
         self.remainder_address = address.into();
@@ -362,8 +371,8 @@ impl InputSelection {
     /// transaction. Also creates a remainder output and chain transition outputs if required.
     pub fn select(mut self) -> Result<Selected, Error> {
         if !OUTPUT_COUNT_RANGE.contains(&(self.outputs.len() as u16)) {
-            // If burn is provided, outputs will be added later
-            if !(self.outputs.is_empty() && self.burn.is_some()) {
+            // If burn is provided or empty outputs are allowed, outputs will be added later
+            if !(self.outputs.is_empty() && (self.burn.is_some() || self.allow_empty_outputs)) {
                 return Err(Error::InvalidOutputCount(self.outputs.len()));
             }
         }
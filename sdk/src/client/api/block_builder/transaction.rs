@@ -24,12 +24,16 @@ use crate::{
     },
 };
 
-const MAX_TX_LENGTH_FOR_BLOCK_WITH_8_PARENTS: usize = Block::LENGTH_MAX - Block::LENGTH_MIN - (7 * BlockId::LENGTH);
+pub(crate) const MAX_TX_LENGTH_FOR_BLOCK_WITH_8_PARENTS: usize =
+    Block::LENGTH_MAX - Block::LENGTH_MIN - (7 * BlockId::LENGTH);
 // Length for unlocks with a single signature unlock (unlocks length + unlock type + signature type + public key +
 // signature)
-const SINGLE_UNLOCK_LENGTH: usize = 1 + 1 + Ed25519Signature::PUBLIC_KEY_LENGTH + Ed25519Signature::SIGNATURE_LENGTH;
+pub(crate) const SINGLE_UNLOCK_LENGTH: usize =
+    1 + 1 + Ed25519Signature::PUBLIC_KEY_LENGTH + Ed25519Signature::SIGNATURE_LENGTH;
 // Type + reference index
-const REFERENCE_ALIAS_NFT_UNLOCK_LENGTH: usize = 1 + 2;
+pub(crate) const REFERENCE_ALIAS_NFT_UNLOCK_LENGTH: usize = 1 + 2;
+// Type + output id
+pub(crate) const UTXO_INPUT_LENGTH: usize = 1 + OutputId::LENGTH;
 
 impl<'a> ClientBlockBuilder<'a> {
     /// Prepare a transaction
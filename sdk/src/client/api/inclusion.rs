@@ -0,0 +1,275 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Awaiting the inclusion of blocks and transactions in the ledger.
+
+#[cfg(feature = "mqtt")]
+use std::sync::Arc;
+use std::time::Duration;
+
+use instant::Instant;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "mqtt")]
+use crate::client::node_api::mqtt::{MqttTopic, Topic, TopicEvent, TopicHandler};
+use crate::{
+    client::{
+        constants::{DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL, DEFAULT_RETRY_UNTIL_INCLUDED_MAX_AMOUNT},
+        node_api::error::Error as NodeApiError,
+        node_manager::retry::sleep,
+        Client, Error, Result,
+    },
+    types::{
+        api::core::response::LedgerInclusionState,
+        block::{
+            payload::{transaction::TransactionId, Payload},
+            semantic::ConflictReason,
+            BlockId,
+        },
+    },
+};
+
+/// Options for awaiting the inclusion of a block or transaction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionOptions {
+    /// The duration after which the awaiting stops with [`InclusionOutcome::TimedOut`]
+    pub timeout: Duration,
+    /// The interval in which the state is polled, and the latest attachment is promoted or reattached if needed
+    pub interval: Duration,
+    /// Whether the latest attachment is promoted or reattached when the node suggests it
+    pub promote_and_reattach: bool,
+}
+
+impl Default for InclusionOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(
+                DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL * DEFAULT_RETRY_UNTIL_INCLUDED_MAX_AMOUNT,
+            ),
+            interval: Duration::from_secs(DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL),
+            promote_and_reattach: true,
+        }
+    }
+}
+
+/// The final state of an awaited block or transaction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum InclusionOutcome {
+    /// The block was referenced by a milestone and its transaction, if any, was included in the ledger.
+    Included {
+        /// The included block, which can be a reattachment or another block with the same transaction.
+        block_id: BlockId,
+    },
+    /// The transaction of the block conflicts with the ledger and no other block with it was included.
+    Conflicting {
+        /// The conflicting block.
+        block_id: BlockId,
+        /// The reason of the conflict, if known.
+        conflict_reason: Option<ConflictReason>,
+    },
+    /// The block was neither included nor conflicting before the timeout.
+    TimedOut {
+        /// The latest attachment, which can be awaited again.
+        latest_attachment: BlockId,
+    },
+}
+
+impl Client {
+    /// Returns once the block is included, conflicting or the timeout of the options passed. Until then the latest
+    /// attachment is promoted or reattached when the node suggests it. A block the node didn't receive yet is requested
+    /// again in the interval of the options.
+    ///
+    /// With the `mqtt` feature the state is checked as soon as the node publishes new metadata of an attachment or the
+    /// block including its transaction, otherwise and if the subscription fails it's polled in the interval of the
+    /// options.
+    pub async fn await_inclusion(
+        &self,
+        block_id: &BlockId,
+        options: impl Into<Option<InclusionOptions>> + Send,
+    ) -> Result<InclusionOutcome> {
+        log::debug!("[await_inclusion]");
+        let options = options.into().unwrap_or_default();
+        let deadline = Instant::now() + options.timeout;
+        // The node may not have received the block yet, so it's requested again until the timeout
+        let block = loop {
+            match self.get_block(block_id).await {
+                Ok(block) => break block,
+                Err(Error::Node(NodeApiError::NotFound(_))) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Ok(InclusionOutcome::TimedOut {
+                            latest_attachment: *block_id,
+                        });
+                    }
+                    sleep(options.interval.min(deadline - now)).await;
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        let transaction_id = match block.payload() {
+            Some(Payload::Transaction(transaction_payload)) => Some(transaction_payload.id()),
+            _ => None,
+        };
+        // Attachments of the block, the latest one is promoted or reattached
+        let mut attachments = vec![*block_id];
+
+        #[cfg(feature = "mqtt")]
+        let notify = Arc::new(tokio::sync::Notify::new());
+        #[cfg(feature = "mqtt")]
+        let handler: Arc<TopicHandler> = {
+            let notify = notify.clone();
+            Arc::new(Box::new(move |_: &TopicEvent| notify.notify_one()) as TopicHandler)
+        };
+        #[cfg(feature = "mqtt")]
+        let mut topics = Vec::new();
+        #[cfg(feature = "mqtt")]
+        let mut subscribed_attachments = 0;
+        #[cfg(feature = "mqtt")]
+        if let Some(transaction_id) = transaction_id {
+            self.subscribe_inclusion_topic(
+                Topic::new_unchecked(MqttTopic::TransactionIncludedBlock(transaction_id).to_string()),
+                &handler,
+                &mut topics,
+            )
+            .await;
+        }
+
+        let outcome: Result<InclusionOutcome> = async {
+            loop {
+                #[cfg(feature = "mqtt")]
+                {
+                    for attachment in &attachments[subscribed_attachments..] {
+                        self.subscribe_inclusion_topic(
+                            Topic::new_unchecked(MqttTopic::block_metadata(*attachment).to_string()),
+                            &handler,
+                            &mut topics,
+                        )
+                        .await;
+                    }
+                    subscribed_attachments = attachments.len();
+                }
+
+                if let Some(outcome) = self
+                    .check_inclusion(&mut attachments, transaction_id, options.promote_and_reattach)
+                    .await?
+                {
+                    return Ok(outcome);
+                }
+
+                let now = Instant::now();
+                if now >= deadline {
+                    // Safe to unwrap since there is always the original block
+                    return Ok(InclusionOutcome::TimedOut {
+                        latest_attachment: *attachments.last().unwrap(),
+                    });
+                }
+                let delay = options.interval.min(deadline - now);
+
+                #[cfg(feature = "mqtt")]
+                {
+                    let interval = sleep(delay);
+                    let notified = notify.notified();
+                    futures::pin_mut!(interval, notified);
+                    futures::future::select(interval, notified).await;
+                }
+                #[cfg(not(feature = "mqtt"))]
+                sleep(delay).await;
+            }
+        }
+        .await;
+
+        // Only our handler is removed, other subscriptions to the topics are kept
+        #[cfg(feature = "mqtt")]
+        if !topics.is_empty() {
+            if let Err(err) = self
+                .subscriber()
+                .with_topics(topics)
+                .unsubscribe_handler(&handler)
+                .await
+            {
+                log::debug!("[await_inclusion] failed to unsubscribe: {err}");
+            }
+        }
+
+        outcome
+    }
+
+    /// Checks the state of the attachments, and promotes or reattaches the latest one if needed.
+    async fn check_inclusion(
+        &self,
+        attachments: &mut Vec<BlockId>,
+        transaction_id: Option<TransactionId>,
+        promote_and_reattach: bool,
+    ) -> Result<Option<InclusionOutcome>> {
+        let mut conflict = None;
+        let mut latest_metadata = None;
+        for attachment in attachments.iter() {
+            let metadata = match self.get_block_metadata(attachment).await {
+                Ok(metadata) => metadata,
+                // The node didn't receive the attachment yet, so it's not included
+                Err(Error::Node(NodeApiError::NotFound(_))) => continue,
+                Err(err) => return Err(err),
+            };
+            match metadata.ledger_inclusion_state {
+                Some(LedgerInclusionState::Included | LedgerInclusionState::NoTransaction) => {
+                    return Ok(Some(InclusionOutcome::Included { block_id: *attachment }));
+                }
+                // Another attachment could still be included
+                Some(LedgerInclusionState::Conflicting) if conflict.is_none() => {
+                    conflict = Some((*attachment, metadata.conflict_reason));
+                }
+                _ => {}
+            }
+            latest_metadata = Some(metadata);
+        }
+
+        if let Some((block_id, conflict_reason)) = conflict {
+            // The transaction could have been included in a block that isn't one of our attachments
+            if let Some(transaction_id) = transaction_id {
+                match self.get_included_block_metadata(&transaction_id).await {
+                    Ok(metadata) if metadata.ledger_inclusion_state == Some(LedgerInclusionState::Included) => {
+                        return Ok(Some(InclusionOutcome::Included {
+                            block_id: metadata.block_id,
+                        }));
+                    }
+                    Ok(_) | Err(Error::Node(NodeApiError::NotFound(_))) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            return Ok(Some(InclusionOutcome::Conflicting {
+                block_id,
+                conflict_reason: conflict_reason.and_then(|reason| ConflictReason::try_from(reason).ok()),
+            }));
+        }
+
+        if promote_and_reattach {
+            // Only the latest attachment is promoted or reattached
+            if let Some(metadata) = latest_metadata {
+                if metadata.should_promote.unwrap_or(false) {
+                    self.promote_unchecked(&metadata.block_id).await?;
+                } else if metadata.should_reattach.unwrap_or(false) {
+                    let (reattached_block_id, _) = self.reattach_unchecked(&metadata.block_id).await?;
+                    attachments.push(reattached_block_id);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Subscribes to a topic notifying the awaiting of an inclusion, failures fall back to polling.
+    #[cfg(feature = "mqtt")]
+    async fn subscribe_inclusion_topic(&self, topic: Topic, handler: &Arc<TopicHandler>, topics: &mut Vec<Topic>) {
+        match self
+            .subscriber()
+            .with_topic(topic.clone())
+            .subscribe_handler(handler.clone())
+            .await
+        {
+            Ok(()) => topics.push(topic),
+            Err(err) => log::debug!("[await_inclusion] failed to subscribe to {}: {err}", topic.as_str()),
+        }
+    }
+}
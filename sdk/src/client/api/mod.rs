@@ -7,8 +7,9 @@ mod address;
 mod block_builder;
 mod consolidation;
 mod high_level;
+mod inclusion;
 mod types;
 
-pub use self::{address::*, block_builder::*, types::*};
+pub use self::{address::*, block_builder::*, inclusion::*, types::*};
 
 const ADDRESS_GAP_RANGE: u32 = 20;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Builder of the Client Instance
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{collections::HashMap, fmt::Display, sync::Arc, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
@@ -11,12 +11,20 @@ use super::{node_manager::builder::NodeManagerBuilder, ClientInner};
 use crate::client::node_api::mqtt::{BrokerOptions, MqttEvent};
 use crate::{
     client::{
+        cache::{Cache, CacheBackend, CacheOptions},
         constants::{DEFAULT_API_TIMEOUT, DEFAULT_REMOTE_POW_API_TIMEOUT, DEFAULT_TIPS_INTERVAL},
         error::Result,
         node_manager::{
+            auth::{AuthProvider, SharedAuthProvider},
             builder::validate_url,
+            circuit_breaker::CircuitBreakerOptions,
             node::{Node, NodeAuth},
+            quorum::QuorumEndpoint,
+            retry::RetryPolicy,
+            scoring::NodeSelectionPolicy,
+            transport::{HttpTransport, Transport},
         },
+        storage::StorageAdapter,
         Client,
     },
     types::block::protocol::ProtocolParameters,
@@ -52,6 +60,12 @@ pub struct ClientBuilder {
     #[cfg(not(target_family = "wasm"))]
     #[serde(default = "default_max_parallel_api_requests")]
     pub max_parallel_api_requests: usize,
+    /// Options of the cache of immutable data, disabled if `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheOptions>,
+    /// The persistent backend of the cache, not serialized
+    #[serde(skip)]
+    pub(crate) cache_backend: Option<CacheBackend>,
 }
 
 fn default_api_timeout() -> Duration {
@@ -93,6 +107,8 @@ impl Default for ClientBuilder {
             pow_worker_count: None,
             #[cfg(not(target_family = "wasm"))]
             max_parallel_api_requests: super::constants::MAX_PARALLEL_API_REQUESTS,
+            cache: None,
+            cache_backend: None,
         }
     }
 }
@@ -196,6 +212,34 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the endpoint classes for which quorum is required when it's enabled, all by default
+    pub fn with_quorum_endpoints(mut self, quorum_endpoints: impl IntoIterator<Item = QuorumEndpoint>) -> Self {
+        self.node_manager_builder = self
+            .node_manager_builder
+            .with_quorum_endpoints(quorum_endpoints.into_iter().collect());
+        self
+    }
+
+    /// Set the policy used to select the nodes for a request
+    pub fn with_node_selection_policy(mut self, node_selection_policy: NodeSelectionPolicy) -> Self {
+        self.node_manager_builder = self
+            .node_manager_builder
+            .with_node_selection_policy(node_selection_policy);
+        self
+    }
+
+    /// Set the policy for retrying requests that failed on all nodes, with exponential backoff
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_retry_policy(retry_policy);
+        self
+    }
+
+    /// Set the options of the circuit breakers that skip nodes after repeated failures
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreakerOptions) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_circuit_breaker(circuit_breaker);
+        self
+    }
+
     /// Sets the MQTT broker options.
     #[cfg(feature = "mqtt")]
     #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
@@ -248,6 +292,21 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the transport sending the HTTP requests to the nodes, reqwest is used by default.
+    pub fn with_http_transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_transport(Transport(Arc::new(transport)));
+        self
+    }
+
+    /// Set the provider of the credentials for the REST requests and MQTT connections to the nodes, which takes
+    /// precedence over the static [`NodeAuth`] of the nodes.
+    pub fn with_auth_provider(mut self, auth_provider: impl AuthProvider + 'static) -> Self {
+        self.node_manager_builder = self
+            .node_manager_builder
+            .with_auth_provider(SharedAuthProvider(Arc::new(auth_provider)));
+        self
+    }
+
     /// Set maximum parallel API requests.
     #[cfg(not(target_family = "wasm"))]
     pub fn with_max_parallel_api_requests(mut self, max_parallel_api_requests: usize) -> Self {
@@ -255,6 +314,23 @@ impl ClientBuilder {
         self
     }
 
+    /// Enables the cache of immutable data like blocks, milestones and spent outputs.
+    pub fn with_cache(mut self, options: CacheOptions) -> Self {
+        self.cache = Some(options);
+        self
+    }
+
+    /// Sets a persistent backend for the cache, so cached data survives restarts. Only used if the cache is enabled.
+    /// The backend isn't serialized with the options, a wallet with storage uses its database unless another backend
+    /// is set.
+    pub fn with_cache_backend<T: StorageAdapter + 'static>(mut self, backend: T) -> Self
+    where
+        T::Error: Display,
+    {
+        self.cache_backend = Some(CacheBackend::new(backend));
+        self
+    }
+
     /// Build the Client instance.
     #[cfg(not(target_family = "wasm"))]
     pub async fn finish(self) -> Result<Client> {
@@ -288,6 +364,10 @@ impl ClientBuilder {
                 receiver: RwLock::new(mqtt_event_rx),
             },
             request_pool: crate::client::request_pool::RequestPool::new(self.max_parallel_api_requests),
+            cache: RwLock::new(
+                self.cache
+                    .map(|options| Arc::new(Cache::new(options, self.cache_backend))),
+            ),
         });
 
         client_inner.sync_nodes(&nodes, ignore_node_health).await?;
@@ -330,6 +410,10 @@ impl ClientBuilder {
                     receiver: RwLock::new(mqtt_event_rx),
                 },
                 last_sync: tokio::sync::Mutex::new(None),
+                cache: RwLock::new(
+                    self.cache
+                        .map(|options| Arc::new(Cache::new(options, self.cache_backend))),
+                ),
             }),
         };
 
@@ -348,6 +432,13 @@ impl ClientBuilder {
             pow_worker_count: *client.pow_worker_count.read().await,
             #[cfg(not(target_family = "wasm"))]
             max_parallel_api_requests: client.request_pool.size().await,
+            cache: client.cache.read().await.as_ref().map(|cache| cache.options.clone()),
+            cache_backend: client
+                .cache
+                .read()
+                .await
+                .as_ref()
+                .and_then(|cache| cache.backend.clone()),
         }
     }
 }
@@ -0,0 +1,599 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cache of immutable data requested from the nodes, like blocks, milestones and spent outputs.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::{Debug, Display},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use instant::Instant;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    client::{
+        constants::{DEFAULT_CACHE_CAPACITY, DEFAULT_CACHE_TTL},
+        node_manager::quorum::QuorumEndpoint,
+        storage::StorageAdapter,
+        ClientInner, Error, Result,
+    },
+    utils::unix_timestamp_now,
+};
+
+/// The kinds of data that can be cached. Only data that can't change anymore once it's returned by a node can be
+/// cached, so unspent outputs, output metadata, block metadata and tips are never cached.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CacheEntryKind {
+    /// Blocks by their id.
+    Block,
+    /// Spent outputs with their metadata.
+    SpentOutput,
+    /// Milestones by their index.
+    Milestone,
+    /// The blocks including transactions.
+    IncludedBlock,
+}
+
+impl CacheEntryKind {
+    /// Returns all kinds.
+    pub fn all() -> [Self; 4] {
+        [Self::Block, Self::SpentOutput, Self::Milestone, Self::IncludedBlock]
+    }
+}
+
+/// Options of the cache of immutable data.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheOptions {
+    /// The maximum amount of entries kept in memory, the least recently used ones are evicted first
+    #[serde(default = "default_capacity")]
+    pub capacity: usize,
+    /// How long entries of a kind are kept, kinds without a TTL aren't cached
+    #[serde(default = "default_ttl")]
+    pub ttl: HashMap<CacheEntryKind, Duration>,
+}
+
+fn default_capacity() -> usize {
+    DEFAULT_CACHE_CAPACITY
+}
+
+fn default_ttl() -> HashMap<CacheEntryKind, Duration> {
+    CacheEntryKind::all()
+        .into_iter()
+        .map(|kind| (kind, DEFAULT_CACHE_TTL))
+        .collect()
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CACHE_CAPACITY,
+            ttl: default_ttl(),
+        }
+    }
+}
+
+impl CacheOptions {
+    /// Creates options keeping up to `capacity` entries in memory, caching all kinds with the default TTL.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    /// Sets how long entries of a kind are kept.
+    pub fn with_ttl(mut self, kind: CacheEntryKind, ttl: Duration) -> Self {
+        self.ttl.insert(kind, ttl);
+        self
+    }
+
+    /// Disables the caching of a kind.
+    pub fn without(mut self, kind: CacheEntryKind) -> Self {
+        self.ttl.remove(&kind);
+        self
+    }
+}
+
+/// The lookups of a kind in the cache.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheMetrics {
+    /// The amount of lookups answered from memory.
+    pub memory_hits: u64,
+    /// The amount of lookups answered from the persistent backend.
+    pub persistent_hits: u64,
+    /// The amount of lookups that required a request to a node.
+    pub misses: u64,
+}
+
+impl CacheMetrics {
+    /// Returns the ratio of lookups that didn't require a request, between 0 and 1.
+    pub fn hit_ratio(&self) -> f64 {
+        let hits = self.memory_hits + self.persistent_hits;
+        match hits + self.misses {
+            0 => 0.0,
+            lookups => hits as f64 / lookups as f64,
+        }
+    }
+}
+
+#[async_trait]
+pub(crate) trait DynCacheBackend: Debug + Send + Sync {
+    async fn dyn_get_bytes(&self, key: &str) -> std::result::Result<Option<Vec<u8>>, String>;
+
+    async fn dyn_set_bytes(&self, key: &str, record: &[u8]) -> std::result::Result<(), String>;
+
+    async fn dyn_delete(&self, key: &str) -> std::result::Result<(), String>;
+}
+
+#[async_trait]
+impl<T: StorageAdapter> DynCacheBackend for T
+where
+    T::Error: Display,
+{
+    async fn dyn_get_bytes(&self, key: &str) -> std::result::Result<Option<Vec<u8>>, String> {
+        self.get_bytes(key).await.map_err(|e| e.to_string())
+    }
+
+    async fn dyn_set_bytes(&self, key: &str, record: &[u8]) -> std::result::Result<(), String> {
+        self.set_bytes(key, record).await.map_err(|e| e.to_string())
+    }
+
+    async fn dyn_delete(&self, key: &str) -> std::result::Result<(), String> {
+        self.delete(key).await.map_err(|e| e.to_string())
+    }
+}
+
+/// The persistent backend of the cache, shared by the builder and the client.
+#[derive(Clone, Debug)]
+pub(crate) struct CacheBackend(Arc<dyn DynCacheBackend>);
+
+impl CacheBackend {
+    pub(crate) fn new<T: StorageAdapter + 'static>(adapter: T) -> Self
+    where
+        T::Error: Display,
+    {
+        Self(Arc::new(adapter))
+    }
+}
+
+impl PartialEq for CacheBackend {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CacheBackend {}
+
+// Key of the expiry of every entry in the persistent backend, so expired entries can be deleted without reading them.
+const PERSISTED_EXPIRY_KEY: &str = "cache-expiry";
+
+// Amount of writes to the persistent backend after which the expired entries are deleted and the expiry is persisted.
+const PERSISTED_EXPIRY_WRITE_INTERVAL: usize = 100;
+
+// Entries in the persistent backend keep their expiry, since they outlive the client.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedEntry {
+    /// Unix timestamp in seconds
+    expires_at: u64,
+    value: String,
+}
+
+#[derive(Debug)]
+struct MemoryEntry {
+    value: Vec<u8>,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+// Least recently used cache, `order` maps the last use of every entry to its key.
+#[derive(Debug, Default)]
+struct MemoryCache {
+    entries: HashMap<String, MemoryEntry>,
+    order: BTreeMap<u64, String>,
+    counter: u64,
+}
+
+impl MemoryCache {
+    fn get(&mut self, key: &str) -> Option<Vec<u8>> {
+        let entry = self.entries.get_mut(key)?;
+        if entry.expires_at <= Instant::now() {
+            self.order.remove(&entry.last_used);
+            self.entries.remove(key);
+            return None;
+        }
+        self.order.remove(&entry.last_used);
+        self.counter += 1;
+        entry.last_used = self.counter;
+        self.order.insert(self.counter, key.to_owned());
+        Some(entry.value.clone())
+    }
+
+    fn insert(&mut self, key: String, value: Vec<u8>, ttl: Duration, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        if let Some(entry) = self.entries.remove(&key) {
+            self.order.remove(&entry.last_used);
+        }
+        while self.entries.len() >= capacity {
+            match self.order.pop_first() {
+                Some((_, evicted)) => {
+                    self.entries.remove(&evicted);
+                }
+                None => break,
+            }
+        }
+        self.counter += 1;
+        self.order.insert(self.counter, key.clone());
+        self.entries.insert(
+            key,
+            MemoryEntry {
+                value,
+                expires_at: Instant::now() + ttl,
+                last_used: self.counter,
+            },
+        );
+    }
+}
+
+// The expiry of the persisted entries, indexed by key and by expiry so the expired entries are found without going
+// through all entries.
+#[derive(Debug, Default)]
+struct PersistedExpiry {
+    by_key: HashMap<String, u64>,
+    by_expiry: BTreeSet<(u64, String)>,
+    // Writes since the expired entries were last deleted
+    writes: usize,
+}
+
+impl PersistedExpiry {
+    fn new(by_key: HashMap<String, u64>) -> Self {
+        let by_expiry = by_key
+            .iter()
+            .map(|(key, expires_at)| (*expires_at, key.clone()))
+            .collect();
+        Self {
+            by_key,
+            by_expiry,
+            writes: 0,
+        }
+    }
+
+    fn insert(&mut self, key: String, expires_at: u64) {
+        if let Some(previous) = self.by_key.insert(key.clone(), expires_at) {
+            self.by_expiry.remove(&(previous, key.clone()));
+        }
+        self.by_expiry.insert((expires_at, key));
+    }
+
+    fn remove(&mut self, expires_at: u64, key: &str) {
+        self.by_key.remove(key);
+        self.by_expiry.remove(&(expires_at, key.to_owned()));
+    }
+
+    fn expired(&self, now: u64) -> Vec<(u64, String)> {
+        self.by_expiry
+            .iter()
+            .take_while(|(expires_at, _)| *expires_at <= now)
+            .cloned()
+            .collect()
+    }
+}
+
+/// The cache of immutable data, with an in-memory LRU in front of an optional persistent backend.
+#[derive(Debug)]
+pub(crate) struct Cache {
+    pub(crate) options: CacheOptions,
+    pub(crate) backend: Option<CacheBackend>,
+    memory: Mutex<MemoryCache>,
+    // The expiry of the persisted entries, loaded from the backend on the first write
+    persisted_expiry: tokio::sync::Mutex<Option<PersistedExpiry>>,
+    metrics: Mutex<HashMap<CacheEntryKind, CacheMetrics>>,
+}
+
+impl Cache {
+    pub(crate) fn new(options: CacheOptions, backend: Option<CacheBackend>) -> Self {
+        Self {
+            options,
+            backend,
+            memory: Default::default(),
+            persisted_expiry: Default::default(),
+            metrics: Default::default(),
+        }
+    }
+
+    fn record(&self, kind: CacheEntryKind, f: impl FnOnce(&mut CacheMetrics)) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            f(metrics.entry(kind).or_default());
+        }
+    }
+
+    async fn get(&self, kind: CacheEntryKind, key: &str) -> Option<Vec<u8>> {
+        if let Some(value) = self.memory.lock().ok().and_then(|mut memory| memory.get(key)) {
+            self.record(kind, |metrics| metrics.memory_hits += 1);
+            return Some(value);
+        }
+
+        if let Some(backend) = &self.backend {
+            match backend.0.dyn_get_bytes(key).await {
+                Ok(Some(bytes)) => match serde_json::from_slice::<PersistedEntry>(&bytes) {
+                    Ok(entry) => {
+                        let now = unix_timestamp_now().as_secs();
+                        if entry.expires_at > now {
+                            let value = entry.value.into_bytes();
+                            if let Ok(mut memory) = self.memory.lock() {
+                                memory.insert(
+                                    key.to_owned(),
+                                    value.clone(),
+                                    Duration::from_secs(entry.expires_at - now),
+                                    self.options.capacity,
+                                );
+                            }
+                            self.record(kind, |metrics| metrics.persistent_hits += 1);
+                            return Some(value);
+                        }
+                        backend.0.dyn_delete(key).await.ok();
+                    }
+                    Err(e) => log::warn!("invalid cache entry {key}: {e}"),
+                },
+                Ok(None) => {}
+                Err(e) => log::warn!("failed to read cache entry {key}: {e}"),
+            }
+        }
+
+        self.record(kind, |metrics| metrics.misses += 1);
+        None
+    }
+
+    async fn insert(&self, key: String, value: Vec<u8>, ttl: Duration) {
+        if let Some(backend) = &self.backend {
+            // Values are serialized JSON, so the conversion is lossless
+            let entry = PersistedEntry {
+                expires_at: (unix_timestamp_now() + ttl).as_secs(),
+                value: String::from_utf8_lossy(&value).into_owned(),
+            };
+            match serde_json::to_vec(&entry) {
+                Ok(bytes) => match backend.0.dyn_set_bytes(&key, &bytes).await {
+                    Ok(()) => self.prune_persisted(backend, key.clone(), entry.expires_at).await,
+                    Err(e) => log::warn!("failed to write cache entry {key}: {e}"),
+                },
+                Err(e) => log::warn!("failed to serialize cache entry {key}: {e}"),
+            }
+        }
+
+        if let Ok(mut memory) = self.memory.lock() {
+            memory.insert(key, value, ttl, self.options.capacity);
+        }
+    }
+
+    // Records the expiry of a persisted entry. Every `PERSISTED_EXPIRY_WRITE_INTERVAL` writes the expired entries are
+    // deleted from the backend, so it doesn't grow with entries that are never read again, and the expiry is
+    // persisted. The expiry of the last writes is lost if the client is dropped before, these entries are still deleted
+    // once they are read after they expired.
+    async fn prune_persisted(&self, backend: &CacheBackend, key: String, expires_at: u64) {
+        let mut persisted_expiry = self.persisted_expiry.lock().await;
+        let persisted_expiry = match &mut *persisted_expiry {
+            Some(persisted_expiry) => persisted_expiry,
+            None => {
+                let loaded = match backend.0.dyn_get_bytes(PERSISTED_EXPIRY_KEY).await {
+                    Ok(Some(bytes)) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                        log::warn!("invalid cache expiry: {e}");
+                        HashMap::new()
+                    }),
+                    Ok(None) => HashMap::new(),
+                    Err(e) => {
+                        log::warn!("failed to read cache expiry: {e}");
+                        HashMap::new()
+                    }
+                };
+                persisted_expiry.insert(PersistedExpiry::new(loaded))
+            }
+        };
+
+        persisted_expiry.insert(key, expires_at);
+        persisted_expiry.writes += 1;
+        if persisted_expiry.writes < PERSISTED_EXPIRY_WRITE_INTERVAL {
+            return;
+        }
+        persisted_expiry.writes = 0;
+
+        let now = unix_timestamp_now().as_secs();
+        for (expires_at, expired) in persisted_expiry.expired(now) {
+            match backend.0.dyn_delete(&expired).await {
+                Ok(()) => {
+                    persisted_expiry.remove(expires_at, &expired);
+                }
+                Err(e) => log::warn!("failed to delete cache entry {expired}: {e}"),
+            }
+        }
+
+        match serde_json::to_vec(&persisted_expiry.by_key) {
+            Ok(bytes) => {
+                if let Err(e) = backend.0.dyn_set_bytes(PERSISTED_EXPIRY_KEY, &bytes).await {
+                    log::warn!("failed to write cache expiry: {e}");
+                }
+            }
+            Err(e) => log::warn!("failed to serialize cache expiry: {e}"),
+        }
+    }
+}
+
+impl ClientInner {
+    /// Sends a GET request for immutable data, answering it from the cache if possible. The response is only cached if
+    /// `is_immutable` returns true for it.
+    pub(crate) async fn get_request_cached<T: DeserializeOwned + Debug + Serialize>(
+        &self,
+        kind: CacheEntryKind,
+        path: &str,
+        quorum_endpoint: Option<QuorumEndpoint>,
+        is_immutable: impl Fn(&T) -> bool + Send,
+    ) -> Result<T> {
+        let cache = self.cache.read().await.clone();
+        let Some((cache, ttl)) = cache.and_then(|cache| cache.options.ttl.get(&kind).copied().map(|ttl| (cache, ttl)))
+        else {
+            return self.get_request(path, None, quorum_endpoint, false).await;
+        };
+
+        // Milestone indexes are only unique within a network
+        let key = format!(
+            "cache-{}/{path}",
+            self.network_info.read().await.protocol_parameters.network_id()
+        );
+
+        if let Some(value) = cache.get(kind, &key).await {
+            match serde_json::from_slice(&value) {
+                Ok(value) => return Ok(value),
+                Err(e) => log::warn!("invalid cache entry {key}: {e}"),
+            }
+        }
+
+        let value: T = self.get_request(path, None, quorum_endpoint, false).await?;
+        if is_immutable(&value) {
+            cache.insert(key, serde_json::to_vec(&value)?, ttl).await;
+        }
+
+        Ok(value)
+    }
+
+    /// Returns the hits and misses of the cache per kind, empty if the cache isn't enabled.
+    pub async fn cache_metrics(&self) -> Result<HashMap<CacheEntryKind, CacheMetrics>> {
+        match self.cache.read().await.as_ref() {
+            Some(cache) => Ok(cache.metrics.lock().map_err(|_| Error::PoisonError)?.clone()),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    /// Removes all entries from the in-memory cache, the persistent backend is kept.
+    pub async fn clear_cache(&self) -> Result<()> {
+        if let Some(cache) = self.cache.read().await.as_ref() {
+            *cache.memory.lock().map_err(|_| Error::PoisonError)? = MemoryCache::default();
+        }
+        Ok(())
+    }
+
+    /// Replaces the cache if its options or backend changed. The current backend is kept if no new one is provided.
+    pub(crate) async fn update_cache(&self, options: Option<CacheOptions>, backend: Option<CacheBackend>) {
+        let mut cache = self.cache.write().await;
+        let backend = backend.or_else(|| cache.as_ref().and_then(|cache| cache.backend.clone()));
+        let unchanged = match (cache.as_ref(), &options) {
+            (Some(cache), Some(options)) => &cache.options == options && cache.backend == backend,
+            (None, None) => true,
+            _ => false,
+        };
+        if !unchanged {
+            *cache = options.map(|options| Arc::new(Cache::new(options, backend)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_cache_evicts_least_recently_used() {
+        let mut memory = MemoryCache::default();
+        let ttl = Duration::from_secs(60);
+        memory.insert("a".to_owned(), vec![1], ttl, 2);
+        memory.insert("b".to_owned(), vec![2], ttl, 2);
+        assert_eq!(memory.get("a"), Some(vec![1]));
+        memory.insert("c".to_owned(), vec![3], ttl, 2);
+
+        assert_eq!(memory.get("b"), None);
+        assert_eq!(memory.get("a"), Some(vec![1]));
+        assert_eq!(memory.get("c"), Some(vec![3]));
+    }
+
+    #[test]
+    fn memory_cache_expires_entries() {
+        let mut memory = MemoryCache::default();
+        memory.insert("a".to_owned(), vec![1], Duration::ZERO, 2);
+
+        assert_eq!(memory.get("a"), None);
+        assert!(memory.entries.is_empty() && memory.order.is_empty());
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct TestBackend {
+        entries: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+        expiry_writes: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait]
+    impl StorageAdapter for TestBackend {
+        type Error = std::convert::Infallible;
+
+        async fn get_bytes(&self, key: &str) -> std::result::Result<Option<Vec<u8>>, Self::Error> {
+            Ok(self.entries.lock().unwrap().get(key).cloned())
+        }
+
+        async fn set_bytes(&self, key: &str, record: &[u8]) -> std::result::Result<(), Self::Error> {
+            if key == PERSISTED_EXPIRY_KEY {
+                *self.expiry_writes.lock().unwrap() += 1;
+            }
+            self.entries.lock().unwrap().insert(key.to_owned(), record.to_vec());
+            Ok(())
+        }
+
+        async fn delete(&self, key: &str) -> std::result::Result<(), Self::Error> {
+            self.entries.lock().unwrap().remove(key);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn persisted_entries_expire_on_write() {
+        let backend = TestBackend::default();
+        let cache = Cache::new(CacheOptions::default(), Some(CacheBackend::new(backend.clone())));
+
+        cache.insert("a".to_owned(), b"1".to_vec(), Duration::ZERO).await;
+        for i in 1..PERSISTED_EXPIRY_WRITE_INTERVAL {
+            cache
+                .insert(i.to_string(), b"2".to_vec(), Duration::from_secs(60))
+                .await;
+        }
+        assert!(!backend.entries.lock().unwrap().contains_key("a"));
+        assert!(backend.entries.lock().unwrap().contains_key("1"));
+
+        // The expiry of the entries is loaded again by a new cache with the same backend
+        let cache = Cache::new(CacheOptions::default(), Some(CacheBackend::new(backend.clone())));
+        cache.insert("c".to_owned(), b"3".to_vec(), Duration::ZERO).await;
+        let persisted_expiry = cache.persisted_expiry.lock().await;
+        let persisted_expiry = persisted_expiry.as_ref().unwrap();
+        assert!(persisted_expiry.by_key.contains_key("1") && persisted_expiry.by_key.contains_key("c"));
+        assert!(!persisted_expiry.by_key.contains_key("a"));
+        assert_eq!(
+            persisted_expiry.expired(u64::MAX).len(),
+            PERSISTED_EXPIRY_WRITE_INTERVAL
+        );
+        assert_eq!(cache.get(CacheEntryKind::Block, "1").await, Some(b"2".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn persisted_expiry_is_written_in_batches() {
+        let backend = TestBackend::default();
+        let cache = Cache::new(CacheOptions::new(10), Some(CacheBackend::new(backend.clone())));
+
+        for i in 0..10 * PERSISTED_EXPIRY_WRITE_INTERVAL {
+            // Every other entry is expired right away
+            let ttl = Duration::from_secs((i % 2 * 60) as u64);
+            cache.insert(i.to_string(), b"1".to_vec(), ttl).await;
+        }
+
+        assert_eq!(*backend.expiry_writes.lock().unwrap(), 10);
+        let entries = backend.entries.lock().unwrap();
+        // The expired entries are deleted, the others are kept together with the expiry
+        assert_eq!(entries.len(), 5 * PERSISTED_EXPIRY_WRITE_INTERVAL + 1);
+        assert!((0..10 * PERSISTED_EXPIRY_WRITE_INTERVAL).all(|i| entries.contains_key(&i.to_string()) == (i % 2 == 1)));
+        let persisted_expiry: HashMap<String, u64> = serde_json::from_slice(&entries[PERSISTED_EXPIRY_KEY]).unwrap();
+        assert_eq!(persisted_expiry.len(), 5 * PERSISTED_EXPIRY_WRITE_INTERVAL);
+    }
+}
@@ -19,6 +19,28 @@ pub(crate) const DEFAULT_TIPS_INTERVAL: u64 = 5;
 pub(crate) const NODE_SYNC_INTERVAL: Duration = Duration::from_secs(60);
 pub(crate) const DEFAULT_MIN_QUORUM_SIZE: usize = 3;
 pub(crate) const DEFAULT_QUORUM_THRESHOLD: usize = 66;
+/// Weight of the latest request in the moving averages of the node scores
+pub(crate) const NODE_SCORE_SMOOTHING_FACTOR: f64 = 0.2;
+/// Nodes failing more requests than this rate are only used when no other node is available
+pub(crate) const MAX_NODE_ERROR_RATE: f64 = 0.5;
+/// Nodes more milestones behind the most synced node are only used when no other node is available
+pub(crate) const MAX_NODE_MILESTONE_LAG: u32 = 5;
+/// Requests are only sent once by default, to all available nodes
+pub(crate) const DEFAULT_RETRY_MAX_ATTEMPTS: u32 = 1;
+/// Delay before the first retry of a request
+pub(crate) const DEFAULT_RETRY_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// Maximum delay between two attempts of a request
+pub(crate) const DEFAULT_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(10);
+/// % by which the delay between two attempts is randomly varied
+pub(crate) const DEFAULT_RETRY_JITTER: usize = 20;
+/// Consecutive failed requests after which a node is skipped
+pub(crate) const DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD: u32 = 5;
+/// Duration a failing node is skipped for, before it's probed again
+pub(crate) const DEFAULT_CIRCUIT_BREAKER_COOL_DOWN: Duration = Duration::from_secs(30);
+/// Maximum amount of entries kept in the in-memory cache
+pub(crate) const DEFAULT_CACHE_CAPACITY: usize = 1000;
+/// Duration cached entries are kept for
+pub(crate) const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
 pub(crate) const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 #[cfg(not(target_family = "wasm"))]
 pub(crate) const MAX_PARALLEL_API_REQUESTS: usize = 100;
@@ -20,6 +20,7 @@ use crate::client::constants::CACHE_NETWORK_INFO_TIMEOUT_IN_SECONDS;
 use crate::{
     client::{
         builder::{ClientBuilder, NetworkInfo},
+        cache::Cache,
         error::Result,
         node_manager::NodeManager,
         Error,
@@ -60,6 +61,8 @@ pub struct ClientInner {
     pub(crate) last_sync: tokio::sync::Mutex<Option<u32>>,
     #[cfg(not(target_family = "wasm"))]
     pub(crate) request_pool: RequestPool,
+    /// Cache of immutable data, if enabled.
+    pub(crate) cache: RwLock<Option<Arc<Cache>>>,
 }
 
 #[derive(Default)]
@@ -12,7 +12,10 @@ use serde::{
 };
 
 use crate::{
-    client::{api::input_selection::Error as InputSelectionError, node_api::indexer::QueryParameter},
+    client::{
+        api::input_selection::Error as InputSelectionError, node_api::indexer::QueryParameter,
+        node_manager::quorum::QuorumReport,
+    },
     types::block::semantic::ConflictReason,
 };
 
@@ -109,11 +112,14 @@ pub enum Error {
     },
     /// Error on reaching quorum
     #[error("failed to reach quorum: {quorum_size} < {minimum_threshold}")]
+    #[non_exhaustive]
     QuorumThresholdError {
         /// The current quorum size.
         quorum_size: usize,
         /// The minimum quorum threshold.
         minimum_threshold: usize,
+        /// The responses of the nodes.
+        report: QuorumReport,
     },
     /// Specifically used for `TryInfo` implementations for `SecretManager`.
     #[error("cannot unwrap a SecretManager: type mismatch!")]
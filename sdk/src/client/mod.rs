@@ -37,6 +37,7 @@ macro_rules! lazy_static {
 
 pub mod api;
 pub mod builder;
+pub mod cache;
 pub mod constants;
 pub mod core;
 pub mod error;
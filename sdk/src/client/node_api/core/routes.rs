@@ -9,8 +9,13 @@ use url::Url;
 
 use crate::{
     client::{
+        cache::CacheEntryKind,
         constants::{DEFAULT_API_TIMEOUT, DEFAULT_USER_AGENT},
-        node_manager::node::{Node, NodeAuth},
+        node_manager::{
+            http_client::HttpClient,
+            node::{Node, NodeAuth},
+            quorum::QuorumEndpoint,
+        },
         Client, ClientInner, Error, Result,
     },
     types::{
@@ -53,7 +58,8 @@ impl ClientInner {
 
         let mut url = Url::parse(url)?;
         url.set_path(path);
-        let status = crate::client::node_manager::http_client::HttpClient::new(DEFAULT_USER_AGENT.to_string())
+        let http_client = self.node_manager.read().await.http_client.clone();
+        let status = http_client
             .get(
                 Node {
                     url,
@@ -76,13 +82,21 @@ impl ClientInner {
     pub async fn get_routes(&self) -> Result<RoutesResponse> {
         let path = "api/routes";
 
-        self.get_request(path, None, false, false).await
+        self.get_request(path, None, None, false).await
     }
 
     /// Returns general information about the node.
     /// GET /api/core/v2/info
     pub async fn get_info(&self) -> Result<NodeInfoWrapper> {
-        self.get_request(INFO_PATH, None, false, false).await
+        self.get_request(INFO_PATH, None, None, false).await
+    }
+
+    /// Returns general information about a node, which doesn't need to be one of the nodes of the client. The request
+    /// is sent with the transport of the client.
+    /// GET /api/core/v2/info
+    pub async fn get_info_from(&self, url: &str, auth: Option<NodeAuth>) -> Result<InfoResponse> {
+        let http_client = self.node_manager.read().await.http_client.clone();
+        Client::get_node_info_with_client(&http_client, url, auth).await
     }
 
     // Tangle routes.
@@ -92,7 +106,7 @@ impl ClientInner {
     pub async fn get_tips(&self) -> Result<Vec<BlockId>> {
         let path = "api/core/v2/tips";
 
-        let response = self.get_request::<TipsResponse>(path, None, false, false).await?;
+        let response = self.get_request::<TipsResponse>(path, None, None, false).await?;
 
         Ok(response.tips)
     }
@@ -211,7 +225,9 @@ impl ClientInner {
     pub async fn get_block(&self, block_id: &BlockId) -> Result<Block> {
         let path = &format!("api/core/v2/blocks/{block_id}");
 
-        let dto = self.get_request::<BlockDto>(path, None, false, true).await?;
+        let dto = self
+            .get_request_cached::<BlockDto>(CacheEntryKind::Block, path, Some(QuorumEndpoint::Blocks), |_| true)
+            .await?;
 
         Ok(Block::try_from_dto_with_params(
             dto,
@@ -232,7 +248,7 @@ impl ClientInner {
     pub async fn get_block_metadata(&self, block_id: &BlockId) -> Result<BlockMetadataResponse> {
         let path = &format!("api/core/v2/blocks/{block_id}/metadata");
 
-        self.get_request(path, None, true, true).await
+        self.get_request(path, None, Some(QuorumEndpoint::Blocks), false).await
     }
 
     // UTXO routes.
@@ -242,7 +258,15 @@ impl ClientInner {
     pub async fn get_output(&self, output_id: &OutputId) -> Result<OutputWithMetadata> {
         let path = &format!("api/core/v2/outputs/{output_id}");
 
-        let response: OutputWithMetadataResponse = self.get_request(path, None, false, true).await?;
+        // Unspent outputs are never cached, since their metadata changes once they're spent
+        let response: OutputWithMetadataResponse = self
+            .get_request_cached(
+                CacheEntryKind::SpentOutput,
+                path,
+                Some(QuorumEndpoint::Outputs),
+                |response: &OutputWithMetadataResponse| response.metadata.is_spent(),
+            )
+            .await?;
 
         let token_supply = self.get_token_supply().await?;
         let output = Output::try_from_dto_with_params(response.output, token_supply)?;
@@ -263,7 +287,8 @@ impl ClientInner {
     pub async fn get_output_metadata(&self, output_id: &OutputId) -> Result<OutputMetadata> {
         let path = &format!("api/core/v2/outputs/{output_id}/metadata");
 
-        self.get_request::<OutputMetadata>(path, None, false, true).await
+        self.get_request::<OutputMetadata>(path, None, Some(QuorumEndpoint::OutputMetadata), false)
+            .await
     }
 
     /// Gets all stored receipts.
@@ -271,7 +296,7 @@ impl ClientInner {
     pub async fn get_receipts(&self) -> Result<Vec<ReceiptResponse>> {
         let path = &"api/core/v2/receipts";
 
-        let resp = self.get_request::<ReceiptsResponse>(path, None, false, false).await?;
+        let resp = self.get_request::<ReceiptsResponse>(path, None, None, false).await?;
 
         Ok(resp.receipts)
     }
@@ -281,7 +306,7 @@ impl ClientInner {
     pub async fn get_receipts_migrated_at(&self, milestone_index: u32) -> Result<Vec<ReceiptResponse>> {
         let path = &format!("api/core/v2/receipts/{milestone_index}");
 
-        let resp = self.get_request::<ReceiptsResponse>(path, None, false, false).await?;
+        let resp = self.get_request::<ReceiptsResponse>(path, None, None, false).await?;
 
         Ok(resp.receipts)
     }
@@ -292,7 +317,7 @@ impl ClientInner {
     pub async fn get_treasury(&self) -> Result<TreasuryResponse> {
         let path = "api/core/v2/treasury";
 
-        self.get_request(path, None, false, false).await
+        self.get_request(path, None, None, false).await
     }
 
     /// Returns the block, as object, that was included in the ledger for a given TransactionId.
@@ -300,7 +325,14 @@ impl ClientInner {
     pub async fn get_included_block(&self, transaction_id: &TransactionId) -> Result<Block> {
         let path = &format!("api/core/v2/transactions/{transaction_id}/included-block");
 
-        let dto = self.get_request::<BlockDto>(path, None, true, true).await?;
+        let dto = self
+            .get_request_cached::<BlockDto>(
+                CacheEntryKind::IncludedBlock,
+                path,
+                Some(QuorumEndpoint::Blocks),
+                |_| true,
+            )
+            .await?;
 
         Ok(Block::try_from_dto_with_params(
             dto,
@@ -321,7 +353,7 @@ impl ClientInner {
     pub async fn get_included_block_metadata(&self, transaction_id: &TransactionId) -> Result<BlockMetadataResponse> {
         let path = &format!("api/core/v2/transactions/{transaction_id}/included-block/metadata");
 
-        self.get_request(path, None, true, true).await
+        self.get_request(path, None, Some(QuorumEndpoint::Blocks), false).await
     }
 
     // Milestones routes.
@@ -331,7 +363,9 @@ impl ClientInner {
     pub async fn get_milestone_by_id(&self, milestone_id: &MilestoneId) -> Result<MilestonePayload> {
         let path = &format!("api/core/v2/milestones/{milestone_id}");
 
-        let dto = self.get_request::<MilestonePayloadDto>(path, None, false, true).await?;
+        let dto = self
+            .get_request::<MilestonePayloadDto>(path, None, Some(QuorumEndpoint::Milestones), false)
+            .await?;
 
         Ok(MilestonePayload::try_from_dto_with_params(
             dto,
@@ -352,7 +386,8 @@ impl ClientInner {
     pub async fn get_utxo_changes_by_id(&self, milestone_id: &MilestoneId) -> Result<UtxoChangesResponse> {
         let path = &format!("api/core/v2/milestones/{milestone_id}/utxo-changes");
 
-        self.get_request(path, None, false, false).await
+        self.get_request(path, None, Some(QuorumEndpoint::Milestones), false)
+            .await
     }
 
     /// Gets the milestone by the given milestone index.
@@ -360,7 +395,14 @@ impl ClientInner {
     pub async fn get_milestone_by_index(&self, index: u32) -> Result<MilestonePayload> {
         let path = &format!("api/core/v2/milestones/by-index/{index}");
 
-        let dto = self.get_request::<MilestonePayloadDto>(path, None, false, true).await?;
+        let dto = self
+            .get_request_cached::<MilestonePayloadDto>(
+                CacheEntryKind::Milestone,
+                path,
+                Some(QuorumEndpoint::Milestones),
+                |_| true,
+            )
+            .await?;
 
         Ok(MilestonePayload::try_from_dto_with_params(
             dto,
@@ -381,7 +423,8 @@ impl ClientInner {
     pub async fn get_utxo_changes_by_index(&self, index: u32) -> Result<UtxoChangesResponse> {
         let path = &format!("api/core/v2/milestones/by-index/{index}/utxo-changes");
 
-        self.get_request(path, None, false, false).await
+        self.get_request(path, None, Some(QuorumEndpoint::Milestones), false)
+            .await
     }
 
     // Peers routes.
@@ -390,7 +433,7 @@ impl ClientInner {
     pub async fn get_peers(&self) -> Result<Vec<PeerResponse>> {
         let path = "api/core/v2/peers";
 
-        let resp = self.get_request::<Vec<PeerResponse>>(path, None, false, false).await?;
+        let resp = self.get_request::<Vec<PeerResponse>>(path, None, None, false).await?;
 
         Ok(resp)
     }
@@ -418,7 +461,21 @@ impl ClientInner {
 
 impl Client {
     /// GET /api/core/v2/info endpoint
+    /// The request is sent with the default transport, a custom transport and the retry policy of a client aren't used.
+    #[deprecated(
+        since = "1.1.0",
+        note = "use `get_info_from()` to send the request with the transport of the client"
+    )]
     pub async fn get_node_info(url: &str, auth: Option<NodeAuth>) -> Result<InfoResponse> {
+        Self::get_node_info_with_client(&HttpClient::new(DEFAULT_USER_AGENT.to_string()), url, auth).await
+    }
+
+    // Requests the info of a node with the transport of a client.
+    pub(crate) async fn get_node_info_with_client(
+        http_client: &HttpClient,
+        url: &str,
+        auth: Option<NodeAuth>,
+    ) -> Result<InfoResponse> {
         let mut url = crate::client::node_manager::builder::validate_url(Url::parse(url)?)?;
         if let Some(auth) = &auth {
             if let Some((name, password)) = &auth.basic_auth_name_pwd {
@@ -431,19 +488,18 @@ impl Client {
         let path = "api/core/v2/info";
         url.set_path(path);
 
-        let resp: InfoResponse =
-            crate::client::node_manager::http_client::HttpClient::new(DEFAULT_USER_AGENT.to_string())
-                .get(
-                    Node {
-                        url,
-                        auth,
-                        disabled: false,
-                    },
-                    DEFAULT_API_TIMEOUT,
-                )
-                .await?
-                .into_json()
-                .await?;
+        let resp: InfoResponse = http_client
+            .get(
+                Node {
+                    url,
+                    auth,
+                    disabled: false,
+                },
+                DEFAULT_API_TIMEOUT,
+            )
+            .await?
+            .into_json()
+            .await?;
 
         Ok(resp)
     }
@@ -1,6 +1,8 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
 /// Type alias of `Result` in Node errors
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -13,8 +15,18 @@ pub enum Error {
     /// Reqwest error
     #[error("{0}")]
     Reqwest(#[from] reqwest::Error),
+    /// Error of a custom HTTP transport
+    #[error("transport error: {0}")]
+    Transport(String),
+    /// Error of the auth provider
+    #[error("auth error: {0}")]
+    Auth(String),
+    /// The response of a node isn't valid JSON
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
     /// Error from RestAPI calls with unexpected status code response
     #[error("Response error with status code {code}: {text}, URL: {url}")]
+    #[non_exhaustive]
     ResponseError {
         /// The status code.
         code: u16,
@@ -22,6 +34,8 @@ pub enum Error {
         text: String,
         /// The url of the API.
         url: String,
+        /// The delay the node asked for before retrying, from the `Retry-After` header.
+        retry_after: Option<Duration>,
     },
     /// None of our nodes have remote Pow enabled
     #[error("No node available for remote Pow")]
@@ -30,3 +44,13 @@ pub enum Error {
     #[error("Call to {0} is not supported on this node")]
     NotSupported(String),
 }
+
+impl Error {
+    /// Returns the delay a node asked for before retrying, from the `Retry-After` header of a response error.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::ResponseError { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
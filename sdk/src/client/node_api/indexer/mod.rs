@@ -6,10 +6,15 @@
 pub mod query_parameters;
 pub mod routes;
 
+use futures::{Stream, StreamExt, TryStreamExt};
+
 pub(crate) use self::query_parameters::{QueryParameter, QueryParameters};
 use crate::{
-    client::{ClientInner, Result},
-    types::api::plugins::indexer::OutputIdsResponse,
+    client::{node_manager::quorum::QuorumEndpoint, ClientInner, Error, Result},
+    types::{
+        api::plugins::indexer::OutputIdsResponse,
+        block::output::{OutputId, OutputWithMetadata},
+    },
 };
 
 impl ClientInner {
@@ -36,7 +41,7 @@ impl ClientInner {
                 .get_request::<OutputIdsResponse>(
                     route,
                     query_parameters.to_query_string().as_deref(),
-                    need_quorum,
+                    need_quorum.then_some(QuorumEndpoint::Indexer),
                     prefer_permanode,
                 )
                 .await?;
@@ -56,4 +61,66 @@ impl ClientInner {
 
         Ok(merged_output_ids_response)
     }
+
+    /// Streams the output ids for a provided URL route and query parameters page by page, following the cursors. The
+    /// next page is only requested once the previous one was consumed, so the pages can be processed with bounded
+    /// memory. The amount of output ids per page can be set with `QueryParameter::PageSize`, a provided
+    /// `QueryParameter::Cursor` is the first page to request.
+    pub fn output_ids_stream(
+        &self,
+        route: impl Into<String>,
+        query_parameters: QueryParameters,
+    ) -> impl Stream<Item = Result<OutputIdsResponse>> + '_ {
+        let route = route.into();
+
+        futures::stream::try_unfold(Some(query_parameters), move |query_parameters| {
+            let route = route.clone();
+            async move {
+                let mut query_parameters = match query_parameters {
+                    Some(query_parameters) => query_parameters,
+                    // The previous page was the last one
+                    None => return Ok(None),
+                };
+
+                let output_ids_response = self
+                    .get_request::<OutputIdsResponse>(
+                        &route,
+                        query_parameters.to_query_string().as_deref(),
+                        Some(QuorumEndpoint::Indexer),
+                        false,
+                    )
+                    .await?;
+
+                let next_query_parameters = output_ids_response.cursor.as_ref().map(|cursor| {
+                    query_parameters.replace(QueryParameter::Cursor(cursor.to_string()));
+                    query_parameters
+                });
+
+                Ok::<_, Error>(Some((output_ids_response, next_query_parameters)))
+            }
+        })
+    }
+
+    /// Requests the outputs of a stream of output id pages, with up to `max_parallel_requests` outputs requested
+    /// concurrently. Only as many pages are requested as needed to keep the requests going, so processing can start
+    /// before all output ids are known.
+    /// ```ignore
+    /// let output_ids = client.basic_output_ids_stream([QueryParameter::PageSize(1000)])?;
+    /// let outputs = client.get_outputs_stream(output_ids, 50);
+    /// futures::pin_mut!(outputs);
+    /// while let Some(output) = outputs.try_next().await? {
+    ///     println!("{}", output.metadata().output_id());
+    /// }
+    /// ```
+    pub fn get_outputs_stream<'a>(
+        &'a self,
+        output_ids: impl Stream<Item = Result<OutputIdsResponse>> + 'a,
+        max_parallel_requests: usize,
+    ) -> impl Stream<Item = Result<OutputWithMetadata>> + 'a {
+        output_ids
+            .map_ok(|page| futures::stream::iter(page.items.into_iter().map(Ok)))
+            .try_flatten()
+            .map(move |output_id: Result<OutputId>| async move { self.get_output(&output_id?).await })
+            .buffered(max_parallel_requests.max(1))
+    }
 }
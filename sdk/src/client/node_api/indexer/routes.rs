@@ -3,6 +3,8 @@
 
 //! IOTA node indexer routes
 
+use futures::Stream;
+
 use crate::{
     client::{
         node_api::indexer::{
@@ -41,6 +43,18 @@ impl ClientInner {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Streams the basic output ids filtered by the given parameters page by page, see
+    /// [`ClientInner::basic_output_ids()`] for the query parameters and [`ClientInner::output_ids_stream()`] for the
+    /// pagination.
+    pub fn basic_output_ids_stream(
+        &self,
+        query_parameters: impl Into<Vec<QueryParameter>> + Send,
+    ) -> Result<impl Stream<Item = Result<OutputIdsResponse>> + '_> {
+        let query_parameters = verify_query_parameters_basic_outputs(query_parameters.into())?;
+
+        Ok(self.output_ids_stream("api/indexer/v1/outputs/basic", query_parameters))
+    }
+
     /// Get alias outputs filtered by the given parameters.
     /// GET with query parameter returns all outputIDs that fit these filter criteria.
     /// Query parameters: "stateController", "governor", "issuer", "sender", "createdBefore", "createdAfter"
@@ -57,6 +71,18 @@ impl ClientInner {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Streams the alias output ids filtered by the given parameters page by page, see
+    /// [`ClientInner::alias_output_ids()`] for the query parameters and [`ClientInner::output_ids_stream()`] for the
+    /// pagination.
+    pub fn alias_output_ids_stream(
+        &self,
+        query_parameters: impl Into<Vec<QueryParameter>> + Send,
+    ) -> Result<impl Stream<Item = Result<OutputIdsResponse>> + '_> {
+        let query_parameters = verify_query_parameters_alias_outputs(query_parameters.into())?;
+
+        Ok(self.output_ids_stream("api/indexer/v1/outputs/alias", query_parameters))
+    }
+
     /// Get alias output by its aliasID.
     /// api/indexer/v1/outputs/alias/:{AliasId}
     pub async fn alias_output_id(&self, alias_id: AliasId) -> Result<OutputId> {
@@ -85,6 +111,18 @@ impl ClientInner {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Streams the foundry output ids filtered by the given parameters page by page, see
+    /// [`ClientInner::foundry_output_ids()`] for the query parameters and [`ClientInner::output_ids_stream()`] for the
+    /// pagination.
+    pub fn foundry_output_ids_stream(
+        &self,
+        query_parameters: impl Into<Vec<QueryParameter>> + Send,
+    ) -> Result<impl Stream<Item = Result<OutputIdsResponse>> + '_> {
+        let query_parameters = verify_query_parameters_foundry_outputs(query_parameters.into())?;
+
+        Ok(self.output_ids_stream("api/indexer/v1/outputs/foundry", query_parameters))
+    }
+
     /// Get foundry output by its foundryID.
     /// api/indexer/v1/outputs/foundry/:{FoundryID}
     pub async fn foundry_output_id(&self, foundry_id: FoundryId) -> Result<OutputId> {
@@ -114,6 +152,18 @@ impl ClientInner {
         self.get_output_ids(route, query_parameters, true, false).await
     }
 
+    /// Streams the NFT output ids filtered by the given parameters page by page, see
+    /// [`ClientInner::nft_output_ids()`] for the query parameters and [`ClientInner::output_ids_stream()`] for the
+    /// pagination.
+    pub fn nft_output_ids_stream(
+        &self,
+        query_parameters: impl Into<Vec<QueryParameter>> + Send,
+    ) -> Result<impl Stream<Item = Result<OutputIdsResponse>> + '_> {
+        let query_parameters = verify_query_parameters_nft_outputs(query_parameters.into())?;
+
+        Ok(self.output_ids_stream("api/indexer/v1/outputs/nft", query_parameters))
+    }
+
     /// Get NFT output by its nftID.
     /// api/indexer/v1/outputs/nft/:{NftId}
     pub async fn nft_output_id(&self, nft_id: NftId) -> Result<OutputId> {
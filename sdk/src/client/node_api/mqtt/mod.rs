@@ -11,12 +11,18 @@ use std::{sync::Arc, time::Instant};
 use crypto::utils;
 use log::warn;
 use packable::PackableExt;
-use rumqttc::{AsyncClient, Event, EventLoop, Incoming, MqttOptions, NetworkOptions, QoS, SubscribeFilter, Transport};
+use rumqttc::{
+    AsyncClient, ConnectReturnCode, ConnectionError, Event, EventLoop, Incoming, MqttOptions, NetworkOptions, QoS,
+    SubscribeFilter, Transport,
+};
 use tokio::sync::watch::Receiver as WatchReceiver;
 
 pub use self::{error::Error, types::*};
 use crate::{
-    client::{Client, ClientInner},
+    client::{
+        node_manager::{auth::SharedAuthProvider, node::Node},
+        Client, ClientInner,
+    },
     types::block::{
         payload::{milestone::ReceiptMilestoneOption, Payload},
         Block,
@@ -85,7 +91,7 @@ async fn set_mqtt_client(client: &Client) -> Result<(), Error> {
             let broker_options = client.mqtt.broker_options.read().await;
             let port = broker_options.port;
             let secure = node.url.scheme() == "https";
-            let mqtt_options = if broker_options.use_ws {
+            let mut mqtt_options = if broker_options.use_ws {
                 let uri = format!(
                     "{}://{host}:{}/api/mqtt/v1",
                     if secure { "wss" } else { "ws" },
@@ -106,32 +112,110 @@ async fn set_mqtt_client(client: &Client) -> Result<(), Error> {
                 }
                 mqtt_options
             };
-            let (_, mut connection) = AsyncClient::new(mqtt_options.clone(), 10);
-            connection
-                .set_network_options(*NetworkOptions::new().set_connection_timeout(broker_options.timeout.as_secs()));
-            // poll the event loop until we find a ConnAck event,
-            // which means that the mqtt client is ready to be used on this host
-            // if the event loop returns an error, we check the next node
+            let auth_provider = node_manager.http_client.auth_provider.as_ref();
             let mut got_ack = false;
-            while let Ok(event) = connection.poll().await {
-                if let Event::Incoming(Incoming::ConnAck(_)) = event {
-                    got_ack = true;
-                    break;
+            // A second attempt is made if the auth provider refreshed rejected credentials
+            for _ in 0..2 {
+                if let Some(auth_provider) = auth_provider {
+                    if let Err(e) = set_credentials(&mut mqtt_options, node, auth_provider).await {
+                        warn!("Couldn't get the credentials for {}: {e}", node.url);
+                        break;
+                    }
                 }
+
+                let (_, mut connection) = AsyncClient::new(mqtt_options.clone(), 10);
+                connection.set_network_options(
+                    *NetworkOptions::new().set_connection_timeout(broker_options.timeout.as_secs()),
+                );
+                // poll the event loop until we find a ConnAck event,
+                // which means that the mqtt client is ready to be used on this host
+                // if the event loop returns an error, we check the next node
+                let error = loop {
+                    match connection.poll().await {
+                        Ok(Event::Incoming(Incoming::ConnAck(_))) => {
+                            got_ack = true;
+                            break None;
+                        }
+                        Ok(_) => {}
+                        Err(e) => break Some(e),
+                    }
+                };
+                match (error, auth_provider) {
+                    (Some(error), Some(auth_provider)) if is_unauthorized(&error) => {
+                        match auth_provider.on_unauthorized(&node.url).await {
+                            Ok(true) => continue,
+                            Ok(false) => {}
+                            Err(e) => warn!("Couldn't refresh the credentials for {}: {e}", node.url),
+                        }
+                    }
+                    _ => {}
+                }
+                break;
             }
 
             // if we found a valid mqtt connection, loop it on a separate thread
             if got_ack {
-                let (mqtt_client, connection) = AsyncClient::new(mqtt_options, 10);
+                let (mqtt_client, connection) = AsyncClient::new(mqtt_options.clone(), 10);
                 client.mqtt.client.write().await.replace(mqtt_client);
-                poll_mqtt(client, connection);
+                poll_mqtt(client, connection, node.clone(), mqtt_options);
             }
         }
     }
     Ok(())
 }
 
-fn poll_mqtt(client: &Client, mut event_loop: EventLoop) {
+// Sets the credentials of the auth provider for a connection to a node. Without an auth provider no credentials are
+// sent to the broker.
+async fn set_credentials(
+    mqtt_options: &mut MqttOptions,
+    node: &Node,
+    auth_provider: &SharedAuthProvider,
+) -> crate::client::node_api::error::Result<()> {
+    if let Some(node_auth) = auth_provider.credentials(node).await? {
+        if let Some((name, password)) = node_auth.basic_auth_name_pwd {
+            mqtt_options.set_credentials(name, password);
+        } else if let Some(jwt) = node_auth.jwt {
+            mqtt_options.set_credentials("", jwt);
+        }
+    }
+    Ok(())
+}
+
+// Replaces the connection that was refused because of the credentials with a new one with refreshed credentials,
+// since the credentials of a connection can't be changed. Returns `None` if the credentials weren't refreshed.
+async fn reconnect_with_refreshed_credentials(
+    client: &Client,
+    node: &Node,
+    mqtt_options: &mut MqttOptions,
+) -> Option<EventLoop> {
+    let auth_provider = client.node_manager.read().await.http_client.auth_provider.clone()?;
+    match auth_provider.on_unauthorized(&node.url).await {
+        Ok(true) => {}
+        Ok(false) => return None,
+        Err(e) => {
+            warn!("Couldn't refresh the credentials for {}: {e}", node.url);
+            return None;
+        }
+    }
+    if let Err(e) = set_credentials(mqtt_options, node, &auth_provider).await {
+        warn!("Couldn't get the credentials for {}: {e}", node.url);
+        return None;
+    }
+
+    let (mqtt_client, event_loop) = AsyncClient::new(mqtt_options.clone(), 10);
+    client.mqtt.client.write().await.replace(mqtt_client);
+    Some(event_loop)
+}
+
+// Returns whether the broker refused the connection because of the credentials.
+fn is_unauthorized(error: &ConnectionError) -> bool {
+    matches!(
+        error,
+        ConnectionError::ConnectionRefused(ConnectReturnCode::NotAuthorized | ConnectReturnCode::BadUserNamePassword)
+    )
+}
+
+fn poll_mqtt(client: &Client, mut event_loop: EventLoop, node: Node, mut mqtt_options: MqttOptions) {
     let client = client.clone();
     std::thread::spawn(move || {
         let runtime = tokio::runtime::Builder::new_current_thread()
@@ -147,12 +231,15 @@ fn poll_mqtt(client: &Client, mut event_loop: EventLoop) {
             let mut is_subscribed = true;
             let mut error_instant = Instant::now();
             let mut connection_failure_count = 0;
+            // Set after reconnecting with refreshed credentials until the broker accepts them
+            let mut refreshed_credentials = false;
 
             loop {
                 let event = event_loop.poll().await;
 
                 match event {
                     Ok(Event::Incoming(Incoming::ConnAck(_))) => {
+                        refreshed_credentials = false;
                         let _ = client.mqtt.sender.read().await.send(MqttEvent::Connected);
                         if !is_subscribed {
                             is_subscribed = true;
@@ -209,7 +296,7 @@ fn poll_mqtt(client: &Client, mut event_loop: EventLoop) {
                                         match Payload::unpack_verified(payload, protocol_parameters) {
                                             Ok(Payload::Milestone(milestone)) => Ok(TopicEvent {
                                                 topic: p.topic.clone(),
-                                                payload: MqttPayload::MilestonePayload(milestone.as_ref().into()),
+                                                payload: MqttPayload::MilestonePayload(*milestone),
                                             }),
                                             Ok(p) => {
                                                 warn!(
@@ -238,13 +325,15 @@ fn poll_mqtt(client: &Client, mut event_loop: EventLoop) {
                                             }
                                         }
                                     } else {
-                                        match serde_json::from_slice(&p.payload) {
-                                            Ok(value) => Ok(TopicEvent {
+                                        let protocol_parameters = &client.network_info.read().await.protocol_parameters;
+
+                                        match MqttPayload::from_json(&p.topic, &p.payload, protocol_parameters) {
+                                            Ok(payload) => Ok(TopicEvent {
                                                 topic: p.topic.clone(),
-                                                payload: MqttPayload::Json(value),
+                                                payload,
                                             }),
                                             Err(e) => {
-                                                warn!("Cannot parse JSON: {:?}", e);
+                                                warn!("Payload decoding of {} failed: {:?}", p.topic, e);
                                                 Err(())
                                             }
                                         }
@@ -258,7 +347,23 @@ fn poll_mqtt(client: &Client, mut event_loop: EventLoop) {
                             }
                         });
                     }
-                    Err(_) => {
+                    Err(error) => {
+                        if is_unauthorized(&error) {
+                            // Refreshed credentials that are rejected again aren't refreshed endlessly
+                            if !refreshed_credentials {
+                                if let Some(new_event_loop) =
+                                    reconnect_with_refreshed_credentials(&client, &node, &mut mqtt_options).await
+                                {
+                                    event_loop = new_event_loop;
+                                    refreshed_credentials = true;
+                                    // The topics are subscribed again on the ConnAck of the new connection
+                                    is_subscribed = false;
+                                    continue;
+                                }
+                            }
+                            let _ = client.mqtt.sender.read().await.send(MqttEvent::Disconnected);
+                            break;
+                        }
                         if error_instant.elapsed().as_secs() < 5 {
                             connection_failure_count += 1;
                         } else {
@@ -352,11 +457,13 @@ impl<'a> MqttTopicManager<'a> {
         self,
         callback: C,
     ) -> Result<(), Error> {
-        let cb =
-            Arc::new(Box::new(callback)
-                as Box<
-                    dyn Fn(&crate::client::node_api::mqtt::TopicEvent) + Send + Sync + 'static,
-                >);
+        self.subscribe_handler(Arc::new(Box::new(callback) as TopicHandler))
+            .await
+    }
+
+    /// Subscribe to the given topics with a handler, which can be unsubscribed with
+    /// [`MqttTopicManager::unsubscribe_handler()`] without affecting other handlers of the topics.
+    pub(crate) async fn subscribe_handler(self, handler: Arc<TopicHandler>) -> Result<(), Error> {
         set_mqtt_client(self.client).await?;
         self.client
             .inner
@@ -375,9 +482,42 @@ impl<'a> MqttTopicManager<'a> {
         {
             let mut mqtt_topic_handlers = self.client.mqtt.topic_handlers.write().await;
             for topic in self.topics {
-                mqtt_topic_handlers.entry(topic).or_default().push(cb.clone());
+                mqtt_topic_handlers.entry(topic).or_default().push(handler.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes a handler from the given topics. Only the topics without other handlers are unsubscribed.
+    pub(crate) async fn unsubscribe_handler(self, handler: &Arc<TopicHandler>) -> Result<(), Error> {
+        let (unused_topics, empty_topic_handlers) = {
+            let mut mqtt_topic_handlers = self.client.mqtt.topic_handlers.write().await;
+            let mut unused_topics = Vec::new();
+            for topic in self.topics {
+                if let Some(handlers) = mqtt_topic_handlers.get_mut(&topic) {
+                    handlers.retain(|other| !Arc::ptr_eq(other, handler));
+                    if handlers.is_empty() {
+                        mqtt_topic_handlers.remove(&topic);
+                        unused_topics.push(topic);
+                    }
+                }
             }
+            (unused_topics, mqtt_topic_handlers.is_empty())
+        };
+
+        if let Some(client) = &*self.client.mqtt.client.write().await {
+            for topic in &unused_topics {
+                client.unsubscribe(topic.as_str()).await?;
+            }
+        }
+
+        if self.client.mqtt.broker_options.read().await.automatic_disconnect
+            && empty_topic_handlers
+            && !unused_topics.is_empty()
+        {
+            MqttManager::new(self.client).disconnect().await?;
         }
+
         Ok(())
     }
 
@@ -3,19 +3,29 @@
 
 //! MQTT types
 
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{collections::HashMap, fmt, sync::Arc, time::Duration};
 
 use regex::RegexSet;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 use super::Error;
-use crate::types::block::{
-    payload::{dto::MilestonePayloadDto, milestone::option::dto::ReceiptMilestoneOptionDto},
-    BlockDto,
+use crate::types::{
+    api::core::response::{BlockMetadataResponse, OutputWithMetadataResponse},
+    block::{
+        address::Bech32Address,
+        output::{AliasId, FoundryId, NftId, Output, OutputId, OutputWithMetadata},
+        payload::{
+            milestone::{option::dto::ReceiptMilestoneOptionDto, MilestonePayload},
+            transaction::TransactionId,
+        },
+        protocol::ProtocolParameters,
+        BlockDto, BlockId,
+    },
+    TryFromDto,
 };
 
-type TopicHandler = Box<dyn Fn(&TopicEvent) + Send + Sync>;
+pub(crate) type TopicHandler = Box<dyn Fn(&TopicEvent) + Send + Sync>;
 
 pub(crate) type TopicHandlerMap = HashMap<Topic, Vec<Arc<TopicHandler>>>;
 
@@ -39,9 +49,76 @@ pub enum MqttPayload {
     /// In case it contains a `Block` object.
     Block(BlockDto),
     /// In case it contains a `Milestone` object.
-    MilestonePayload(MilestonePayloadDto),
+    MilestonePayload(#[serde(with = "milestone_payload")] MilestonePayload),
     /// In case it contains a `Receipt` object.
     Receipt(ReceiptMilestoneOptionDto),
+    /// In case it contains an `Output` object with its metadata.
+    Output(#[serde(with = "output_with_metadata")] OutputWithMetadata),
+    /// In case it contains the metadata of a block.
+    BlockMetadata(BlockMetadataResponse),
+}
+
+impl MqttPayload {
+    /// Decodes the JSON payload published on a topic, outputs and block metadata are decoded into their types.
+    pub(crate) fn from_json(
+        topic: &str,
+        payload: &[u8],
+        protocol_parameters: &ProtocolParameters,
+    ) -> Result<Self, String> {
+        if topic.starts_with("outputs/") {
+            let response = serde_json::from_slice::<OutputWithMetadataResponse>(payload).map_err(|e| e.to_string())?;
+            let output =
+                Output::try_from_dto_with_params(response.output, protocol_parameters).map_err(|e| e.to_string())?;
+
+            Ok(Self::Output(OutputWithMetadata::new(output, response.metadata)))
+        } else if topic.starts_with("block-metadata/") {
+            serde_json::from_slice(payload)
+                .map(Self::BlockMetadata)
+                .map_err(|e| e.to_string())
+        } else {
+            serde_json::from_slice(payload)
+                .map(Self::Json)
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+mod milestone_payload {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::types::{
+        block::payload::{dto::MilestonePayloadDto, milestone::MilestonePayload},
+        TryFromDto,
+    };
+
+    pub(super) fn serialize<S: Serializer>(milestone: &MilestonePayload, s: S) -> Result<S::Ok, S::Error> {
+        MilestonePayloadDto::from(milestone).serialize(s)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<MilestonePayload, D::Error> {
+        MilestonePayload::try_from_dto(MilestonePayloadDto::deserialize(d)?).map_err(D::Error::custom)
+    }
+}
+
+mod output_with_metadata {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::types::{
+        api::core::response::OutputWithMetadataResponse,
+        block::output::{Output, OutputWithMetadata},
+        TryFromDto,
+    };
+
+    pub(super) fn serialize<S: Serializer>(output: &OutputWithMetadata, s: S) -> Result<S::Ok, S::Error> {
+        OutputWithMetadataResponse::from(output).serialize(s)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<OutputWithMetadata, D::Error> {
+        let response = OutputWithMetadataResponse::deserialize(d)?;
+        let output = Output::try_from_dto(response.output).map_err(D::Error::custom)?;
+
+        Ok(OutputWithMetadata::new(output, response.metadata))
+    }
 }
 
 /// Mqtt events.
@@ -141,6 +218,166 @@ impl BrokerOptions {
     }
 }
 
+/// The type of unlock condition by which the outputs of an address are filtered in
+/// [`MqttTopic::UnlockConditionOutputs`].
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum UnlockConditionTopic {
+    /// Any unlock condition.
+    Any,
+    /// The address unlock condition.
+    Address,
+    /// The storage deposit return unlock condition.
+    StorageReturn,
+    /// The expiration unlock condition.
+    Expiration,
+    /// The state controller address unlock condition.
+    StateController,
+    /// The governor address unlock condition.
+    Governor,
+    /// The immutable alias address unlock condition.
+    ImmutableAlias,
+}
+
+impl UnlockConditionTopic {
+    /// Returns the unlock condition as it's used in topics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Any => "+",
+            Self::Address => "address",
+            Self::StorageReturn => "storage-return",
+            Self::Expiration => "expiration",
+            Self::StateController => "state-controller",
+            Self::Governor => "governor",
+            Self::ImmutableAlias => "immutable-alias",
+        }
+    }
+}
+
+/// The MQTT topics of a node, converted into a [`Topic`] to subscribe to them.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum MqttTopic {
+    /// The latest milestone, published as JSON.
+    LatestMilestone,
+    /// The latest confirmed milestone, published as JSON.
+    ConfirmedMilestone,
+    /// All milestones, published as [`MqttPayload::MilestonePayload`].
+    Milestones,
+    /// All blocks, published as [`MqttPayload::Block`].
+    Blocks,
+    /// The blocks with a transaction.
+    TransactionBlocks,
+    /// The blocks with a transaction with tagged data, optionally only with the given tag of 1 to 64 bytes.
+    TransactionTaggedDataBlocks {
+        /// The tag of the tagged data.
+        tag: Option<Vec<u8>>,
+    },
+    /// The blocks with tagged data, optionally only with the given tag of 1 to 64 bytes.
+    TaggedDataBlocks {
+        /// The tag of the tagged data.
+        tag: Option<Vec<u8>>,
+    },
+    /// The metadata of a block, published as [`MqttPayload::BlockMetadata`].
+    BlockMetadata(BlockId),
+    /// The metadata of all blocks once they're referenced by a milestone.
+    ReferencedBlockMetadata,
+    /// The block including a transaction.
+    TransactionIncludedBlock(TransactionId),
+    /// An output, published as [`MqttPayload::Output`] once it's created or spent.
+    Output(OutputId),
+    /// The latest state of an alias output.
+    AliasOutput(AliasId),
+    /// The latest state of an NFT output.
+    NftOutput(NftId),
+    /// The latest state of a foundry output.
+    FoundryOutput(FoundryId),
+    /// The outputs with an unlock condition of the given type and address, once they're created or, if `spent` is
+    /// set, spent.
+    UnlockConditionOutputs {
+        /// The type of the unlock condition.
+        condition: UnlockConditionTopic,
+        /// The address of the unlock condition.
+        address: Bech32Address,
+        /// Whether the spent outputs are published instead of the created ones.
+        spent: bool,
+    },
+    /// The receipts of migrations, published as [`MqttPayload::Receipt`].
+    Receipts,
+}
+
+impl MqttTopic {
+    /// Creates the topic of an output.
+    pub fn output(output_id: OutputId) -> Self {
+        Self::Output(output_id)
+    }
+
+    /// Creates the topic of the outputs created with any unlock condition of an address.
+    pub fn address_outputs(address: Bech32Address) -> Self {
+        Self::UnlockConditionOutputs {
+            condition: UnlockConditionTopic::Any,
+            address,
+            spent: false,
+        }
+    }
+
+    /// Creates the topic of the outputs created or spent with an unlock condition of the given type and address.
+    pub fn unlock_condition_outputs(condition: UnlockConditionTopic, address: Bech32Address, spent: bool) -> Self {
+        Self::UnlockConditionOutputs {
+            condition,
+            address,
+            spent,
+        }
+    }
+
+    /// Creates the topic of the metadata of a block.
+    pub fn block_metadata(block_id: BlockId) -> Self {
+        Self::BlockMetadata(block_id)
+    }
+}
+
+impl fmt::Display for MqttTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LatestMilestone => write!(f, "milestone-info/latest"),
+            Self::ConfirmedMilestone => write!(f, "milestone-info/confirmed"),
+            Self::Milestones => write!(f, "milestones"),
+            Self::Blocks => write!(f, "blocks"),
+            Self::TransactionBlocks => write!(f, "blocks/transaction"),
+            Self::TransactionTaggedDataBlocks { tag: None } => write!(f, "blocks/transaction/tagged-data"),
+            Self::TransactionTaggedDataBlocks { tag: Some(tag) } => {
+                write!(f, "blocks/transaction/tagged-data/{}", prefix_hex::encode(tag))
+            }
+            Self::TaggedDataBlocks { tag: None } => write!(f, "blocks/tagged-data"),
+            Self::TaggedDataBlocks { tag: Some(tag) } => write!(f, "blocks/tagged-data/{}", prefix_hex::encode(tag)),
+            Self::BlockMetadata(block_id) => write!(f, "block-metadata/{block_id}"),
+            Self::ReferencedBlockMetadata => write!(f, "block-metadata/referenced"),
+            Self::TransactionIncludedBlock(transaction_id) => write!(f, "transactions/{transaction_id}/included-block"),
+            Self::Output(output_id) => write!(f, "outputs/{output_id}"),
+            Self::AliasOutput(alias_id) => write!(f, "outputs/alias/{alias_id}"),
+            Self::NftOutput(nft_id) => write!(f, "outputs/nft/{nft_id}"),
+            Self::FoundryOutput(foundry_id) => write!(f, "outputs/foundry/{foundry_id}"),
+            Self::UnlockConditionOutputs {
+                condition,
+                address,
+                spent,
+            } => write!(
+                f,
+                "outputs/unlock/{}/{address}{}",
+                condition.as_str(),
+                if *spent { "/spent" } else { "" }
+            ),
+            Self::Receipts => write!(f, "receipts"),
+        }
+    }
+}
+
+impl TryFrom<MqttTopic> for Topic {
+    type Error = Error;
+
+    fn try_from(topic: MqttTopic) -> Result<Self, Self::Error> {
+        Self::new(topic.to_string())
+    }
+}
+
 /// A MQTT topic.
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize)]
 pub struct Topic(String);
@@ -191,7 +428,7 @@ impl Topic {
                 // Transaction topics.
                 r"^transactions/0x([a-f0-9]{64})/included-block$",
                 // Output topics.
-                r"^outputs/0x([a-f0-9]{64})(\d{4})$",
+                r"^outputs/0x([a-f0-9]{64})([a-f0-9]{4})$",
                 r"^outputs/alias/0x([a-f0-9]{64})$",
                 r"^outputs/nft/0x([a-f0-9]{64})$",
                 r"^outputs/foundry/0x([a-f0-9]{76})$",
@@ -207,3 +444,158 @@ impl Topic {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        api::core::response::LedgerInclusionState,
+        block::address::{Address, Ed25519Address},
+    };
+
+    const BLOCK_ID: &str = "0xedf5f572c58ddf4b4f9567d82bf96689cc68b730df796d822b4b9fb643f5efda";
+    const PARENT_ID: &str = "0x7ffec9e1233204d9c6dce6812b1539ee96af691ca2e4d9065daa85907d33e5d3";
+    const OUTPUT_ID: &str = "0xbce525324af12eda02bf7927e92cea3a8e8322d0f41966271443e6c3b245a4400100";
+
+    fn topics() -> Vec<MqttTopic> {
+        let address = Bech32Address::try_new("rms", Ed25519Address::new([1; 32])).unwrap();
+        let mut topics = vec![
+            MqttTopic::LatestMilestone,
+            MqttTopic::ConfirmedMilestone,
+            MqttTopic::Milestones,
+            MqttTopic::Blocks,
+            MqttTopic::TransactionBlocks,
+            MqttTopic::TransactionTaggedDataBlocks { tag: None },
+            MqttTopic::TransactionTaggedDataBlocks {
+                tag: Some(b"tag".to_vec()),
+            },
+            MqttTopic::TaggedDataBlocks { tag: None },
+            MqttTopic::TaggedDataBlocks {
+                tag: Some(vec![0xff; 64]),
+            },
+            MqttTopic::block_metadata(BLOCK_ID.parse().unwrap()),
+            MqttTopic::ReferencedBlockMetadata,
+            MqttTopic::TransactionIncludedBlock(TransactionId::new([2; TransactionId::LENGTH])),
+            MqttTopic::output(OUTPUT_ID.parse().unwrap()),
+            MqttTopic::AliasOutput(AliasId::new([3; AliasId::LENGTH])),
+            MqttTopic::NftOutput(NftId::new([4; NftId::LENGTH])),
+            MqttTopic::FoundryOutput(FoundryId::new([5; FoundryId::LENGTH])),
+            MqttTopic::address_outputs(address),
+            MqttTopic::Receipts,
+        ];
+        for condition in [
+            UnlockConditionTopic::Any,
+            UnlockConditionTopic::Address,
+            UnlockConditionTopic::StorageReturn,
+            UnlockConditionTopic::Expiration,
+            UnlockConditionTopic::StateController,
+            UnlockConditionTopic::Governor,
+            UnlockConditionTopic::ImmutableAlias,
+        ] {
+            for spent in [false, true] {
+                topics.push(MqttTopic::unlock_condition_outputs(condition, address, spent));
+            }
+        }
+        topics
+    }
+
+    #[test]
+    fn topics_round_trip() {
+        for mqtt_topic in topics() {
+            let topic = Topic::try_from(mqtt_topic.clone()).unwrap_or_else(|e| panic!("{mqtt_topic:?}: {e}"));
+            assert_eq!(topic.as_str(), mqtt_topic.to_string());
+
+            let json = serde_json::to_string(&topic).unwrap();
+            assert_eq!(json, format!("\"{mqtt_topic}\""));
+            assert_eq!(serde_json::from_str::<Topic>(&json).unwrap(), topic);
+        }
+    }
+
+    #[test]
+    fn invalid_topics_are_rejected() {
+        assert!(Topic::new("outputs/unknown").is_err());
+        assert!(serde_json::from_str::<Topic>("\"blocks/tagged-data/0xZZ\"").is_err());
+        // Tags are limited to 64 bytes
+        assert!(Topic::try_from(MqttTopic::TaggedDataBlocks { tag: Some(vec![0; 65]) }).is_err());
+    }
+
+    #[test]
+    fn output_payload_is_decoded() {
+        let payload = format!(
+            r#"{{"metadata":{{"blockId":"{BLOCK_ID}","transactionId":"0xbce525324af12eda02bf7927e92cea3a8e8322d0f41966271443e6c3b245a440","outputIndex":1,"isSpent":false,"milestoneIndexBooked":5,"milestoneTimestampBooked":1680000000,"ledgerIndex":6}},"output":{{"type":3,"amount":"1000000","unlockConditions":[{{"type":0,"address":{{"type":0,"pubKeyHash":"{PARENT_ID}"}}}}]}}}}"#
+        );
+
+        let payload = MqttPayload::from_json(
+            &format!("outputs/{OUTPUT_ID}"),
+            payload.as_bytes(),
+            &ProtocolParameters::default(),
+        )
+        .unwrap();
+
+        let MqttPayload::Output(output) = payload else {
+            panic!("unexpected payload {payload:?}");
+        };
+        assert_eq!(output.metadata().output_id(), &OUTPUT_ID.parse::<OutputId>().unwrap());
+        assert_eq!(output.metadata().block_id(), &BLOCK_ID.parse::<BlockId>().unwrap());
+        assert!(!output.metadata().is_spent());
+        assert_eq!(output.output().amount(), 1_000_000);
+        assert_eq!(
+            output.output().as_basic().address(),
+            &Address::from(Ed25519Address::new(prefix_hex::decode(PARENT_ID).unwrap()))
+        );
+    }
+
+    #[test]
+    fn invalid_output_payload_is_rejected() {
+        let topic = format!("outputs/{OUTPUT_ID}");
+        let protocol_parameters = ProtocolParameters::default();
+
+        assert!(MqttPayload::from_json(&topic, b"{}", &protocol_parameters).is_err());
+        // The amount exceeds the token supply
+        let payload = format!(
+            r#"{{"metadata":{{"blockId":"{BLOCK_ID}","transactionId":"0xbce525324af12eda02bf7927e92cea3a8e8322d0f41966271443e6c3b245a440","outputIndex":1,"isSpent":false,"milestoneIndexBooked":5,"milestoneTimestampBooked":1680000000,"ledgerIndex":6}},"output":{{"type":3,"amount":"{}","unlockConditions":[{{"type":0,"address":{{"type":0,"pubKeyHash":"{PARENT_ID}"}}}}]}}}}"#,
+            u64::MAX
+        );
+        assert!(MqttPayload::from_json(&topic, payload.as_bytes(), &protocol_parameters).is_err());
+    }
+
+    #[test]
+    fn block_metadata_payload_is_decoded() {
+        let payload = format!(
+            r#"{{"blockId":"{BLOCK_ID}","parents":["{PARENT_ID}"],"isSolid":true,"referencedByMilestoneIndex":7,"milestoneIndex":7,"ledgerInclusionState":"conflicting","conflictReason":1,"whiteFlagIndex":0}}"#
+        );
+
+        let payload = MqttPayload::from_json(
+            &format!("block-metadata/{BLOCK_ID}"),
+            payload.as_bytes(),
+            &ProtocolParameters::default(),
+        )
+        .unwrap();
+
+        let MqttPayload::BlockMetadata(metadata) = payload else {
+            panic!("unexpected payload {payload:?}");
+        };
+        assert_eq!(metadata.block_id, BLOCK_ID.parse().unwrap());
+        assert_eq!(metadata.parents, [PARENT_ID.parse().unwrap()]);
+        assert_eq!(metadata.referenced_by_milestone_index, Some(7));
+        assert_eq!(metadata.ledger_inclusion_state, Some(LedgerInclusionState::Conflicting));
+        assert_eq!(metadata.conflict_reason, Some(1));
+        assert_eq!(metadata.should_reattach, None);
+    }
+
+    #[test]
+    fn other_payloads_are_decoded_as_json() {
+        let payload = br#"{"index":7,"timestamp":1680000000,"milestoneId":"0x7ffec9e1233204d9c6dce6812b1539ee96af691ca2e4d9065daa85907d33e5d3"}"#;
+
+        let payload = MqttPayload::from_json("milestone-info/latest", payload, &ProtocolParameters::default()).unwrap();
+
+        assert_eq!(
+            payload,
+            MqttPayload::Json(serde_json::json!({
+                "index": 7,
+                "timestamp": 1680000000,
+                "milestoneId": "0x7ffec9e1233204d9c6dce6812b1539ee96af691ca2e4d9065daa85907d33e5d3",
+            }))
+        );
+    }
+}
@@ -29,14 +29,14 @@ impl ClientInner {
             ParticipationEventType::Staking => "type=1",
         });
 
-        self.get_request(route, query, false, false).await
+        self.get_request(route, query, None, false).await
     }
 
     /// RouteParticipationEvent is the route to access a single participation by its ID.
     pub async fn event(&self, event_id: &ParticipationEventId) -> Result<ParticipationEventData> {
         let route = format!("api/participation/v1/events/{event_id}");
 
-        self.get_request(&route, None, false, false).await
+        self.get_request(&route, None, None, false).await
     }
 
     /// RouteParticipationEventStatus is the route to access the status of a single participation by its ID.
@@ -50,7 +50,7 @@ impl ClientInner {
         self.get_request(
             &route,
             milestone_index.map(|index| index.to_string()).as_deref(),
-            false,
+            None,
             false,
         )
         .await
@@ -60,7 +60,7 @@ impl ClientInner {
     pub async fn output_status(&self, output_id: &OutputId) -> Result<OutputStatusResponse> {
         let route = format!("api/participation/v1/outputs/{output_id}");
 
-        self.get_request(&route, None, false, false).await
+        self.get_request(&route, None, None, false).await
     }
 
     /// RouteAddressBech32Status is the route to get the staking rewards for the given bech32 address.
@@ -70,7 +70,7 @@ impl ClientInner {
     ) -> Result<AddressStakingStatus> {
         let route = format!("api/participation/v1/addresses/{}", bech32_address.convert()?);
 
-        self.get_request(&route, None, false, false).await
+        self.get_request(&route, None, None, false).await
     }
 
     /// RouteAddressBech32Outputs is the route to get the outputs for the given bech32 address.
@@ -80,6 +80,6 @@ impl ClientInner {
     ) -> Result<AddressOutputsResponse> {
         let route = format!("api/participation/v1/addresses/{}/outputs", bech32_address.convert()?);
 
-        self.get_request(&route, None, false, false).await
+        self.get_request(&route, None, None, false).await
     }
 }
@@ -30,7 +30,7 @@ impl ClientInner {
         let path = format!("{}{}{}", base_plugin_path, endpoint, query_params.join("&"));
 
         match req_method {
-            Ok(Method::GET) => self.get_request(&path, None, false, false).await,
+            Ok(Method::GET) => self.get_request(&path, None, None, false).await,
             Ok(Method::POST) => self.post_request_json(&path, request_object.into(), true).await,
             _ => Err(crate::client::Error::Node(
                 crate::client::node_api::error::Error::NotSupported(method.to_string()),
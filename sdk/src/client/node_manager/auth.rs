@@ -0,0 +1,68 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Providers of the credentials for the requests to the nodes.
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use url::Url;
+
+use crate::client::{
+    node_api::error::Result,
+    node_manager::{
+        node::{Node, NodeAuth},
+        scoring::base_url,
+    },
+};
+
+/// Provides the credentials for the REST requests and MQTT connections to the nodes, for example short-lived tokens
+/// issued by an OAuth gateway. Set it with
+/// [`ClientBuilder::with_auth_provider()`](crate::client::ClientBuilder::with_auth_provider).
+///
+/// For MQTT connections the username and password are used as credentials, a JWT is sent as password. Without an auth
+/// provider no credentials are sent to the MQTT broker. A connection refused because of the credentials is replaced by
+/// a new one with the refreshed credentials, subscribing to all topics again.
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+pub trait AuthProvider: Debug + Send + Sync {
+    /// Returns the credentials for a request to the node with the given url, which has no path or credentials. If
+    /// `None` is returned, the static [`NodeAuth`] of the node is used. Errors should be returned as
+    /// [`Error::Auth`](crate::client::node_api::error::Error::Auth).
+    async fn credentials(&self, node_url: &Url) -> Result<Option<NodeAuth>>;
+
+    /// Called when a node rejected the credentials, with a 401 or 403 response or a refused MQTT connection, so they
+    /// can be refreshed. If `true` is returned, the request is sent again once with the credentials returned by
+    /// [`AuthProvider::credentials()`].
+    async fn on_unauthorized(&self, _node_url: &Url) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+/// The auth provider of a client, shared by the builder and the node manager.
+#[derive(Clone, Debug)]
+pub(crate) struct SharedAuthProvider(pub(crate) Arc<dyn AuthProvider>);
+
+impl SharedAuthProvider {
+    /// Returns the credentials for a node, from the provider or the node itself.
+    pub(crate) async fn credentials(&self, node: &Node) -> Result<Option<NodeAuth>> {
+        Ok(self
+            .0
+            .credentials(&base_url(&node.url))
+            .await?
+            .or_else(|| node.auth.clone()))
+    }
+
+    /// Notifies the provider that a node rejected the credentials, returns whether the request should be sent again.
+    pub(crate) async fn on_unauthorized(&self, node_url: &Url) -> Result<bool> {
+        self.0.on_unauthorized(&base_url(node_url)).await
+    }
+}
+
+impl PartialEq for SharedAuthProvider {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedAuthProvider {}
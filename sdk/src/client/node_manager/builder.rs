@@ -17,8 +17,14 @@ use crate::{
         constants::{DEFAULT_MIN_QUORUM_SIZE, DEFAULT_QUORUM_THRESHOLD, DEFAULT_USER_AGENT, NODE_SYNC_INTERVAL},
         error::{Error, Result},
         node_manager::{
+            auth::SharedAuthProvider,
+            circuit_breaker::{CircuitBreakerOptions, CircuitBreakers},
             http_client::HttpClient,
             node::{Node, NodeAuth, NodeDto},
+            quorum::QuorumEndpoint,
+            retry::RetryPolicy,
+            scoring::{NodeScores, NodeSelectionPolicy},
+            transport::Transport,
             NodeManager,
         },
     },
@@ -58,9 +64,27 @@ pub struct NodeManagerBuilder {
     /// % of nodes that have to return the same response so it gets accepted
     #[serde(default = "default_quorum_threshold")]
     pub quorum_threshold: usize,
+    /// The endpoint classes for which quorum is required when it's enabled
+    #[serde(default = "QuorumEndpoint::all")]
+    pub quorum_endpoints: HashSet<QuorumEndpoint>,
+    /// The policy used to select the nodes for a request
+    #[serde(default)]
+    pub node_selection_policy: NodeSelectionPolicy,
+    /// The policy for retrying requests that failed on all nodes
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
+    /// Options of the circuit breakers that skip failing nodes
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerOptions,
     /// The User-Agent header for requests
     #[serde(default = "default_user_agent")]
     pub user_agent: String,
+    /// The custom transport sending the requests, reqwest is used if `None`
+    #[serde(skip)]
+    pub(crate) transport: Option<Transport>,
+    /// The provider of the credentials for the nodes
+    #[serde(skip)]
+    pub(crate) auth_provider: Option<SharedAuthProvider>,
 }
 
 fn default_user_agent() -> String {
@@ -206,11 +230,41 @@ impl NodeManagerBuilder {
         self
     }
 
+    pub(crate) fn with_quorum_endpoints(mut self, quorum_endpoints: HashSet<QuorumEndpoint>) -> Self {
+        self.quorum_endpoints = quorum_endpoints;
+        self
+    }
+
+    pub(crate) fn with_node_selection_policy(mut self, node_selection_policy: NodeSelectionPolicy) -> Self {
+        self.node_selection_policy = node_selection_policy;
+        self
+    }
+
+    pub(crate) fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub(crate) fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreakerOptions) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
     pub(crate) fn with_user_agent(mut self, user_agent: String) -> Self {
         self.user_agent = user_agent;
         self
     }
 
+    pub(crate) fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    pub(crate) fn with_auth_provider(mut self, auth_provider: SharedAuthProvider) -> Self {
+        self.auth_provider = Some(auth_provider);
+        self
+    }
+
     pub(crate) fn build(self, healthy_nodes: HashMap<Node, InfoResponse>) -> NodeManager {
         NodeManager {
             primary_node: self.primary_node.map(Into::into),
@@ -223,7 +277,14 @@ impl NodeManagerBuilder {
             quorum: self.quorum,
             min_quorum_size: self.min_quorum_size,
             quorum_threshold: self.quorum_threshold,
-            http_client: HttpClient::new(self.user_agent),
+            quorum_endpoints: self.quorum_endpoints,
+            node_selection_policy: self.node_selection_policy,
+            scores: NodeScores::default(),
+            retry_policy: self.retry_policy,
+            circuit_breakers: CircuitBreakers::new(self.circuit_breaker),
+            http_client: HttpClient::new(self.user_agent)
+                .with_transport(self.transport)
+                .with_auth_provider(self.auth_provider),
         }
     }
 }
@@ -240,7 +301,13 @@ impl Default for NodeManagerBuilder {
             quorum: false,
             min_quorum_size: DEFAULT_MIN_QUORUM_SIZE,
             quorum_threshold: DEFAULT_QUORUM_THRESHOLD,
+            quorum_endpoints: QuorumEndpoint::all(),
+            node_selection_policy: NodeSelectionPolicy::default(),
+            retry_policy: RetryPolicy::default(),
+            circuit_breaker: CircuitBreakerOptions::default(),
             user_agent: DEFAULT_USER_AGENT.to_string(),
+            transport: None,
+            auth_provider: None,
         }
     }
 }
@@ -265,7 +332,13 @@ impl From<&NodeManager> for NodeManagerBuilder {
             quorum: value.quorum,
             min_quorum_size: value.min_quorum_size,
             quorum_threshold: value.quorum_threshold,
+            quorum_endpoints: value.quorum_endpoints.clone(),
+            node_selection_policy: value.node_selection_policy,
+            retry_policy: value.retry_policy.clone(),
+            circuit_breaker: value.circuit_breakers.options,
             user_agent: value.http_client.user_agent.clone(),
+            transport: value.http_client.transport.clone(),
+            auth_provider: value.http_client.auth_provider.clone(),
         }
     }
 }
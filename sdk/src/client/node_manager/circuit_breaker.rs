@@ -0,0 +1,382 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Circuit breakers skipping nodes that failed repeatedly.
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use instant::Instant;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::client::{
+    constants::{DEFAULT_CIRCUIT_BREAKER_COOL_DOWN, DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD},
+    node_api::error::Error as NodeApiError,
+    node_manager::{
+        node::Node,
+        scoring::{base_url, is_node_failure, node_key},
+    },
+    ClientInner, Error, Result,
+};
+
+/// Options of the circuit breakers of the nodes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitBreakerOptions {
+    /// The amount of consecutive failed requests after which a node is skipped, 0 disables the circuit breakers
+    pub failure_threshold: u32,
+    /// The duration a node is skipped for before a probe request is sent to it
+    pub cool_down: Duration,
+}
+
+impl Default for CircuitBreakerOptions {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+            cool_down: DEFAULT_CIRCUIT_BREAKER_COOL_DOWN,
+        }
+    }
+}
+
+/// The state of the circuit breaker of a node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CircuitState {
+    /// Requests are sent to the node.
+    Closed,
+    /// The node failed too often and is skipped until the cool-down passed.
+    Open,
+    /// The cool-down passed, a single probe request is sent to the node. If it succeeds the circuit is closed,
+    /// otherwise it's opened again.
+    HalfOpen,
+}
+
+/// The circuit breaker of a node.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeCircuitBreaker {
+    /// The url of the node.
+    pub url: Url,
+    /// The state of the circuit.
+    pub state: CircuitState,
+    /// The amount of consecutive failed requests.
+    pub consecutive_failures: u32,
+    /// The time until the next probe request can be sent to the node, if the circuit is open.
+    pub remaining_cool_down: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    url: Url,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    cool_down: Duration,
+    probing_since: Option<Instant>,
+}
+
+impl Breaker {
+    fn state(&self, now: Instant) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if now < opened_at + self.cool_down => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
+
+/// Keeps the circuit breakers of the nodes.
+#[derive(Debug, Default)]
+pub(crate) struct CircuitBreakers {
+    pub(crate) options: CircuitBreakerOptions,
+    breakers: Mutex<HashMap<String, Breaker>>,
+}
+
+impl CircuitBreakers {
+    pub(crate) fn new(options: CircuitBreakerOptions) -> Self {
+        Self {
+            options,
+            breakers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn circuit_breakers(&self) -> Result<Vec<NodeCircuitBreaker>> {
+        let now = Instant::now();
+        Ok(self
+            .breakers
+            .lock()
+            .map_err(|_| Error::PoisonError)?
+            .values()
+            .map(|breaker| {
+                let state = breaker.state(now);
+                NodeCircuitBreaker {
+                    url: breaker.url.clone(),
+                    state,
+                    consecutive_failures: breaker.consecutive_failures,
+                    remaining_cool_down: breaker
+                        .opened_at
+                        .filter(|_| state == CircuitState::Open)
+                        .map(|opened_at| opened_at + breaker.cool_down - now),
+                }
+            })
+            .collect())
+    }
+
+    /// Removes the nodes with an open circuit, and half-open nodes that are already probed. If no node is left, all
+    /// nodes are kept.
+    pub(crate) fn filter(&self, nodes: &mut Vec<Node>) {
+        if self.options.failure_threshold == 0 {
+            return;
+        }
+        let breakers = match self.breakers.lock() {
+            Ok(breakers) => breakers,
+            Err(_) => return,
+        };
+        let now = Instant::now();
+
+        let allowed = nodes
+            .iter()
+            .map(|node| match breakers.get(&node_key(&node.url)) {
+                Some(breaker) => match breaker.state(now) {
+                    CircuitState::Closed => true,
+                    CircuitState::Open => false,
+                    CircuitState::HalfOpen => !self.is_probing(breaker, now),
+                },
+                None => true,
+            })
+            .collect::<Vec<_>>();
+
+        if allowed.iter().any(|allowed| *allowed) {
+            let mut allowed = allowed.into_iter();
+            nodes.retain(|_| allowed.next().unwrap_or(true));
+        }
+    }
+
+    /// Marks a request that is sent to a half-open node as its probe, so no other requests are sent to the node until
+    /// it's recorded.
+    pub(crate) fn start_request(&self, node: &Node) {
+        if self.options.failure_threshold == 0 {
+            return;
+        }
+        let mut breakers = match self.breakers.lock() {
+            Ok(breakers) => breakers,
+            Err(_) => return,
+        };
+        let now = Instant::now();
+
+        if let Some(breaker) = breakers.get_mut(&node_key(&node.url)) {
+            if breaker.state(now) == CircuitState::HalfOpen && !self.is_probing(breaker, now) {
+                breaker.probing_since = Some(now);
+            }
+        }
+    }
+
+    // Only one probe at a time, unless the probe got no response within the cool-down.
+    fn is_probing(&self, breaker: &Breaker, now: Instant) -> bool {
+        breaker
+            .probing_since
+            .map_or(false, |probing_since| now < probing_since + self.options.cool_down)
+    }
+
+    /// Records the outcome of a request sent to a node.
+    pub(crate) fn record<T>(&self, node: &Node, result: &std::result::Result<T, NodeApiError>) {
+        if self.options.failure_threshold == 0 {
+            return;
+        }
+        let mut breakers = match self.breakers.lock() {
+            Ok(breakers) => breakers,
+            Err(_) => return,
+        };
+
+        match result {
+            Err(error) if is_node_failure(error) => {
+                let now = Instant::now();
+                let breaker = breakers.entry(node_key(&node.url)).or_insert_with(|| Breaker {
+                    url: base_url(&node.url),
+                    consecutive_failures: 0,
+                    opened_at: None,
+                    cool_down: self.options.cool_down,
+                    probing_since: None,
+                });
+                breaker.consecutive_failures += 1;
+                breaker.probing_since = None;
+
+                // A failed probe or too many failures open the circuit, for at least as long as the node asked to retry
+                // later
+                if breaker.opened_at.is_some() || breaker.consecutive_failures >= self.options.failure_threshold {
+                    if breaker.opened_at.is_none() {
+                        log::warn!(
+                            "Circuit of {} opened after {} failures",
+                            breaker.url,
+                            breaker.consecutive_failures
+                        );
+                    }
+                    breaker.opened_at = Some(now);
+                    breaker.cool_down = error.retry_after().map_or(self.options.cool_down, |retry_after| {
+                        retry_after.max(self.options.cool_down)
+                    });
+                }
+            }
+            Err(_) => {}
+            Ok(_) => {
+                // Other nodes never failed, so they don't need a breaker
+                breakers.remove(&node_key(&node.url));
+            }
+        }
+    }
+}
+
+impl ClientInner {
+    /// Returns the circuit breakers of the nodes that failed since their last successful request.
+    pub async fn circuit_breakers(&self) -> Result<Vec<NodeCircuitBreaker>> {
+        self.node_manager.read().await.circuit_breakers.circuit_breakers()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node() -> Node {
+        Node::from(Url::parse("http://node:14265").unwrap())
+    }
+
+    fn failure(code: u16, retry_after: Option<Duration>) -> std::result::Result<(), NodeApiError> {
+        Err(NodeApiError::ResponseError {
+            code,
+            text: String::new(),
+            url: "http://node:14265/api/core/v2/info".to_owned(),
+            retry_after,
+        })
+    }
+
+    fn breakers(failure_threshold: u32) -> CircuitBreakers {
+        CircuitBreakers::new(CircuitBreakerOptions {
+            failure_threshold,
+            cool_down: Duration::from_secs(30),
+        })
+    }
+
+    #[test]
+    fn record_opens_after_threshold() {
+        let breakers = breakers(3);
+        let node = node();
+
+        breakers.record(&node, &failure(503, None));
+        breakers.record(&node, &failure(500, None));
+        let breaker = &breakers.circuit_breakers().unwrap()[0];
+        assert_eq!(breaker.state, CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures, 2);
+
+        breakers.record(&node, &failure(502, None));
+        let breaker = &breakers.circuit_breakers().unwrap()[0];
+        assert_eq!(breaker.state, CircuitState::Open);
+        assert!(breaker.remaining_cool_down.unwrap() <= Duration::from_secs(30));
+
+        let mut nodes = vec![node, Node::from(Url::parse("http://other:14265").unwrap())];
+        breakers.filter(&mut nodes);
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].url.host_str(), Some("other"));
+    }
+
+    #[test]
+    fn record_counts_retry_after_toward_threshold() {
+        let breakers = breakers(2);
+        let node = node();
+
+        breakers.record(&node, &failure(429, Some(Duration::from_secs(60))));
+        assert_eq!(breakers.circuit_breakers().unwrap()[0].state, CircuitState::Closed);
+
+        breakers.record(&node, &failure(429, Some(Duration::from_secs(60))));
+        let breaker = &breakers.circuit_breakers().unwrap()[0];
+        assert_eq!(breaker.state, CircuitState::Open);
+        assert!(breaker.remaining_cool_down.unwrap() > Duration::from_secs(30));
+    }
+
+    #[test]
+    fn record_ignores_client_errors_and_resets_on_success() {
+        let breakers = breakers(2);
+        let node = node();
+
+        breakers.record(&node, &Err::<(), _>(NodeApiError::NotFound(String::new())));
+        breakers.record(&node, &failure(400, None));
+        assert!(breakers.circuit_breakers().unwrap().is_empty());
+
+        breakers.record(&node, &failure(503, None));
+        breakers.record(&node, &Ok(()));
+        assert!(breakers.circuit_breakers().unwrap().is_empty());
+
+        breakers.record(&node, &failure(503, None));
+        assert_eq!(breakers.circuit_breakers().unwrap()[0].consecutive_failures, 1);
+    }
+
+    #[test]
+    fn record_disabled() {
+        let breakers = breakers(0);
+        breakers.record(&node(), &failure(503, None));
+        assert!(breakers.circuit_breakers().unwrap().is_empty());
+    }
+    // Moves the time the circuit of the node was opened back, so its cool-down passed.
+    fn pass_cool_down(breakers: &CircuitBreakers, node: &Node) {
+        let mut breakers = breakers.breakers.lock().unwrap();
+        let breaker = breakers.get_mut(&node_key(&node.url)).unwrap();
+        breaker.opened_at = Some(Instant::now() - breaker.cool_down - Duration::from_secs(1));
+    }
+
+    fn filtered_hosts(breakers: &CircuitBreakers) -> Vec<String> {
+        let mut nodes = vec![node(), Node::from(Url::parse("http://other:14265").unwrap())];
+        breakers.filter(&mut nodes);
+        nodes
+            .iter()
+            .map(|node| node.url.host_str().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn half_open_probe_closes_on_success() {
+        let breakers = breakers(1);
+        let node = node();
+
+        breakers.record(&node, &failure(503, None));
+        pass_cool_down(&breakers, &node);
+        assert_eq!(breakers.circuit_breakers().unwrap()[0].state, CircuitState::HalfOpen);
+
+        // Filtering doesn't start a probe, since the request may not be sent to the node
+        assert_eq!(filtered_hosts(&breakers), ["node", "other"]);
+        assert_eq!(filtered_hosts(&breakers), ["node", "other"]);
+
+        // No other request is sent to the node while it's probed
+        breakers.start_request(&node);
+        assert_eq!(filtered_hosts(&breakers), ["other"]);
+
+        breakers.record(&node, &Ok(()));
+        assert!(breakers.circuit_breakers().unwrap().is_empty());
+        assert_eq!(filtered_hosts(&breakers), ["node", "other"]);
+    }
+
+    #[test]
+    fn half_open_probe_opens_on_failure() {
+        let breakers = breakers(1);
+        let node = node();
+
+        breakers.record(&node, &failure(503, None));
+        pass_cool_down(&breakers, &node);
+        breakers.start_request(&node);
+        breakers.record(&node, &failure(503, None));
+
+        let breaker = &breakers.circuit_breakers().unwrap()[0];
+        assert_eq!(breaker.state, CircuitState::Open);
+        assert_eq!(breaker.consecutive_failures, 2);
+        assert!(breaker.remaining_cool_down.unwrap() > Duration::from_secs(29));
+        assert_eq!(filtered_hosts(&breakers), ["other"]);
+    }
+
+    #[test]
+    fn requests_to_closed_nodes_are_no_probes() {
+        let breakers = breakers(2);
+        let node = node();
+
+        breakers.record(&node, &failure(503, None));
+        breakers.start_request(&node);
+        assert_eq!(filtered_hosts(&breakers), ["node", "other"]);
+    }
+}
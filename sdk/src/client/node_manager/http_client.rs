@@ -5,55 +5,77 @@
 
 use std::time::Duration;
 
-use reqwest::RequestBuilder;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
 use crate::client::{
     node_api::error::{Error, Result},
-    node_manager::node::Node,
+    node_manager::{
+        auth::SharedAuthProvider,
+        node::Node,
+        transport::{HttpMethod, HttpRequest, HttpResponse, HttpTransport, ReqwestTransport, Transport},
+    },
 };
-pub(crate) struct Response(reqwest::Response);
+pub(crate) struct Response(HttpResponse);
 
 impl Response {
     pub(crate) fn status(&self) -> u16 {
-        self.0.status().as_u16()
+        self.0.status
     }
 
     pub(crate) async fn into_json<T: DeserializeOwned>(self) -> Result<T> {
-        self.0.json().await.map_err(Into::into)
+        serde_json::from_slice(&self.0.body).map_err(Into::into)
     }
 
     #[cfg(not(target_family = "wasm"))]
     pub(crate) async fn into_text(self) -> Result<String> {
-        self.0.text().await.map_err(Into::into)
+        Ok(String::from_utf8_lossy(&self.0.body).into_owned())
     }
 
     pub(crate) async fn into_bytes(self) -> Result<Vec<u8>> {
-        self.0.bytes().await.map(|b| b.to_vec()).map_err(Into::into)
+        Ok(self.0.body)
     }
 }
 
 #[derive(Clone)]
 pub(crate) struct HttpClient {
-    client: reqwest::Client,
+    default_transport: ReqwestTransport,
+    pub(crate) transport: Option<Transport>,
+    pub(crate) auth_provider: Option<SharedAuthProvider>,
     pub(crate) user_agent: String,
 }
 
 impl HttpClient {
     pub(crate) fn new(user_agent: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            default_transport: ReqwestTransport::new(),
+            transport: None,
+            auth_provider: None,
             user_agent,
         }
     }
 
-    async fn parse_response(response: reqwest::Response, url: &url::Url) -> Result<Response> {
-        let status = response.status();
-        if status.is_success() {
+    pub(crate) fn with_transport(mut self, transport: Option<Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    pub(crate) fn with_auth_provider(mut self, auth_provider: Option<SharedAuthProvider>) -> Self {
+        self.auth_provider = auth_provider;
+        self
+    }
+
+    fn parse_response(response: HttpResponse, url: &url::Url) -> Result<Response> {
+        let status = response.status;
+        if (200..300).contains(&status) {
             Ok(Response(response))
         } else {
-            let text = response.text().await?;
+            // Only the delay in seconds is supported, not the http-date format
+            let retry_after = response
+                .header("retry-after")
+                .and_then(|retry_after| retry_after.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            let text = String::from_utf8_lossy(&response.body).into_owned();
             // Different urls, nodes and versions give different replies
             if text == *"no available nodes with remote Pow"
                 || text.contains("proof of work is not available on this node")
@@ -61,66 +83,102 @@ impl HttpClient {
                 || text.contains("`Pow` not enabled")
             {
                 Err(Error::UnavailablePow)
-            } else if status.as_u16() == 404 {
+            } else if status == 404 {
                 Err(Error::NotFound(url.to_string()))
             } else {
                 Err(Error::ResponseError {
-                    code: status.as_u16(),
+                    code: status,
                     text,
                     url: url.to_string(),
+                    retry_after,
                 })
             }
         }
     }
 
-    fn build_request(&self, request_builder: RequestBuilder, node: &Node, _timeout: Duration) -> RequestBuilder {
-        let mut request_builder = request_builder.header(reqwest::header::USER_AGENT, &self.user_agent);
+    fn build_request(&self, method: HttpMethod, node: &Node, timeout: Duration) -> HttpRequest {
+        HttpRequest::new(method, node.url.clone(), timeout).with_header("user-agent", &self.user_agent)
+    }
+
+    async fn send(&self, node: &Node, request: HttpRequest) -> Result<Response> {
+        let response = match &self.auth_provider {
+            Some(auth_provider) => {
+                let response = self.send_authorized(node, request.clone()).await?;
+                // Give the provider the chance to refresh rejected credentials
+                if matches!(response.status, 401 | 403) && auth_provider.on_unauthorized(&node.url).await? {
+                    self.send_authorized(node, request).await?
+                } else {
+                    response
+                }
+            }
+            None => self.send_authorized(node, request).await?,
+        };
+        Self::parse_response(response, &node.url)
+    }
 
-        if let Some(node_auth) = &node.auth {
+    // Sets the credentials of the node, from the auth provider if there is one, and sends the request.
+    async fn send_authorized(&self, node: &Node, mut request: HttpRequest) -> Result<HttpResponse> {
+        let node_auth = match &self.auth_provider {
+            Some(auth_provider) => auth_provider.credentials(node).await?,
+            None => node.auth.clone(),
+        };
+        if let Some(node_auth) = node_auth {
             if let Some(jwt) = &node_auth.jwt {
-                request_builder = request_builder.bearer_auth(jwt);
+                request = request.with_header("authorization", format!("Bearer {jwt}"));
+            }
+            if let Some((name, password)) = &node_auth.basic_auth_name_pwd {
+                request
+                    .url
+                    .set_username(name)
+                    .map_err(|_| Error::Auth("can't set username to URL".to_string()))?;
+                request
+                    .url
+                    .set_password(Some(password))
+                    .map_err(|_| Error::Auth("can't set password to URL".to_string()))?;
             }
         }
-        #[cfg(not(target_family = "wasm"))]
-        {
-            request_builder = request_builder.timeout(_timeout);
-        }
-        request_builder
-    }
 
-    pub(crate) async fn get(&self, node: Node, timeout: Duration) -> Result<Response> {
-        let mut request_builder = self.client.get(node.url.clone());
-        request_builder = self.build_request(request_builder, &node, timeout);
+        let method = request.method;
         let start_time = instant::Instant::now();
-        let resp = request_builder.send().await?;
+        let response = match &self.transport {
+            Some(transport) => transport.0.send(request).await?,
+            None => self.default_transport.send(request).await?,
+        };
         log::debug!(
-            "GET: {:?} ms for {} {}",
+            "{method:?}: {:?} ms for {} {}",
             start_time.elapsed().as_millis(),
-            resp.status(),
+            response.status,
             node.url
         );
-        Self::parse_response(resp, &node.url).await
+        Ok(response)
+    }
+
+    pub(crate) async fn get(&self, node: Node, timeout: Duration) -> Result<Response> {
+        let request = self.build_request(HttpMethod::Get, &node, timeout);
+        self.send(&node, request).await
     }
 
     // Get with header: "accept", "application/vnd.iota.serializer-v1"
     pub(crate) async fn get_bytes(&self, node: Node, timeout: Duration) -> Result<Response> {
-        let mut request_builder = self.client.get(node.url.clone());
-        request_builder = self.build_request(request_builder, &node, timeout);
-        request_builder = request_builder.header("accept", "application/vnd.iota.serializer-v1");
-        let resp = request_builder.send().await?;
-        Self::parse_response(resp, &node.url).await
+        let request = self
+            .build_request(HttpMethod::Get, &node, timeout)
+            .with_header("accept", "application/vnd.iota.serializer-v1");
+        self.send(&node, request).await
     }
 
     pub(crate) async fn post_json(&self, node: Node, timeout: Duration, json: Value) -> Result<Response> {
-        let mut request_builder = self.client.post(node.url.clone());
-        request_builder = self.build_request(request_builder, &node, timeout);
-        Self::parse_response(request_builder.json(&json).send().await?, &node.url).await
+        let request = self
+            .build_request(HttpMethod::Post, &node, timeout)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_vec(&json)?);
+        self.send(&node, request).await
     }
 
     pub(crate) async fn post_bytes(&self, node: Node, timeout: Duration, body: &[u8]) -> Result<Response> {
-        let mut request_builder = self.client.post(node.url.clone());
-        request_builder = self.build_request(request_builder, &node, timeout);
-        request_builder = request_builder.header("Content-Type", "application/vnd.iota.serializer-v1");
-        Self::parse_response(request_builder.body(body.to_vec()).send().await?, &node.url).await
+        let request = self
+            .build_request(HttpMethod::Post, &node, timeout)
+            .with_header("content-type", "application/vnd.iota.serializer-v1")
+            .with_body(body.to_vec());
+        self.send(&node, request).await
     }
 }
@@ -3,11 +3,17 @@
 
 //! The node manager that takes care of sending requests with healthy nodes and quorum if enabled
 
+pub mod auth;
 pub mod builder;
+pub mod circuit_breaker;
 pub(crate) mod http_client;
 /// Structs for nodes
 pub mod node;
+pub mod quorum;
+pub mod retry;
+pub mod scoring;
 pub(crate) mod syncing;
+pub mod transport;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -19,7 +25,14 @@ use std::{
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 
-use self::{http_client::HttpClient, node::Node};
+use self::{
+    circuit_breaker::CircuitBreakers,
+    http_client::{HttpClient, Response},
+    node::Node,
+    quorum::{QuorumEndpoint, QuorumNodeResponse, QuorumReport},
+    retry::RetryPolicy,
+    scoring::{NodeScores, NodeSelectionPolicy, RouteClass},
+};
 use super::ClientInner;
 #[cfg(not(target_family = "wasm"))]
 use crate::client::request_pool::RateLimitExt;
@@ -44,6 +57,11 @@ pub struct NodeManager {
     quorum: bool,
     min_quorum_size: usize,
     quorum_threshold: usize,
+    quorum_endpoints: HashSet<QuorumEndpoint>,
+    node_selection_policy: NodeSelectionPolicy,
+    pub(crate) scores: NodeScores,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) circuit_breakers: CircuitBreakers,
     pub(crate) http_client: HttpClient,
 }
 
@@ -59,7 +77,12 @@ impl Debug for NodeManager {
         d.field("healthy_nodes", &self.healthy_nodes);
         d.field("quorum", &self.quorum);
         d.field("min_quorum_size", &self.min_quorum_size);
-        d.field("quorum_threshold", &self.quorum_threshold).finish()
+        d.field("quorum_threshold", &self.quorum_threshold);
+        d.field("quorum_endpoints", &self.quorum_endpoints);
+        d.field("node_selection_policy", &self.node_selection_policy);
+        d.field("scores", &self.scores);
+        d.field("retry_policy", &self.retry_policy);
+        d.field("circuit_breakers", &self.circuit_breakers).finish()
     }
 }
 
@@ -68,16 +91,21 @@ impl ClientInner {
         &self,
         path: &str,
         query: Option<&str>,
-        need_quorum: bool,
+        quorum_endpoint: Option<QuorumEndpoint>,
         prefer_permanode: bool,
     ) -> Result<T> {
         let node_manager = self.node_manager.read().await;
-        let request = node_manager.get_request(path, query, self.get_timeout().await, need_quorum, prefer_permanode);
+        let request =
+            node_manager.get_request(path, query, self.get_timeout().await, quorum_endpoint, prefer_permanode);
         #[cfg(not(target_family = "wasm"))]
         let request = request.rate_limit(&self.request_pool);
         request.await
     }
 
+    pub(crate) async fn has_permanodes(&self) -> bool {
+        self.node_manager.read().await.has_permanodes()
+    }
+
     pub(crate) async fn get_request_bytes(&self, path: &str, query: Option<&str>) -> Result<Vec<u8>> {
         let node_manager = self.node_manager.read().await;
         let request = node_manager.get_request_bytes(path, query, self.get_timeout().await);
@@ -105,6 +133,47 @@ impl NodeManager {
         NodeManagerBuilder::new()
     }
 
+    pub(crate) fn has_permanodes(&self) -> bool {
+        self.permanodes.iter().any(|permanode| !permanode.disabled)
+    }
+
+    // Records the outcome of a request in the score and the circuit breaker of the node.
+    fn record_request<T>(
+        &self,
+        node: &Node,
+        route_class: RouteClass,
+        latency: Duration,
+        result: &std::result::Result<T, crate::client::node_api::error::Error>,
+    ) {
+        self.scores.record_request(node, route_class, latency, result);
+        self.circuit_breakers.record(node, result);
+    }
+
+    // Returns the start time of a request that is sent to the node.
+    fn start_request(&self, node: &Node) -> instant::Instant {
+        self.circuit_breakers.start_request(node);
+        instant::Instant::now()
+    }
+
+    // Waits before the next attempt of a request and returns true, if the retry policy allows to retry one of the
+    // errors of the nodes in the current attempt.
+    async fn retry(&self, attempt: &mut u32, errors: &mut NodeErrors) -> bool {
+        match self.retry_policy.retry_delay(*attempt, &errors.attempt_errors) {
+            Some(delay) => {
+                log::debug!(
+                    "Retrying request in {} ms after: {:?}",
+                    delay.as_millis(),
+                    errors.attempt_errors
+                );
+                errors.attempt_errors.clear();
+                retry::sleep(delay).await;
+                *attempt += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
     fn get_nodes(
         &self,
         path: &str,
@@ -137,7 +206,7 @@ impl NodeManager {
         }
 
         // Add other nodes in random order, so they are not always used in the same order
-        let nodes_random_order = if !self.ignore_node_health {
+        let mut nodes_random_order = if !self.ignore_node_health {
             #[cfg(not(target_family = "wasm"))]
             {
                 self.healthy_nodes
@@ -158,17 +227,25 @@ impl NodeManager {
                             Some(n.clone())
                         }
                     })
-                    .collect()
+                    .collect::<Vec<_>>()
             }
             #[cfg(target_family = "wasm")]
             {
-                self.nodes.clone()
+                self.nodes.iter().cloned().collect()
             }
         } else {
-            self.nodes.clone()
+            self.nodes.iter().cloned().collect::<Vec<_>>()
         };
 
-        // Add remaining nodes in random order
+        // Order the remaining nodes by their scores
+        self.scores.order(
+            &mut nodes_random_order,
+            path,
+            RouteClass::from_path(path, use_pow_nodes),
+            self.node_selection_policy,
+        );
+
+        // Add remaining nodes
         for node in nodes_random_order {
             if !nodes_with_modified_url.iter().any(|n| n.url == node.url) {
                 nodes_with_modified_url.push(node);
@@ -178,6 +255,9 @@ impl NodeManager {
         // remove disabled nodes
         nodes_with_modified_url.retain(|n| !n.disabled);
 
+        // Skip nodes that failed repeatedly, as long as there are others
+        self.circuit_breakers.filter(&mut nodes_with_modified_url);
+
         if nodes_with_modified_url.is_empty() {
             if use_pow_nodes {
                 return Err(crate::client::Error::Node(
@@ -189,36 +269,84 @@ impl NodeManager {
 
         // Set path and query parameters
         for node in &mut nodes_with_modified_url {
-            node.url.set_path(path);
-            node.url.set_query(query);
-            if let Some(auth) = &node.auth {
-                if let Some((name, password)) = &auth.basic_auth_name_pwd {
-                    node.url
-                        .set_username(name)
-                        .map_err(|_| crate::client::Error::UrlAuth("username"))?;
-                    node.url
-                        .set_password(Some(password))
-                        .map_err(|_| crate::client::Error::UrlAuth("password"))?;
-                }
-            }
+            set_request_url(node, path, query)?;
         }
 
         Ok(nodes_with_modified_url)
     }
 
+    // Returns whether the nodes could have pruned the data requested with the path, so the permanodes should be asked
+    // when the nodes don't find it.
+    fn may_be_pruned(&self, path: &str) -> bool {
+        if !self.has_permanodes() {
+            return false;
+        }
+        // Unknown if the nodes weren't synced yet
+        let pruning_index = self
+            .healthy_nodes
+            .read()
+            .ok()
+            .and_then(|healthy_nodes| healthy_nodes.values().map(|info| info.status.pruning_index).max());
+
+        if let Some(index) = path.strip_prefix("api/core/v2/milestones/by-index/") {
+            if let Ok(index) = index.split('/').next().unwrap_or_default().parse::<u32>() {
+                return pruning_index.map_or(true, |pruning_index| index <= pruning_index);
+            }
+        }
+
+        PERMANODE_ROUTES.iter().any(|route| path.starts_with(route))
+            && pruning_index.map_or(true, |pruning_index| pruning_index > 0)
+    }
+
+    // Sends a GET request to the permanodes, until one of them returns the data.
+    async fn get_request_from_permanodes(
+        &self,
+        path: &str,
+        query: Option<&str>,
+        timeout: Duration,
+        bytes: bool,
+    ) -> Result<Response> {
+        log::debug!("{path} not found on the nodes, requesting it from the permanodes");
+        let mut error = None;
+        for permanode in self.permanodes.iter().filter(|permanode| !permanode.disabled) {
+            let mut node = permanode.clone();
+            set_request_url(&mut node, path, query)?;
+
+            let start_time = self.start_request(&node);
+            let res = if bytes {
+                self.http_client.get_bytes(node.clone(), timeout).await
+            } else {
+                self.http_client.get(node.clone(), timeout).await
+            };
+            self.record_request(&node, RouteClass::Core, start_time.elapsed(), &res);
+            match res {
+                Ok(res) => return Ok(res),
+                Err(err) => {
+                    error.replace(err.into());
+                }
+            }
+        }
+        Err(error.unwrap_or(Error::HealthyNodePoolEmpty))
+    }
+
     pub(crate) async fn get_request<T: DeserializeOwned + Debug + Serialize>(
         &self,
         path: &str,
         query: Option<&str>,
         timeout: Duration,
-        need_quorum: bool,
+        quorum_endpoint: Option<QuorumEndpoint>,
         prefer_permanode: bool,
     ) -> Result<T> {
-        let mut result: HashMap<String, usize> = HashMap::new();
+        let quorum_endpoint =
+            quorum_endpoint.filter(|endpoint| self.quorum && self.quorum_endpoints.contains(endpoint));
+        // Responses and how many nodes returned them, by response hash
+        let mut result: HashMap<String, (String, usize)> = HashMap::new();
+        let mut responses = Vec::new();
         // primary_pow_node should only be used for post request with remote PoW
         // Get node urls and set path
         let nodes = self.get_nodes(path, query, false, prefer_permanode)?;
-        if self.quorum && need_quorum && nodes.len() < self.min_quorum_size {
+        let route_class = RouteClass::from_path(path, false);
+        if quorum_endpoint.is_some() && nodes.len() < self.min_quorum_size {
             return Err(Error::QuorumPoolSizeError {
                 available_nodes: nodes.len(),
                 minimum_threshold: self.min_quorum_size,
@@ -227,100 +355,158 @@ impl NodeManager {
 
         // Track amount of results for quorum
         let mut result_counter = 0;
-        let mut error: Option<Error> = None;
+        let mut errors = NodeErrors::default();
         // Send requests parallel for quorum
         #[cfg(target_family = "wasm")]
         let wasm = true;
         #[cfg(not(target_family = "wasm"))]
         let wasm = false;
-        if !wasm && self.quorum && need_quorum && query.is_none() {
+        if !wasm && quorum_endpoint.is_some() {
             #[cfg(not(target_family = "wasm"))]
             {
-                let mut tasks = Vec::new();
-                for (index, node) in nodes.into_iter().enumerate() {
-                    if index < self.min_quorum_size {
+                let mut nodes = nodes;
+                let mut attempt = 0;
+                loop {
+                    let mut tasks = Vec::new();
+                    for node in nodes.into_iter().take(self.min_quorum_size) {
                         let client_ = self.http_client.clone();
-                        tasks.push(async move { tokio::spawn(async move { client_.get(node, timeout).await }).await });
+                        let start_time = self.start_request(&node);
+                        tasks.push(async move {
+                            tokio::spawn(async move {
+                                let res = client_.get(node.clone(), timeout).await;
+                                (node, start_time.elapsed(), res)
+                            })
+                            .await
+                        });
                     }
-                }
-                for res in futures::future::try_join_all(tasks).await? {
-                    match res {
-                        Ok(res) => (res.into_text().await).map_or_else(
-                            |_| {
-                                log::warn!("couldn't convert node response to text");
-                            },
-                            |res_text| {
-                                let counters = result.entry(res_text).or_insert(0);
-                                *counters += 1;
-                                result_counter += 1;
+                    for (node, latency, res) in futures::future::try_join_all(tasks).await? {
+                        self.record_request(&node, route_class, latency, &res);
+                        match res {
+                            Ok(res) => match res.into_text().await {
+                                Ok(res_text) => match quorum::response_hash(&res_text) {
+                                    Ok(response_hash) => {
+                                        responses.push(QuorumNodeResponse::new(&node.url, response_hash.clone()));
+                                        result.entry(response_hash).or_insert((res_text, 0)).1 += 1;
+                                        result_counter += 1;
+                                    }
+                                    Err(err) => {
+                                        log::warn!("couldn't hash node response: {err}");
+                                        responses.push(QuorumNodeResponse::failed(&node.url, &err));
+                                        errors.push(err);
+                                    }
+                                },
+                                Err(err) => {
+                                    log::warn!("couldn't convert node response to text");
+                                    responses.push(QuorumNodeResponse::failed(&node.url, &err));
+                                    errors.push(err.into());
+                                }
                             },
-                        ),
-                        Err(err) => {
-                            error.replace(err.into());
+                            Err(err) => {
+                                responses.push(QuorumNodeResponse::failed(&node.url, &err));
+                                errors.push(err.into());
+                            }
                         }
                     }
+                    // Retry if not enough nodes responded to reach the quorum
+                    if (result_counter as f64) >= self.min_quorum_size as f64 * (self.quorum_threshold as f64 / 100.0)
+                        || !self.retry(&mut attempt, &mut errors).await
+                    {
+                        break;
+                    }
+                    result.clear();
+                    responses.clear();
+                    result_counter = 0;
+                    nodes = self.get_nodes(path, query, false, prefer_permanode)?;
                 }
             }
         } else {
-            // Send requests
-            for node in nodes {
-                match self.http_client.get(node.clone(), timeout).await {
-                    Ok(res) => {
-                        // Handle node_info extra because we also want to return the url
-                        if path == crate::client::node_api::core::routes::INFO_PATH {
-                            let node_info: InfoResponse = res.into_json().await?;
-                            let wrapper = crate::client::node_api::core::routes::NodeInfoWrapper {
-                                node_info,
-                                url: format!("{}://{}", node.url.scheme(), node.url.host_str().unwrap_or("")),
-                            };
-                            let serde_res = serde_json::to_string(&wrapper)?;
-                            return Ok(serde_json::from_str(&serde_res)?);
-                        }
+            let mut nodes = nodes;
+            let mut attempt = 0;
+            loop {
+                // Send requests
+                for node in nodes {
+                    let start_time = self.start_request(&node);
+                    let res = self.http_client.get(node.clone(), timeout).await;
+                    self.record_request(&node, route_class, start_time.elapsed(), &res);
+                    match res {
+                        Ok(res) => {
+                            // Handle node_info extra because we also want to return the url
+                            if path == crate::client::node_api::core::routes::INFO_PATH {
+                                let node_info: InfoResponse = res.into_json().await?;
+                                let wrapper = crate::client::node_api::core::routes::NodeInfoWrapper {
+                                    node_info,
+                                    url: format!("{}://{}", node.url.scheme(), node.url.host_str().unwrap_or("")),
+                                };
+                                let serde_res = serde_json::to_string(&wrapper)?;
+                                return Ok(serde_json::from_str(&serde_res)?);
+                            }
 
-                        match res.into_json::<T>().await {
-                            Ok(result_data) => {
-                                let counters = result.entry(serde_json::to_string(&result_data)?).or_insert(0);
-                                *counters += 1;
-                                result_counter += 1;
-                                // Without quorum it's enough if we got one response
-                                if !self.quorum
-                                    || result_counter >= self.min_quorum_size
-                                    || !need_quorum
-                                    // with query we ignore quorum because the nodes can store a different amount of history
-                                    || query.is_some()
-                                {
-                                    break;
+                            match res.into_json::<T>().await {
+                                Ok(result_data) => {
+                                    // Without quorum it's enough if we got one response
+                                    if quorum_endpoint.is_none() {
+                                        return Ok(result_data);
+                                    }
+                                    let res_text = serde_json::to_string(&result_data)?;
+                                    let response_hash = quorum::response_hash(&res_text)?;
+                                    responses.push(QuorumNodeResponse::new(&node.url, response_hash.clone()));
+                                    result.entry(response_hash).or_insert((res_text, 0)).1 += 1;
+                                    result_counter += 1;
+                                    if result_counter >= self.min_quorum_size {
+                                        break;
+                                    }
+                                }
+                                Err(e) => {
+                                    responses.push(QuorumNodeResponse::failed(&node.url, &e));
+                                    errors.push(e.into());
                                 }
-                            }
-                            Err(e) => {
-                                error.replace(e.into());
                             }
                         }
+                        Err(err) => {
+                            responses.push(QuorumNodeResponse::failed(&node.url, &err));
+                            errors.push(err.into());
+                        }
                     }
-                    Err(err) => {
-                        error.replace(err.into());
-                    }
                 }
+                if result_counter > 0 || !self.retry(&mut attempt, &mut errors).await {
+                    break;
+                }
+                nodes = self.get_nodes(path, query, false, prefer_permanode)?;
             }
         }
 
+        // Data that the nodes pruned can still be available on the permanodes
+        if result.is_empty() && !prefer_permanode && errors.is_not_found() && self.may_be_pruned(path) {
+            return Ok(self
+                .get_request_from_permanodes(path, query, timeout, false)
+                .await?
+                .into_json()
+                .await?);
+        }
+
         // Safe unwrap, there are nodes because we throw on empty nodepool.
         // Each node will throw an error or return Ok()
-        let res = result.into_iter().max_by_key(|v| v.1).ok_or_else(|| error.unwrap())?;
+        let (res_text, quorum_size) = result
+            .into_values()
+            .max_by_key(|(_, count)| *count)
+            .ok_or_else(|| errors.into_last().unwrap())?;
 
         // Return if quorum is false or check if quorum was reached
-        if !self.quorum
-            || res.1 as f64 >= self.min_quorum_size as f64 * (self.quorum_threshold as f64 / 100.0)
-            || !need_quorum
-            // with query we ignore quorum because the nodes can store a different amount of history
-            || query.is_some()
-        {
-            Ok(serde_json::from_str(&res.0)?)
-        } else {
-            Err(Error::QuorumThresholdError {
-                quorum_size: res.1,
-                minimum_threshold: self.min_quorum_size,
-            })
+        match quorum_endpoint {
+            Some(endpoint)
+                if (quorum_size as f64) < self.min_quorum_size as f64 * (self.quorum_threshold as f64 / 100.0) =>
+            {
+                Err(Error::QuorumThresholdError {
+                    quorum_size,
+                    minimum_threshold: self.min_quorum_size,
+                    report: QuorumReport {
+                        endpoint,
+                        path: path.to_string(),
+                        responses,
+                    },
+                })
+            }
+            _ => Ok(serde_json::from_str(&res_text)?),
         }
     }
 
@@ -333,25 +519,44 @@ impl NodeManager {
     ) -> Result<Vec<u8>> {
         // primary_pow_node should only be used for post request with remote Pow
         // Get node urls and set path
-        let nodes = self.get_nodes(path, query, false, false)?;
-        let mut error = None;
-        // Send requests
-        for node in nodes {
-            match self.http_client.get_bytes(node, timeout).await {
-                Ok(res) => {
-                    match res.into_bytes().await {
-                        Ok(res_text) => return Ok(res_text),
-                        Err(e) => error.replace(e.into()),
-                    };
-                }
-                Err(err) => {
-                    error.replace(err.into());
+        let mut nodes = self.get_nodes(path, query, false, false)?;
+        let route_class = RouteClass::from_path(path, false);
+        let mut errors = NodeErrors::default();
+        let mut attempt = 0;
+        loop {
+            // Send requests
+            for node in nodes {
+                let start_time = self.start_request(&node);
+                let res = self.http_client.get_bytes(node.clone(), timeout).await;
+                self.record_request(&node, route_class, start_time.elapsed(), &res);
+                match res {
+                    Ok(res) => {
+                        match res.into_bytes().await {
+                            Ok(res_text) => return Ok(res_text),
+                            Err(e) => errors.push(e.into()),
+                        };
+                    }
+                    Err(err) => {
+                        errors.push(err.into());
+                    }
                 }
             }
+            if !self.retry(&mut attempt, &mut errors).await {
+                break;
+            }
+            nodes = self.get_nodes(path, query, false, false)?;
+        }
+        // Data that the nodes pruned can still be available on the permanodes
+        if errors.is_not_found() && self.may_be_pruned(path) {
+            return Ok(self
+                .get_request_from_permanodes(path, query, timeout, true)
+                .await?
+                .into_bytes()
+                .await?);
         }
         // Safe unwrap, there are nodes because we throw on empty nodepool.
         // Each node will throw an error or return Ok()
-        Err(error.unwrap())
+        Err(errors.into_last().unwrap())
     }
 
     pub(crate) async fn post_request_bytes<T: DeserializeOwned>(
@@ -362,11 +567,16 @@ impl NodeManager {
         local_pow: bool,
     ) -> Result<T> {
         // primary_pow_node should only be used for post request with remote PoW
+        // Not retried, the request could have been processed by a node that failed to respond
         let nodes = self.get_nodes(path, None, !local_pow, false)?;
+        let route_class = RouteClass::from_path(path, !local_pow);
         let mut error = None;
         // Send requests
         for node in nodes {
-            match self.http_client.post_bytes(node, timeout, body).await {
+            let start_time = self.start_request(&node);
+            let res = self.http_client.post_bytes(node.clone(), timeout, body).await;
+            self.record_request(&node, route_class, start_time.elapsed(), &res);
+            match res {
                 Ok(res) => {
                     match res.into_json::<T>().await {
                         Ok(res) => return Ok(res),
@@ -391,11 +601,16 @@ impl NodeManager {
         local_pow: bool,
     ) -> Result<T> {
         // primary_pow_node should only be used for post request with remote PoW
+        // Not retried, the request could have been processed by a node that failed to respond
         let nodes = self.get_nodes(path, None, !local_pow, false)?;
+        let route_class = RouteClass::from_path(path, !local_pow);
         let mut error = None;
         // Send requests
         for node in nodes {
-            match self.http_client.post_json(node, timeout, json.clone()).await {
+            let start_time = self.start_request(&node);
+            let res = self.http_client.post_json(node.clone(), timeout, json.clone()).await;
+            self.record_request(&node, route_class, start_time.elapsed(), &res);
+            match res {
                 Ok(res) => {
                     match res.into_json::<T>().await {
                         Ok(res) => return Ok(res),
@@ -412,3 +627,83 @@ impl NodeManager {
         Err(error.unwrap())
     }
 }
+
+// Routes of data that nodes prune and permanodes keep.
+const PERMANODE_ROUTES: [&str; 4] = [
+    "api/core/v2/blocks/",
+    "api/core/v2/outputs/",
+    "api/core/v2/transactions/",
+    "api/core/v2/milestones/",
+];
+
+// The errors of the nodes a GET request was sent to.
+#[derive(Default)]
+struct NodeErrors {
+    // The errors of the current attempt
+    attempt_errors: Vec<Error>,
+    count: usize,
+    not_found: usize,
+}
+
+impl NodeErrors {
+    fn push(&mut self, error: Error) {
+        if matches!(error, Error::Node(crate::client::node_api::error::Error::NotFound(_))) {
+            self.not_found += 1;
+        }
+        self.count += 1;
+        self.attempt_errors.push(error);
+    }
+
+    // Returns the last error of the current attempt.
+    fn into_last(mut self) -> Option<Error> {
+        self.attempt_errors.pop()
+    }
+
+    // Returns whether all nodes failed because they didn't find the data.
+    fn is_not_found(&self) -> bool {
+        self.count > 0 && self.count == self.not_found
+    }
+}
+
+// Sets the path, query parameters and basic auth credentials of a request to a node.
+fn set_request_url(node: &mut Node, path: &str, query: Option<&str>) -> Result<()> {
+    node.url.set_path(path);
+    node.url.set_query(query);
+    if let Some(auth) = &node.auth {
+        if let Some((name, password)) = &auth.basic_auth_name_pwd {
+            node.url
+                .set_username(name)
+                .map_err(|_| crate::client::Error::UrlAuth("username"))?;
+            node.url
+                .set_password(Some(password))
+                .map_err(|_| crate::client::Error::UrlAuth("password"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::node_api::error::Error as NodeApiError;
+
+    #[test]
+    fn not_found_on_all_nodes() {
+        let mut errors = NodeErrors::default();
+        assert!(!errors.is_not_found());
+
+        errors.push(Error::Node(NodeApiError::NotFound("node0".to_owned())));
+        errors.push(Error::Node(NodeApiError::NotFound("node1".to_owned())));
+        assert!(errors.is_not_found());
+
+        // Another node may still have the data
+        let mut errors = NodeErrors::default();
+        errors.push(Error::Node(NodeApiError::Transport("timeout".to_owned())));
+        errors.push(Error::Node(NodeApiError::NotFound("node1".to_owned())));
+        assert!(!errors.is_not_found());
+        assert!(matches!(
+            errors.into_last(),
+            Some(Error::Node(NodeApiError::NotFound(_)))
+        ));
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Quorum of the responses of multiple nodes.
+
+use std::collections::{HashMap, HashSet};
+
+use crypto::hashes::{blake2b::Blake2b256, Digest};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use crate::client::{node_manager::scoring::base_url, Result};
+
+/// The endpoint classes for which the responses of multiple nodes can be compared when quorum is enabled.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuorumEndpoint {
+    /// Outputs with their metadata.
+    Outputs,
+    /// Output metadata.
+    OutputMetadata,
+    /// Blocks, their metadata and the blocks including transactions.
+    Blocks,
+    /// Milestones and their UTXO changes.
+    Milestones,
+    /// Output ids from the indexer plugin, every page is compared separately.
+    Indexer,
+}
+
+impl QuorumEndpoint {
+    /// Returns all endpoint classes.
+    pub fn all() -> HashSet<Self> {
+        [
+            Self::Outputs,
+            Self::OutputMetadata,
+            Self::Blocks,
+            Self::Milestones,
+            Self::Indexer,
+        ]
+        .into_iter()
+        .collect()
+    }
+}
+
+/// The response of a node to a request that required quorum.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuorumNodeResponse {
+    /// The url of the node.
+    pub url: Url,
+    /// The hash of the response, `None` if the request failed.
+    pub response_hash: Option<String>,
+    /// The error of the request, if it failed.
+    pub error: Option<String>,
+}
+
+impl QuorumNodeResponse {
+    pub(crate) fn new(url: &Url, response_hash: String) -> Self {
+        Self {
+            url: base_url(url),
+            response_hash: Some(response_hash),
+            error: None,
+        }
+    }
+
+    pub(crate) fn failed(url: &Url, error: impl ToString) -> Self {
+        Self {
+            url: base_url(url),
+            response_hash: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// The responses of the nodes to a request for which quorum wasn't reached.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuorumReport {
+    /// The endpoint class of the request.
+    pub endpoint: QuorumEndpoint,
+    /// The path of the request.
+    pub path: String,
+    /// The responses of the nodes.
+    pub responses: Vec<QuorumNodeResponse>,
+}
+
+impl QuorumReport {
+    /// Returns the urls of the nodes per response hash.
+    pub fn nodes_by_response_hash(&self) -> HashMap<&str, Vec<&Url>> {
+        let mut nodes_by_response_hash: HashMap<&str, Vec<&Url>> = HashMap::new();
+        for response in &self.responses {
+            if let Some(response_hash) = &response.response_hash {
+                nodes_by_response_hash
+                    .entry(response_hash)
+                    .or_default()
+                    .push(&response.url);
+            }
+        }
+        nodes_by_response_hash
+    }
+}
+
+/// Returns the hash of a JSON response, ignoring the ledger index at which the node answered.
+pub(crate) fn response_hash(response: &str) -> Result<String> {
+    let mut value: Value = serde_json::from_str(response)?;
+    if let Value::Object(object) = &mut value {
+        object.remove("ledgerIndex");
+        if let Some(Value::Object(metadata)) = object.get_mut("metadata") {
+            metadata.remove("ledgerIndex");
+        }
+    }
+
+    Ok(prefix_hex::encode(
+        Blake2b256::digest(serde_json::to_vec(&value)?).as_slice(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_hash_ignores_ledger_index() {
+        assert_eq!(
+            response_hash(r#"{"metadata":{"isSpent":false,"ledgerIndex":1},"output":{}}"#).unwrap(),
+            response_hash(r#"{"metadata":{"isSpent":false,"ledgerIndex":2},"output":{}}"#).unwrap()
+        );
+        assert_ne!(
+            response_hash(r#"{"isSpent":false,"ledgerIndex":1}"#).unwrap(),
+            response_hash(r#"{"isSpent":true,"ledgerIndex":1}"#).unwrap()
+        );
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The policy for retrying requests that failed on all nodes.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::{
+    constants::{
+        DEFAULT_RETRY_INITIAL_BACKOFF, DEFAULT_RETRY_JITTER, DEFAULT_RETRY_MAX_ATTEMPTS, DEFAULT_RETRY_MAX_BACKOFF,
+    },
+    node_api::error::Error as NodeApiError,
+    Error,
+};
+
+/// The kinds of errors after which a request is retried.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RetryableError {
+    /// The node couldn't be reached, the request timed out or the transport failed.
+    Connection,
+    /// The node rate limited the request (429).
+    TooManyRequests,
+    /// The node is temporarily unavailable (503).
+    ServiceUnavailable,
+    /// Any other server error (5xx).
+    ServerError,
+}
+
+impl RetryableError {
+    fn matches(&self, error: &NodeApiError) -> bool {
+        match (self, error) {
+            (Self::Connection, NodeApiError::Reqwest(_) | NodeApiError::Transport(_)) => true,
+            (Self::TooManyRequests, NodeApiError::ResponseError { code, .. }) => *code == 429,
+            (Self::ServiceUnavailable, NodeApiError::ResponseError { code, .. }) => *code == 503,
+            (Self::ServerError, NodeApiError::ResponseError { code, .. }) => (500..600).contains(code),
+            _ => false,
+        }
+    }
+}
+
+/// The policy for retrying a GET request once it failed on all available nodes, other requests aren't retried as they
+/// could have been processed by a node before failing. The delay between two attempts grows exponentially from
+/// `initial_backoff` up to `max_backoff`. A longer delay requested by a node with a `Retry-After` header is respected,
+/// but doesn't exceed `max_backoff` either.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryPolicy {
+    /// The maximum amount of attempts of a request, including the first one. Every attempt tries all available nodes.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// The delay before the first retry
+    #[serde(default = "default_initial_backoff")]
+    pub initial_backoff: Duration,
+    /// The maximum delay between two attempts
+    #[serde(default = "default_max_backoff")]
+    pub max_backoff: Duration,
+    /// % by which the delay is randomly varied, so clients don't retry at the same time
+    #[serde(default = "default_jitter")]
+    pub jitter: usize,
+    /// The errors after which a request is retried
+    #[serde(default = "default_retry_on")]
+    pub retry_on: Vec<RetryableError>,
+}
+
+fn default_max_attempts() -> u32 {
+    DEFAULT_RETRY_MAX_ATTEMPTS
+}
+
+fn default_initial_backoff() -> Duration {
+    DEFAULT_RETRY_INITIAL_BACKOFF
+}
+
+fn default_max_backoff() -> Duration {
+    DEFAULT_RETRY_MAX_BACKOFF
+}
+
+fn default_jitter() -> usize {
+    DEFAULT_RETRY_JITTER
+}
+
+fn default_retry_on() -> Vec<RetryableError> {
+    vec![
+        RetryableError::Connection,
+        RetryableError::TooManyRequests,
+        RetryableError::ServiceUnavailable,
+    ]
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_RETRY_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_RETRY_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_RETRY_MAX_BACKOFF,
+            jitter: DEFAULT_RETRY_JITTER,
+            retry_on: default_retry_on(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy retrying a request up to `max_attempts` times in total, with the default backoff.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the delay before the first retry and the maximum delay between two attempts.
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the % by which the delay is randomly varied.
+    pub fn with_jitter(mut self, jitter: usize) -> Self {
+        self.jitter = jitter.min(100);
+        self
+    }
+
+    /// Sets the errors after which a request is retried.
+    pub fn with_retry_on(mut self, retry_on: impl Into<Vec<RetryableError>>) -> Self {
+        self.retry_on = retry_on.into();
+        self
+    }
+
+    /// Returns whether a request that failed with the error is retried.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        match error {
+            Error::Node(error) => self.retry_on.iter().any(|retryable| retryable.matches(error)),
+            _ => false,
+        }
+    }
+
+    /// Returns the delay before the next attempt, if a request that failed on the nodes with the errors in the attempt
+    /// with index `attempt` is retried. It's retried if one of the errors is retryable, after the longest delay one of
+    /// the nodes asked for.
+    pub(crate) fn retry_delay(&self, attempt: u32, errors: &[Error]) -> Option<Duration> {
+        if attempt + 1 >= self.max_attempts {
+            return None;
+        }
+        let mut retryable_errors = errors.iter().filter(|error| self.is_retryable(error)).peekable();
+        retryable_errors.peek()?;
+        let retry_after = retryable_errors
+            .filter_map(|error| match error {
+                Error::Node(error) => error.retry_after(),
+                _ => None,
+            })
+            .max();
+
+        let mut random = [0u8; 4];
+        // Random factor between 1 - jitter% and 1 + jitter%
+        let factor = if self.jitter > 0 && crypto::utils::rand::fill(&mut random).is_ok() {
+            let random = u32::from_le_bytes(random) as f64 / u32::MAX as f64;
+            random
+                .mul_add(2.0, -1.0)
+                .mul_add(self.jitter.min(100) as f64 / 100.0, 1.0)
+        } else {
+            1.0
+        };
+
+        Some(self.backoff(attempt, factor, retry_after))
+    }
+
+    // Returns the delay before the next attempt with the jitter factor applied, or the longer delay a node asked for,
+    // which doesn't exceed `max_backoff`.
+    fn backoff(&self, attempt: u32, jitter_factor: f64, retry_after: Option<Duration>) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .mul_f64(jitter_factor)
+            .min(self.max_backoff);
+
+        retry_after.map_or(backoff, |retry_after| retry_after.max(backoff).min(self.max_backoff))
+    }
+}
+
+/// Waits before retrying a request.
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(target_family = "wasm")]
+    gloo_timers::future::TimeoutFuture::new(duration.as_millis() as u32).await;
+    #[cfg(not(target_family = "wasm"))]
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response_error(code: u16, retry_after: Option<Duration>) -> Error {
+        Error::Node(NodeApiError::ResponseError {
+            code,
+            text: String::new(),
+            url: "http://localhost:14265/api/core/v2/info".to_owned(),
+            retry_after,
+        })
+    }
+
+    #[test]
+    fn retryable_errors() {
+        let policy = RetryPolicy::default();
+        assert!(policy.is_retryable(&Error::Node(NodeApiError::Transport("timeout".to_owned()))));
+        assert!(policy.is_retryable(&response_error(429, None)));
+        assert!(policy.is_retryable(&response_error(503, None)));
+        assert!(!policy.is_retryable(&response_error(500, None)));
+        assert!(!policy.is_retryable(&response_error(400, None)));
+        assert!(!policy.is_retryable(&Error::Node(NodeApiError::NotFound(String::new()))));
+        assert!(!policy.is_retryable(&Error::HealthyNodePoolEmpty));
+
+        let policy = policy.with_retry_on([RetryableError::ServerError]);
+        assert!(policy.is_retryable(&response_error(500, None)));
+        assert!(!policy.is_retryable(&Error::Node(NodeApiError::Transport("timeout".to_owned()))));
+    }
+
+    #[test]
+    fn retry_delay_grows_until_max_attempts() {
+        let policy = RetryPolicy::new(4)
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(10))
+            .with_jitter(0);
+        let errors = [response_error(503, None)];
+
+        assert_eq!(policy.retry_delay(0, &errors), Some(Duration::from_millis(100)));
+        assert_eq!(policy.retry_delay(1, &errors), Some(Duration::from_millis(200)));
+        assert_eq!(policy.retry_delay(2, &errors), Some(Duration::from_millis(400)));
+        assert_eq!(policy.retry_delay(3, &errors), None);
+        assert_eq!(policy.retry_delay(0, &[]), None);
+        assert_eq!(policy.retry_delay(0, &[response_error(400, None)]), None);
+    }
+
+    #[test]
+    fn retry_delay_is_clamped_after_jitter() {
+        let policy = RetryPolicy::new(10)
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(1000))
+            .with_jitter(50);
+        let errors = [response_error(503, None)];
+
+        assert_eq!(policy.backoff(9, 1.5, None), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(0, 1.5, None), Duration::from_millis(150));
+        assert_eq!(policy.backoff(0, 0.5, None), Duration::from_millis(50));
+        for attempt in 0..9 {
+            assert!(policy.retry_delay(attempt, &errors).unwrap() <= Duration::from_millis(1000));
+        }
+    }
+
+    #[test]
+    fn retry_delay_respects_retry_after() {
+        let policy = RetryPolicy::new(3)
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .with_jitter(0);
+
+        assert_eq!(
+            policy.retry_delay(0, &[response_error(429, Some(Duration::from_millis(500)))]),
+            Some(Duration::from_millis(500))
+        );
+        // The requested delay is capped
+        assert_eq!(
+            policy.retry_delay(0, &[response_error(429, Some(Duration::from_secs(3600)))]),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.retry_delay(1, &[response_error(429, Some(Duration::from_millis(10)))]),
+            Some(Duration::from_millis(200))
+        );
+    }
+    #[test]
+    fn retry_is_decided_from_all_node_errors() {
+        let policy = RetryPolicy::new(3)
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .with_jitter(0);
+
+        // The node that was rate limited may still have the data
+        let errors = [
+            response_error(429, Some(Duration::from_millis(300))),
+            response_error(503, Some(Duration::from_millis(500))),
+            Error::Node(NodeApiError::NotFound(String::new())),
+        ];
+        assert_eq!(policy.retry_delay(0, &errors), Some(Duration::from_millis(500)));
+
+        let errors = [
+            response_error(400, None),
+            Error::Node(NodeApiError::NotFound(String::new())),
+        ];
+        assert_eq!(policy.retry_delay(0, &errors), None);
+    }
+}
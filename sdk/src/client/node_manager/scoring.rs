@@ -0,0 +1,450 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scores of the nodes, used to select the node(s) a request is sent to.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, RwLock},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::client::{
+    constants::{MAX_NODE_ERROR_RATE, MAX_NODE_MILESTONE_LAG, NODE_SCORE_SMOOTHING_FACTOR},
+    node_api::error::Error as NodeApiError,
+    node_manager::node::Node,
+    ClientInner, Error, Result,
+};
+
+/// The policy used to order the nodes for a request. The primary node, and the primary PoW node for remote PoW, are
+/// always tried first. Nodes that are lagging behind, fail often or don't support the requested route are always
+/// tried last.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NodeSelectionPolicy {
+    /// Nodes are tried in random order.
+    #[default]
+    Random,
+    /// Nodes are tried in order of their average latency.
+    LowestLatency,
+    /// Nodes are rotated for every request of a route class, to spread the load.
+    RoundRobin,
+    /// The last node that successfully answered a request of a route class is tried first, until it fails. The other
+    /// nodes are tried in order of their average latency.
+    Sticky,
+}
+
+/// The class of a route, nodes are selected separately for every class.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RouteClass {
+    /// Core API routes and routes of other plugins.
+    Core,
+    /// Indexer plugin routes.
+    Indexer,
+    /// Participation plugin routes.
+    Participation,
+    /// Requests with remote PoW.
+    Pow,
+}
+
+impl RouteClass {
+    /// Returns the route class of a request path.
+    pub fn from_path(path: &str, use_pow_nodes: bool) -> Self {
+        if use_pow_nodes {
+            Self::Pow
+        } else if path.starts_with("api/indexer/") {
+            Self::Indexer
+        } else if path.starts_with("api/participation/") {
+            Self::Participation
+        } else {
+            Self::Core
+        }
+    }
+}
+
+/// The score of a node, computed from the requests sent to it and from its info and routes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeScore {
+    /// The url of the node.
+    pub url: Url,
+    /// The amount of requests sent to the node.
+    pub requests: u64,
+    /// The amount of requests that failed because of the node.
+    pub errors: u64,
+    /// The exponential moving average of the latency of the node, `None` until a request was sent to it.
+    pub latency: Option<Duration>,
+    /// The exponential moving average of the failed requests, between 0 and 1.
+    pub error_rate: f64,
+    /// The amount of milestones the node is behind the most synced node, `None` until the node was synced.
+    pub milestone_lag: Option<u32>,
+    /// The route groups supported by the node, like "core/v2" or "indexer/v1", `None` if they're not known.
+    pub routes: Option<Vec<String>>,
+    /// If the node supports remote PoW, `None` until the node was synced.
+    pub pow: Option<bool>,
+}
+
+impl NodeScore {
+    fn new(url: Url) -> Self {
+        Self {
+            url,
+            requests: 0,
+            errors: 0,
+            latency: None,
+            error_rate: 0.0,
+            milestone_lag: None,
+            routes: None,
+            pow: None,
+        }
+    }
+
+    /// Returns whether the node is lagging behind or fails too many requests.
+    pub fn is_degraded(&self) -> bool {
+        self.error_rate > MAX_NODE_ERROR_RATE || self.milestone_lag.map_or(false, |lag| lag > MAX_NODE_MILESTONE_LAG)
+    }
+
+    /// Returns whether the node supports the route of a request path, assuming it does if its routes are unknown.
+    pub fn supports(&self, path: &str, route_class: RouteClass) -> bool {
+        if route_class == RouteClass::Pow && self.pow == Some(false) {
+            return false;
+        }
+        match (&self.routes, route_group(path)) {
+            (Some(routes), Some(route_group)) => routes.iter().any(|route| route == route_group),
+            _ => true,
+        }
+    }
+
+    fn record_request(&mut self, latency: Duration, failed: bool) {
+        self.requests += 1;
+        self.errors += failed as u64;
+        self.latency = Some(match self.latency {
+            Some(average) => {
+                average.mul_f64(1.0 - NODE_SCORE_SMOOTHING_FACTOR) + latency.mul_f64(NODE_SCORE_SMOOTHING_FACTOR)
+            }
+            None => latency,
+        });
+        self.error_rate = self.error_rate.mul_add(
+            1.0 - NODE_SCORE_SMOOTHING_FACTOR,
+            NODE_SCORE_SMOOTHING_FACTOR * (failed as u8 as f64),
+        );
+    }
+}
+
+// Returns the route group of a path, "api/indexer/v1/outputs" has the route group "indexer/v1".
+fn route_group(path: &str) -> Option<&str> {
+    let path = path.strip_prefix("api/")?;
+    let mut separators = path.match_indices('/').map(|(index, _)| index);
+    separators.next()?;
+    Some(separators.next().map_or(path, |end| &path[..end]))
+}
+
+// Nodes are identified by their origin, since path, query and credentials are set per request.
+pub(crate) fn node_key(url: &Url) -> String {
+    url.origin().ascii_serialization()
+}
+
+// Returns the url of a node without path, query and credentials, so they're never exposed.
+pub(crate) fn base_url(url: &Url) -> Url {
+    let mut base_url = url.clone();
+    base_url.set_path("");
+    base_url.set_query(None);
+    base_url.set_username("").ok();
+    base_url.set_password(None).ok();
+    base_url
+}
+
+// Returns whether an error was caused by the node, rather than by the request.
+pub(crate) fn is_node_failure(error: &NodeApiError) -> bool {
+    match error {
+        NodeApiError::Reqwest(_) | NodeApiError::Transport(_) | NodeApiError::Json(_) => true,
+        NodeApiError::ResponseError { code, .. } => *code >= 500 || *code == 429,
+        NodeApiError::NotFound(_)
+        | NodeApiError::UnavailablePow
+        | NodeApiError::NotSupported(_)
+        | NodeApiError::Auth(_) => false,
+    }
+}
+
+/// Keeps the scores of the nodes and the selection state of the route classes.
+#[derive(Debug, Default)]
+pub(crate) struct NodeScores {
+    scores: RwLock<HashMap<String, NodeScore>>,
+    round_robin: Mutex<HashMap<RouteClass, usize>>,
+    sticky: Mutex<HashMap<RouteClass, String>>,
+}
+
+impl NodeScores {
+    pub(crate) fn scores(&self) -> Result<Vec<NodeScore>> {
+        Ok(self
+            .scores
+            .read()
+            .map_err(|_| Error::PoisonError)?
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    fn update(&self, url: &Url, f: impl FnOnce(&mut NodeScore)) {
+        if let Ok(mut scores) = self.scores.write() {
+            f(scores
+                .entry(node_key(url))
+                .or_insert_with(|| NodeScore::new(base_url(url))));
+        }
+    }
+
+    /// Records the outcome of a request sent to a node.
+    pub(crate) fn record_request<T>(
+        &self,
+        node: &Node,
+        route_class: RouteClass,
+        latency: Duration,
+        result: &std::result::Result<T, NodeApiError>,
+    ) {
+        let failed = result.as_ref().err().map_or(false, is_node_failure);
+        self.update(&node.url, |score| score.record_request(latency, failed));
+
+        if let Ok(mut sticky) = self.sticky.lock() {
+            if failed {
+                if sticky.get(&route_class) == Some(&node_key(&node.url)) {
+                    sticky.remove(&route_class);
+                }
+            } else if result.is_ok() {
+                sticky.insert(route_class, node_key(&node.url));
+            }
+        }
+    }
+
+    /// Records the sync status of a node and the latency of its info request.
+    pub(crate) fn record_sync(
+        &self,
+        node: &Node,
+        latency: Duration,
+        milestone_lag: u32,
+        pow: bool,
+        routes: Option<Vec<String>>,
+    ) {
+        self.update(&node.url, |score| {
+            score.record_request(latency, false);
+            score.milestone_lag = Some(milestone_lag);
+            score.pow = Some(pow);
+            if routes.is_some() {
+                score.routes = routes;
+            }
+        });
+    }
+
+    /// Orders the nodes for a request according to the policy, nodes that are degraded or don't support the route go
+    /// last.
+    pub(crate) fn order(
+        &self,
+        nodes: &mut Vec<Node>,
+        path: &str,
+        route_class: RouteClass,
+        policy: NodeSelectionPolicy,
+    ) {
+        let scores = match self.scores.read() {
+            Ok(scores) => scores,
+            Err(_) => return,
+        };
+        let score = |node: &Node| scores.get(&node_key(&node.url));
+
+        match policy {
+            NodeSelectionPolicy::Random => {}
+            NodeSelectionPolicy::RoundRobin => {
+                if let Ok(mut round_robin) = self.round_robin.lock() {
+                    let counter = round_robin.entry(route_class).or_default();
+                    if !nodes.is_empty() {
+                        let shift = *counter % nodes.len();
+                        nodes.rotate_left(shift);
+                    }
+                    *counter = counter.wrapping_add(1);
+                }
+            }
+            NodeSelectionPolicy::LowestLatency | NodeSelectionPolicy::Sticky => {
+                // Nodes without latency yet go last
+                nodes.sort_by_key(|node| score(node).and_then(|score| score.latency).unwrap_or(Duration::MAX));
+                if policy == NodeSelectionPolicy::Sticky {
+                    if let Some(sticky_node) = self
+                        .sticky
+                        .lock()
+                        .ok()
+                        .and_then(|sticky| sticky.get(&route_class).cloned())
+                    {
+                        if let Some(index) = nodes.iter().position(|node| node_key(&node.url) == sticky_node) {
+                            let node = nodes.remove(index);
+                            nodes.insert(0, node);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Stable sort, so the order of the policy is kept within healthy and unhealthy nodes
+        nodes.sort_by_key(|node| {
+            score(node).map_or((false, false), |score| {
+                (!score.supports(path, route_class), score.is_degraded())
+            })
+        });
+    }
+}
+
+impl ClientInner {
+    /// Returns the scores of the nodes that requests were sent to or that were synced.
+    pub async fn node_scores(&self) -> Result<Vec<NodeScore>> {
+        self.node_manager.read().await.scores.scores()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_groups() {
+        assert_eq!(route_group("api/core/v2/info"), Some("core/v2"));
+        assert_eq!(route_group("api/indexer/v1/outputs/basic"), Some("indexer/v1"));
+        assert_eq!(route_group("api/participation/v1"), Some("participation/v1"));
+        assert_eq!(route_group("api/routes"), None);
+        assert_eq!(route_group("health"), None);
+    }
+
+    fn nodes(count: u8) -> Vec<Node> {
+        (0..count)
+            .map(|index| Node::from(Url::parse(&format!("http://node{index}:14265")).unwrap()))
+            .collect()
+    }
+
+    fn urls(nodes: &[Node]) -> Vec<&str> {
+        nodes.iter().map(|node| node.url.host_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn order_random_keeps_nodes() {
+        let scores = NodeScores::default();
+        let mut ordered = nodes(3);
+        scores.order(
+            &mut ordered,
+            "api/core/v2/info",
+            RouteClass::Core,
+            NodeSelectionPolicy::Random,
+        );
+        assert_eq!(urls(&ordered), ["node0", "node1", "node2"]);
+    }
+
+    #[test]
+    fn order_lowest_latency() {
+        let scores = NodeScores::default();
+        let nodes = nodes(3);
+        scores.record_sync(&nodes[0], Duration::from_millis(300), 0, false, None);
+        scores.record_sync(&nodes[2], Duration::from_millis(100), 0, false, None);
+
+        let mut ordered = nodes;
+        scores.order(
+            &mut ordered,
+            "api/core/v2/info",
+            RouteClass::Core,
+            NodeSelectionPolicy::LowestLatency,
+        );
+        // Nodes without latency go last
+        assert_eq!(urls(&ordered), ["node2", "node0", "node1"]);
+    }
+
+    #[test]
+    fn order_round_robin_per_route_class() {
+        let scores = NodeScores::default();
+        let order = |route_class| {
+            let mut ordered = nodes(3);
+            scores.order(
+                &mut ordered,
+                "api/core/v2/info",
+                route_class,
+                NodeSelectionPolicy::RoundRobin,
+            );
+            urls(&ordered).join(",")
+        };
+
+        assert_eq!(order(RouteClass::Core), "node0,node1,node2");
+        assert_eq!(order(RouteClass::Core), "node1,node2,node0");
+        assert_eq!(order(RouteClass::Indexer), "node0,node1,node2");
+        assert_eq!(order(RouteClass::Core), "node2,node0,node1");
+        assert_eq!(order(RouteClass::Core), "node0,node1,node2");
+    }
+
+    #[test]
+    fn order_sticky() {
+        let scores = NodeScores::default();
+        let nodes = nodes(3);
+        scores.record_sync(&nodes[0], Duration::from_millis(100), 0, false, None);
+        scores.record_sync(&nodes[1], Duration::from_millis(200), 0, false, None);
+        scores.record_sync(&nodes[2], Duration::from_millis(300), 0, false, None);
+        let order = || {
+            let mut ordered = nodes.clone();
+            scores.order(
+                &mut ordered,
+                "api/core/v2/info",
+                RouteClass::Core,
+                NodeSelectionPolicy::Sticky,
+            );
+            urls(&ordered).join(",")
+        };
+
+        assert_eq!(order(), "node0,node1,node2");
+
+        // The node that answered last is tried first
+        let ok: std::result::Result<(), NodeApiError> = Ok(());
+        scores.record_request(&nodes[2], RouteClass::Core, Duration::from_millis(300), &ok);
+        assert_eq!(order(), "node2,node0,node1");
+
+        // Until it fails
+        let failed: std::result::Result<(), NodeApiError> = Err(NodeApiError::ResponseError {
+            code: 500,
+            text: String::new(),
+            url: nodes[2].url.to_string(),
+            retry_after: None,
+        });
+        scores.record_request(&nodes[2], RouteClass::Core, Duration::from_millis(300), &failed);
+        assert_eq!(order(), "node0,node1,node2");
+    }
+
+    #[test]
+    fn order_degraded_and_unsupported_last() {
+        let scores = NodeScores::default();
+        let nodes = nodes(3);
+        scores.record_sync(
+            &nodes[0],
+            Duration::from_millis(100),
+            MAX_NODE_MILESTONE_LAG + 1,
+            false,
+            None,
+        );
+        scores.record_sync(
+            &nodes[1],
+            Duration::from_millis(200),
+            0,
+            false,
+            Some(vec!["core/v2".to_owned()]),
+        );
+        scores.record_sync(&nodes[2], Duration::from_millis(300), 0, false, None);
+
+        for policy in [
+            NodeSelectionPolicy::Random,
+            NodeSelectionPolicy::LowestLatency,
+            NodeSelectionPolicy::RoundRobin,
+            NodeSelectionPolicy::Sticky,
+        ] {
+            let mut ordered = nodes.clone();
+            scores.order(
+                &mut ordered,
+                "api/indexer/v1/outputs/basic",
+                RouteClass::Indexer,
+                policy,
+            );
+            // node0 is lagging behind, node1 doesn't support the indexer
+            assert_eq!(urls(&ordered), ["node2", "node0", "node1"], "{policy:?}");
+        }
+    }
+}
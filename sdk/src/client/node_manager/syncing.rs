@@ -3,7 +3,11 @@
 
 #[cfg(not(target_family = "wasm"))]
 use {
-    crate::types::api::core::response::InfoResponse,
+    super::http_client::HttpClient,
+    crate::{
+        client::constants::DEFAULT_API_TIMEOUT,
+        types::api::core::response::{InfoResponse, RoutesResponse},
+    },
     std::{collections::HashSet, time::Duration},
     tokio::time::sleep,
 };
@@ -67,11 +71,15 @@ impl ClientInner {
         log::debug!("sync_nodes");
         let mut healthy_nodes = HashMap::new();
         let mut network_nodes: HashMap<String, Vec<(InfoResponse, Node)>> = HashMap::new();
+        let mut latencies = HashMap::new();
+        let http_client = self.node_manager.read().await.http_client.clone();
 
         for node in nodes {
             // Put the healthy node url into the network_nodes
-            match crate::client::Client::get_node_info(node.url.as_ref(), node.auth.clone()).await {
+            let start_time = instant::Instant::now();
+            match Client::get_node_info_with_client(&http_client, node.url.as_ref(), node.auth.clone()).await {
                 Ok(info) => {
+                    latencies.insert(node.clone(), start_time.elapsed());
                     if info.status.is_healthy || ignore_node_health {
                         match network_nodes.get_mut(info.protocol.network_name()) {
                             Some(network_node_entry) => {
@@ -114,11 +122,35 @@ impl ClientInner {
             }
         }
 
+        // Score the healthy nodes by their milestone lag, PoW support and routes
+        let latest_milestone_index = healthy_nodes
+            .values()
+            .map(|info| info.status.latest_milestone.index)
+            .max()
+            .unwrap_or_default();
+        let http_client = &http_client;
+        let mut node_routes = futures::future::join_all(
+            healthy_nodes
+                .keys()
+                .map(|node| async move { (node.clone(), get_node_routes(http_client, node).await) }),
+        )
+        .await
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+        let node_manager = self.node_manager.read().await;
+        for (node, info) in &healthy_nodes {
+            node_manager.scores.record_sync(
+                node,
+                latencies.get(node).copied().unwrap_or_default(),
+                latest_milestone_index.saturating_sub(info.status.latest_milestone.index),
+                info.features.iter().any(|feature| feature == "pow"),
+                node_routes.remove(node).flatten(),
+            );
+        }
+
         // Update the sync list.
-        *self
-            .node_manager
-            .read()
-            .await
+        *node_manager
             .healthy_nodes
             .write()
             .map_err(|_| crate::client::Error::PoisonError)? = healthy_nodes;
@@ -127,6 +159,26 @@ impl ClientInner {
     }
 }
 
+// Returns the route groups supported by a node, or `None` if they couldn't be requested.
+#[cfg(not(target_family = "wasm"))]
+async fn get_node_routes(http_client: &HttpClient, node: &Node) -> Option<Vec<String>> {
+    let mut node = node.clone();
+    node.url.set_path("api/routes");
+    node.url.set_query(None);
+
+    match http_client.get(node, DEFAULT_API_TIMEOUT).await {
+        Ok(response) => response
+            .into_json::<RoutesResponse>()
+            .await
+            .map(|routes| routes.routes)
+            .ok(),
+        Err(err) => {
+            log::debug!("Couldn't get node routes: {err}");
+            None
+        }
+    }
+}
+
 impl Client {
     #[cfg(not(target_family = "wasm"))]
     pub async fn update_node_manager(&self, node_manager: NodeManager) -> Result<()> {
@@ -0,0 +1,154 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The transport sending the HTTP requests to the nodes.
+
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use url::Url;
+
+use crate::client::node_api::error::Result;
+
+/// The method of an HTTP request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HttpMethod {
+    /// GET
+    Get,
+    /// POST
+    Post,
+}
+
+/// An HTTP request to a node, with the headers already set by the client.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpRequest {
+    /// The method of the request.
+    pub method: HttpMethod,
+    /// The url of the request, including the credentials of the node for basic auth.
+    pub url: Url,
+    /// The headers of the request.
+    pub headers: Vec<(String, String)>,
+    /// The body of the request.
+    pub body: Option<Vec<u8>>,
+    /// The time after which the request is aborted. Not supported by the default transport in WASM.
+    pub timeout: Duration,
+}
+
+impl HttpRequest {
+    pub(crate) fn new(method: HttpMethod, url: Url, timeout: Duration) -> Self {
+        Self {
+            method,
+            url,
+            headers: Vec::new(),
+            body: None,
+            timeout,
+        }
+    }
+
+    pub(crate) fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub(crate) fn with_body(mut self, body: Vec<u8>) -> Self {
+        self.body = Some(body);
+        self
+    }
+}
+
+/// The response of a node to an HTTP request.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The headers of the response.
+    pub headers: Vec<(String, String)>,
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Returns the value of a header, the name is case-insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Sends the HTTP requests of the client to the nodes. The default transport uses reqwest, a custom one can be set
+/// with [`ClientBuilder::with_http_transport()`](crate::client::ClientBuilder::with_http_transport) to use proxies,
+/// custom root certificates, certificate pinning, request signing, or to record or mock the responses in tests.
+///
+/// A response with an error status code must be returned as response, not as error. Errors of a custom transport
+/// should be returned as [`Error::Transport`](crate::client::node_api::error::Error::Transport).
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+pub trait HttpTransport: Debug + Send + Sync {
+    /// Sends a request and returns the response of the node.
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// The default transport, sending the requests with a [`reqwest::Client`].
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport with a default reqwest client.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a transport with a configured reqwest client, for example with a proxy or custom root certificates.
+    pub fn from_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl HttpTransport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let method = match request.method {
+            HttpMethod::Get => reqwest::Method::GET,
+            HttpMethod::Post => reqwest::Method::POST,
+        };
+        let mut request_builder = self.client.request(method, request.url);
+        for (name, value) in &request.headers {
+            request_builder = request_builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            request_builder = request_builder.body(body);
+        }
+        #[cfg(not(target_family = "wasm"))]
+        {
+            request_builder = request_builder.timeout(request.timeout);
+        }
+
+        let response = request_builder.send().await?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = response.bytes().await?.to_vec();
+
+        Ok(HttpResponse { status, headers, body })
+    }
+}
+
+/// A custom transport of a client, shared by the builder and the node manager.
+#[derive(Clone, Debug)]
+pub(crate) struct Transport(pub(crate) Arc<dyn HttpTransport>);
+
+impl PartialEq for Transport {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Transport {}
@@ -121,6 +121,10 @@ pub enum Error {
     UnsupportedOutputKind(u8),
     DuplicateOutputChain(ChainId),
     InvalidField(&'static str),
+    #[cfg(feature = "irc_27")]
+    InvalidIrc27Metadata(String),
+    #[cfg(feature = "irc_30")]
+    InvalidIrc30Metadata(String),
 }
 
 #[cfg(feature = "std")]
@@ -329,6 +333,10 @@ impl fmt::Display for Error {
             Self::UnsupportedOutputKind(k) => write!(f, "unsupported output kind: {k}"),
             Self::DuplicateOutputChain(chain_id) => write!(f, "duplicate output chain {chain_id}"),
             Self::InvalidField(field) => write!(f, "invalid field: {field}"),
+            #[cfg(feature = "irc_27")]
+            Self::InvalidIrc27Metadata(reason) => write!(f, "invalid IRC27 metadata: {reason}"),
+            #[cfg(feature = "irc_30")]
+            Self::InvalidIrc30Metadata(reason) => write!(f, "invalid IRC30 metadata: {reason}"),
         }
     }
 }
@@ -0,0 +1,332 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::types::block::{address::Bech32Address, output::feature::MetadataFeature, Error};
+
+/// The IRC27 NFT metadata standard.
+/// <https://github.com/iotaledger/tips/blob/main/tips/TIP-0027/tip-0027.md>
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Irc27Metadata {
+    standard: String,
+    version: String,
+    /// The media type (MIME) of the asset.
+    /// ## Examples
+    /// - Image files: `image/jpeg`, `image/png`, `image/gif`, etc.
+    /// - Video files: `video/x-msvideo` (avi), `video/mp4`, `video/mpeg`, etc.
+    /// - Audio files: `audio/mpeg`, `audio/wav`, etc.
+    /// - 3D Assets: `model/obj`, `model/u3d`, etc.
+    /// - Documents: `application/pdf`, `text/plain`, etc.
+    #[serde(rename = "type")]
+    media_type: String,
+    /// URL pointing to the NFT file location.
+    uri: Url,
+    /// The human-readable name of the NFT.
+    name: String,
+    /// The human-readable collection name of the NFT.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    collection_name: Option<String>,
+    /// Royalty payment addresses mapped to the payout percentage.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    royalties: HashMap<Bech32Address, f64>,
+    /// The human-readable name of the NFT creator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    issuer_name: Option<String>,
+    /// The human-readable description of the NFT.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Additional attributes which follow [OpenSea Metadata standards](https://docs.opensea.io/docs/metadata-standards).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<Irc27Attribute>,
+}
+
+impl Irc27Metadata {
+    /// The value of the `standard` field.
+    pub const STANDARD: &'static str = "IRC27";
+    /// The value of the `version` field.
+    pub const VERSION: &'static str = "v1.0";
+
+    /// Creates a new [`Irc27Metadata`].
+    pub fn new(media_type: impl Into<String>, uri: Url, name: impl Into<String>) -> Self {
+        Self {
+            standard: Self::STANDARD.to_owned(),
+            version: Self::VERSION.to_owned(),
+            media_type: media_type.into(),
+            uri,
+            name: name.into(),
+            collection_name: None,
+            royalties: HashMap::new(),
+            issuer_name: None,
+            description: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Sets the collection name.
+    pub fn with_collection_name(mut self, collection_name: impl Into<Option<String>>) -> Self {
+        self.collection_name = collection_name.into();
+        self
+    }
+
+    /// Adds a royalty payment address with its payout percentage.
+    pub fn add_royalty(mut self, address: Bech32Address, percentage: f64) -> Self {
+        self.royalties.insert(address, percentage);
+        self
+    }
+
+    /// Sets the royalty payment addresses with their payout percentages.
+    pub fn with_royalties(mut self, royalties: HashMap<Bech32Address, f64>) -> Self {
+        self.royalties = royalties;
+        self
+    }
+
+    /// Sets the issuer name.
+    pub fn with_issuer_name(mut self, issuer_name: impl Into<Option<String>>) -> Self {
+        self.issuer_name = issuer_name.into();
+        self
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<Option<String>>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Adds an attribute.
+    pub fn add_attribute(mut self, attribute: Irc27Attribute) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Sets the attributes.
+    pub fn with_attributes(mut self, attributes: impl IntoIterator<Item = Irc27Attribute>) -> Self {
+        self.attributes = attributes.into_iter().collect();
+        self
+    }
+
+    /// Returns the media type.
+    pub fn media_type(&self) -> &str {
+        &self.media_type
+    }
+
+    /// Returns the URI.
+    pub fn uri(&self) -> &Url {
+        &self.uri
+    }
+
+    /// Returns the name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the collection name.
+    pub fn collection_name(&self) -> Option<&str> {
+        self.collection_name.as_deref()
+    }
+
+    /// Returns the royalties.
+    pub fn royalties(&self) -> &HashMap<Bech32Address, f64> {
+        &self.royalties
+    }
+
+    /// Returns the issuer name.
+    pub fn issuer_name(&self) -> Option<&str> {
+        self.issuer_name.as_deref()
+    }
+
+    /// Returns the description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the attributes.
+    pub fn attributes(&self) -> &[Irc27Attribute] {
+        &self.attributes
+    }
+
+    /// Validates the required fields, the media type, the royalties and the attributes.
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |reason: &str| Err(Error::InvalidIrc27Metadata(reason.to_string()));
+
+        if self.standard != Self::STANDARD {
+            return invalid("invalid standard");
+        }
+        if self.version != Self::VERSION {
+            return invalid("unsupported version");
+        }
+        if !is_media_type(&self.media_type) {
+            return invalid("invalid media type");
+        }
+        if self.name.is_empty() {
+            return invalid("empty name");
+        }
+        if self
+            .royalties
+            .values()
+            .any(|percentage| !(0.0..=1.0).contains(percentage))
+            || self.royalties.values().sum::<f64>() > 1.0
+        {
+            return invalid("royalties must be between 0 and 1 and their sum must not exceed 1");
+        }
+
+        let mut trait_types = BTreeSet::new();
+        for attribute in &self.attributes {
+            if attribute.trait_type.is_empty() {
+                return invalid("empty attribute trait type");
+            }
+            if !trait_types.insert(attribute.trait_type.as_str()) {
+                return invalid("duplicate attribute trait type");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates the metadata and serializes it to JSON bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.validate()?;
+
+        serde_json::to_vec(self).map_err(|e| Error::InvalidIrc27Metadata(e.to_string()))
+    }
+
+    /// Deserializes and validates metadata from JSON bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let metadata = serde_json::from_slice::<Self>(bytes).map_err(|e| Error::InvalidIrc27Metadata(e.to_string()))?;
+        metadata.validate()?;
+
+        Ok(metadata)
+    }
+}
+
+impl TryFrom<&Irc27Metadata> for MetadataFeature {
+    type Error = Error;
+
+    fn try_from(metadata: &Irc27Metadata) -> Result<Self, Error> {
+        Self::new(metadata.to_bytes()?)
+    }
+}
+
+impl TryFrom<&MetadataFeature> for Irc27Metadata {
+    type Error = Error;
+
+    fn try_from(metadata: &MetadataFeature) -> Result<Self, Error> {
+        Self::from_bytes(metadata.data())
+    }
+}
+
+/// An attribute of [`Irc27Metadata`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Irc27Attribute {
+    trait_type: String,
+    value: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    display_type: Option<String>,
+}
+
+impl Irc27Attribute {
+    /// Creates a new [`Irc27Attribute`].
+    pub fn new(trait_type: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        Self {
+            trait_type: trait_type.into(),
+            value: value.into(),
+            display_type: None,
+        }
+    }
+
+    /// Sets how the attribute should be displayed.
+    pub fn with_display_type(mut self, display_type: impl Into<Option<String>>) -> Self {
+        self.display_type = display_type.into();
+        self
+    }
+
+    /// Returns the trait type.
+    pub fn trait_type(&self) -> &str {
+        &self.trait_type
+    }
+
+    /// Returns the value.
+    pub fn value(&self) -> &serde_json::Value {
+        &self.value
+    }
+
+    /// Returns the display type.
+    pub fn display_type(&self) -> Option<&str> {
+        self.display_type.as_deref()
+    }
+}
+
+// Checks if a string has the form `type/subtype`
+fn is_media_type(media_type: &str) -> bool {
+    match media_type.split_once('/') {
+        Some((kind, subtype)) => {
+            !kind.is_empty()
+                && !subtype.is_empty()
+                && !subtype.contains('/')
+                && !media_type.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_roundtrip() {
+        let metadata = Irc27Metadata::new(
+            "image/png",
+            "https://mywebsite.com/my-nft-files-1.png".parse().unwrap(),
+            "My NFT #0001",
+        )
+        .with_collection_name("My Collection of Art".to_owned())
+        .add_royalty(
+            Bech32Address::try_from_str("rms1qpllaj0pyveqfkwxmnngz2c488hfdtmfrj3wfkgxtk4gtyrax0jaxzt70zy").unwrap(),
+            0.025,
+        )
+        .with_issuer_name("My Artist Name".to_owned())
+        .with_description("A little information about my NFT collection".to_owned())
+        .add_attribute(Irc27Attribute::new("Background", "Purple"));
+
+        let feature = MetadataFeature::try_from(&metadata).unwrap();
+        assert_eq!(Irc27Metadata::try_from(&feature).unwrap(), metadata);
+    }
+
+    #[test]
+    fn invalid() {
+        let uri: Url = "https://mywebsite.com/my-nft-files-1.png".parse().unwrap();
+
+        assert!(Irc27Metadata::new("image", uri.clone(), "My NFT").validate().is_err());
+        assert!(Irc27Metadata::new("image/png", uri.clone(), "").validate().is_err());
+        assert!(
+            Irc27Metadata::new("image/png", uri, "My NFT")
+                .add_attribute(Irc27Attribute::new("Background", "Purple"))
+                .add_attribute(Irc27Attribute::new("Background", "Blue"))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            Irc27Metadata::from_bytes(
+                br#"{"standard":"IRC30","version":"v1.0","type":"image/png","uri":"https://mywebsite.com/my-nft-files-1.png","name":"My NFT"}"#
+            )
+            .is_err()
+        );
+        assert!(
+            Irc27Metadata::from_bytes(
+                br#"{"standard":"IRC27","version":"v1.0","type":"image/png","uri":"https://mywebsite.com/my-nft-files-1.png","name":"My NFT"}"#
+            )
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::types::block::{output::feature::MetadataFeature, Error};
+
+/// The IRC30 native token metadata standard.
+/// <https://github.com/iotaledger/tips/blob/main/tips/TIP-0030/tip-0030.md>
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Irc30Metadata {
+    standard: String,
+    /// The human-readable name of the native token.
+    name: String,
+    /// The human-readable description of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// The symbol/ticker of the token.
+    symbol: String,
+    /// Number of decimals the token uses (divide the token amount by `10^decimals` to get its user representation).
+    decimals: u32,
+    /// URL pointing to more resources about the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<Url>,
+    /// URL pointing to an image resource of the token logo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    logo_url: Option<Url>,
+    /// The svg logo of the token encoded as a byte string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    logo: Option<String>,
+}
+
+impl Irc30Metadata {
+    /// The value of the `standard` field.
+    pub const STANDARD: &'static str = "IRC30";
+
+    /// Creates a new [`Irc30Metadata`].
+    pub fn new(name: impl Into<String>, symbol: impl Into<String>, decimals: u32) -> Self {
+        Self {
+            standard: Self::STANDARD.to_owned(),
+            name: name.into(),
+            description: None,
+            symbol: symbol.into(),
+            decimals,
+            url: None,
+            logo_url: None,
+            logo: None,
+        }
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<Option<String>>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the URL.
+    pub fn with_url(mut self, url: impl Into<Option<Url>>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Sets the logo URL.
+    pub fn with_logo_url(mut self, logo_url: impl Into<Option<Url>>) -> Self {
+        self.logo_url = logo_url.into();
+        self
+    }
+
+    /// Sets the svg logo.
+    pub fn with_logo(mut self, logo: impl Into<Option<String>>) -> Self {
+        self.logo = logo.into();
+        self
+    }
+
+    /// Returns the name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the symbol.
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Returns the decimals.
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    /// Returns the URL.
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+
+    /// Returns the logo URL.
+    pub fn logo_url(&self) -> Option<&Url> {
+        self.logo_url.as_ref()
+    }
+
+    /// Returns the svg logo.
+    pub fn logo(&self) -> Option<&str> {
+        self.logo.as_deref()
+    }
+
+    /// Formats an amount of the token with its decimals and symbol, e.g. `1.5 MNT` for an amount of `1500000` with `6`
+    /// decimals.
+    pub fn format_amount(&self, amount: U256) -> String {
+        // The largest U256 has 78 digits, more decimals would only add leading zeros.
+        const MAX_DIGITS: usize = 78;
+
+        let amount = amount.to_string();
+        let decimals = self.decimals as usize;
+
+        if decimals == 0 {
+            return format!("{amount} {}", self.symbol);
+        }
+        if decimals > MAX_DIGITS {
+            return format!("{amount}e-{decimals} {}", self.symbol);
+        }
+
+        let padded = format!("{amount:0>width$}", width = decimals + 1);
+        let (integer, fraction) = padded.split_at(padded.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+
+        if fraction.is_empty() {
+            format!("{integer} {}", self.symbol)
+        } else {
+            format!("{integer}.{fraction} {}", self.symbol)
+        }
+    }
+
+    /// Validates the required fields.
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |reason: &str| Err(Error::InvalidIrc30Metadata(reason.to_string()));
+
+        if self.standard != Self::STANDARD {
+            return invalid("invalid standard");
+        }
+        if self.name.is_empty() {
+            return invalid("empty name");
+        }
+        if self.symbol.is_empty() {
+            return invalid("empty symbol");
+        }
+
+        Ok(())
+    }
+
+    /// Validates the metadata and serializes it to JSON bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.validate()?;
+
+        serde_json::to_vec(self).map_err(|e| Error::InvalidIrc30Metadata(e.to_string()))
+    }
+
+    /// Deserializes and validates metadata from JSON bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let metadata = serde_json::from_slice::<Self>(bytes).map_err(|e| Error::InvalidIrc30Metadata(e.to_string()))?;
+        metadata.validate()?;
+
+        Ok(metadata)
+    }
+}
+
+impl TryFrom<&Irc30Metadata> for MetadataFeature {
+    type Error = Error;
+
+    fn try_from(metadata: &Irc30Metadata) -> Result<Self, Error> {
+        Self::new(metadata.to_bytes()?)
+    }
+}
+
+impl TryFrom<&MetadataFeature> for Irc30Metadata {
+    type Error = Error;
+
+    fn try_from(metadata: &MetadataFeature) -> Result<Self, Error> {
+        Self::from_bytes(metadata.data())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_roundtrip() {
+        let metadata = Irc30Metadata::new("My Native Token", "MNT", 10)
+            .with_description("A native token to test the iota-sdk.".to_owned())
+            .with_url("https://www.my-website.com".parse::<Url>().unwrap())
+            .with_logo_url("https://www.my-website.com/nativeToken.png".parse::<Url>().unwrap());
+
+        let feature = MetadataFeature::try_from(&metadata).unwrap();
+        assert_eq!(Irc30Metadata::try_from(&feature).unwrap(), metadata);
+    }
+
+    #[test]
+    fn format_amount() {
+        let metadata = Irc30Metadata::new("My Native Token", "MNT", 6);
+
+        assert_eq!(metadata.format_amount(U256::from(1_500_000u32)), "1.5 MNT");
+        assert_eq!(metadata.format_amount(U256::from(2_000_000u32)), "2 MNT");
+        assert_eq!(metadata.format_amount(U256::from(42u32)), "0.000042 MNT");
+        assert_eq!(metadata.format_amount(U256::zero()), "0 MNT");
+        assert_eq!(
+            Irc30Metadata::new("My Native Token", "MNT", 0).format_amount(U256::from(42u32)),
+            "42 MNT"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Irc30Metadata::new("", "MNT", 6).validate().is_err());
+        assert!(Irc30Metadata::new("My Native Token", "", 6).validate().is_err());
+        assert!(Irc30Metadata::from_bytes(br#"{"standard":"IRC30","name":"NativeToken","decimals":6}"#).is_err());
+        assert!(
+            Irc30Metadata::from_bytes(
+                br#"{"standard":"IRC30","name":"NativeToken","description":"A native token","symbol":"NT","decimals":6,"logoUrl":"https://my.website/nativeToken.png"}"#
+            )
+            .is_ok()
+        );
+    }
+}
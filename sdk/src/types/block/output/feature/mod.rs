@@ -1,6 +1,10 @@
 // Copyright 2021-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "irc_27")]
+mod irc_27;
+#[cfg(feature = "irc_30")]
+mod irc_30;
 mod issuer;
 mod metadata;
 mod sender;
@@ -13,6 +17,10 @@ use derive_more::{Deref, From};
 use iterator_sorted::is_unique_sorted;
 use packable::{bounded::BoundedU8, prefix::BoxedSlicePrefix, Packable};
 
+#[cfg(feature = "irc_27")]
+pub use self::irc_27::{Irc27Attribute, Irc27Metadata};
+#[cfg(feature = "irc_30")]
+pub use self::irc_30::Irc30Metadata;
 pub use self::{issuer::IssuerFeature, metadata::MetadataFeature, sender::SenderFeature, tag::TagFeature};
 pub(crate) use self::{metadata::MetadataFeatureLength, tag::TagFeatureLength};
 use crate::types::block::{create_bitflags, Error};
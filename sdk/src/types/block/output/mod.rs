@@ -85,7 +85,7 @@ pub(crate) enum OutputBuilderAmount {
 }
 
 /// Contains the generic [`Output`] with associated [`OutputMetadata`].
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct OutputWithMetadata {
     pub(crate) output: Output,
     pub(crate) metadata: OutputMetadata,
@@ -129,6 +129,27 @@ pub mod string_prefix {
     }
 }
 
+/// Serializes a [`Duration`](core::time::Duration) as its number of milliseconds.
+pub mod duration_millis {
+    use core::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
 #[cfg(feature = "client")]
 pub mod bip44 {
     use crypto::keys::bip44::Bip44;
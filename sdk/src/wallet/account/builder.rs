@@ -150,6 +150,7 @@ where
             pending_transactions: HashSet::new(),
             incoming_transactions: HashMap::new(),
             inaccessible_incoming_transactions: HashSet::new(),
+            transactions_to_reissue: HashSet::new(),
             native_token_foundries: HashMap::new(),
         };
 
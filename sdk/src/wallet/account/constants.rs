@@ -1,6 +1,8 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
 /// Amount at which outputs on a single addresses will get consolidated by default if consolidatioin is enabled
 pub(crate) const DEFAULT_OUTPUT_CONSOLIDATION_THRESHOLD: usize = 100;
 /// Amount at which outputs on a single addresses will get consolidated by default with a ledger secret_manager if
@@ -8,6 +10,27 @@ pub(crate) const DEFAULT_OUTPUT_CONSOLIDATION_THRESHOLD: usize = 100;
 #[cfg(feature = "ledger_nano")]
 pub(crate) const DEFAULT_LEDGER_OUTPUT_CONSOLIDATION_THRESHOLD: usize = 15;
 
+/// Minimum time between two consolidations of a [ConsolidationPolicy](crate::wallet::account::ConsolidationPolicy) by
+/// default, one hour
+pub(crate) const DEFAULT_CONSOLIDATION_MIN_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Number of consecutive addresses without outputs after which sweeping stops scanning by default
+pub(crate) const DEFAULT_SWEEP_ADDRESS_GAP: u32 = 20;
+
+/// Maximum amount of recipients of an airdrop served in a single transaction, leaving room for a remainder output
+pub(crate) const MAX_AIRDROP_RECIPIENTS_PER_TRANSACTION: usize =
+    crate::types::block::output::OUTPUT_COUNT_MAX as usize - 1;
+
+/// Maximum amount of NFTs of a collection minted in a single transaction, leaving room for the transitioned collection
+/// NFT and a remainder output
+pub(crate) const MAX_NFT_COLLECTION_ITEMS_PER_TRANSACTION: usize =
+    crate::types::block::output::OUTPUT_COUNT_MAX as usize - 2;
+
+/// Maximum packed length of the outputs of a transaction with many outputs, leaving room in the transaction payload
+/// for its other fields, the inputs with their unlocks and a remainder output
+pub(crate) const MAX_OUTPUTS_LENGTH_PER_TRANSACTION: usize =
+    crate::client::api::transaction::MAX_TX_LENGTH_FOR_BLOCK_WITH_8_PARENTS - 4096;
+
 /// Amount of API request that can be sent in parallel during syncing
 pub(crate) const PARALLEL_REQUESTS_AMOUNT: usize = 500;
 
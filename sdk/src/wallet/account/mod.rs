@@ -31,13 +31,16 @@ use self::types::{
 pub use self::{
     operations::{
         output_claiming::OutputsToClaim,
-        output_consolidation::ConsolidationParams,
+        output_consolidation::{ConsolidationParams, ConsolidationPolicy},
+        sweep::SweepParams,
         syncing::{
             options::{AccountSyncOptions, AliasSyncOptions, NftSyncOptions},
             SyncOptions,
         },
         transaction::{
             high_level::{
+                airdrop::{AirdropParams, AirdropRecipient, AirdropRecipientReport, AirdropReport},
+                burning_melting::retire_native_token::RetireNativeTokenTransaction,
                 create_alias::CreateAliasParams,
                 minting::{
                     create_native_token::{
@@ -45,7 +48,9 @@ pub use self::{
                         PreparedCreateNativeTokenTransactionDto,
                     },
                     mint_nfts::MintNftParams,
+                    nft_collection::{MintCollectionNftTransaction, NftCollectionItem},
                 },
+                update_alias::AliasGovernanceParams,
             },
             prepare_output::{Assets, Features, OutputParams, ReturnStrategy, StorageDeposit, Unlocks},
             RemainderValueStrategy, TransactionOptions, TransactionOptionsDto,
@@ -129,10 +134,12 @@ pub struct AccountDetails {
     /// Transaction payloads for received outputs with inputs when not pruned before syncing, can be used to determine
     /// the sender address(es)
     incoming_transactions: HashMap<TransactionId, Transaction>,
-    /// Some incoming transactions can be pruned by the node before we requested them, then this node can never return
-    /// it. To avoid useless requests, these transaction ids are stored here and cleared when new client options are
-    /// set, because another node might still have them.
+    /// Some incoming transactions or their inputs can be pruned by the node before we requested them, then this node
+    /// can never return them. To avoid useless requests, these transaction ids are stored here and cleared when new
+    /// client options are set, because another node or permanode might still have them.
     inaccessible_incoming_transactions: HashSet<TransactionId>,
+    /// Conflicting transactions of which the outputs still need to be sent again, because reissuing them failed
+    pub(crate) transactions_to_reissue: HashSet<TransactionId>,
     /// Foundries for native tokens in outputs
     native_token_foundries: HashMap<FoundryId, FoundryOutput>,
 }
@@ -167,6 +174,9 @@ pub struct AccountInner {
     // again, because sending transactions can change that
     pub(crate) last_synced: Mutex<u128>,
     pub(crate) default_sync_options: Mutex<SyncOptions>,
+    pub(crate) consolidation_policy: Mutex<Option<ConsolidationPolicy>>,
+    // timestamp in ms of the last consolidation done by the consolidation policy, persisted with storage
+    pub(crate) last_consolidation: Mutex<u128>,
 }
 
 // impl Deref so we can use `account.details()` instead of `account.details.read()`
@@ -194,6 +204,25 @@ where
             .unwrap_or_default();
         #[cfg(not(feature = "storage"))]
         let default_sync_options = Default::default();
+        #[cfg(feature = "storage")]
+        let consolidation_policy = wallet
+            .storage_manager
+            .read()
+            .await
+            .get_consolidation_policy(*details.index())
+            .await?;
+        #[cfg(not(feature = "storage"))]
+        let consolidation_policy = None;
+        #[cfg(feature = "storage")]
+        let last_consolidation = wallet
+            .storage_manager
+            .read()
+            .await
+            .get_last_consolidation(*details.index())
+            .await?
+            .unwrap_or_default();
+        #[cfg(not(feature = "storage"))]
+        let last_consolidation = 0;
 
         Ok(Self {
             wallet,
@@ -201,6 +230,8 @@ where
                 details: RwLock::new(details),
                 last_synced: Default::default(),
                 default_sync_options: Mutex::new(default_sync_options),
+                consolidation_policy: Mutex::new(consolidation_policy),
+                last_consolidation: Mutex::new(last_consolidation),
             }),
         })
     }
@@ -252,6 +283,40 @@ where
         Ok(())
     }
 
+    /// Get the transaction with inputs of an incoming transaction stored in the account
+    /// Inputs the node pruned already are requested from the permanodes, the list might still not be complete
+    pub async fn get_incoming_transaction(&self, transaction_id: &TransactionId) -> Option<Transaction> {
+        self.fill_missing_incoming_transaction_inputs(Some(&[*transaction_id].into()))
+            .await;
+        self.details()
+            .await
+            .incoming_transactions()
+            .get(transaction_id)
+            .cloned()
+    }
+
+    /// Returns all incoming transactions of the account
+    /// Inputs the node pruned already are requested from the permanodes, the lists might still not be complete
+    pub async fn incoming_transactions(&self) -> Vec<Transaction> {
+        self.fill_missing_incoming_transaction_inputs(None).await;
+        self.details().await.incoming_transactions.values().cloned().collect()
+    }
+
+    // Requests the inputs of incoming transactions that are missing because the node pruned them, from the permanodes.
+    // Failures are only logged, the stored transactions are returned anyway.
+    async fn fill_missing_incoming_transaction_inputs(&self, transaction_ids: Option<&HashSet<TransactionId>>) {
+        match self.request_missing_incoming_transaction_inputs(transaction_ids).await {
+            #[cfg(feature = "storage")]
+            Ok(true) => {
+                if let Err(e) = self.save(None).await {
+                    log::warn!("couldn't save the inputs of the incoming transactions: {e}");
+                }
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("couldn't request the missing inputs of the incoming transactions: {e}"),
+        }
+    }
+
     #[cfg(feature = "events")]
     pub(crate) async fn emit(&self, account_index: u32, wallet_event: super::events::types::WalletEvent) {
         self.wallet.emit(account_index, wallet_event).await
@@ -281,16 +346,6 @@ impl AccountInner {
         self.details().await.transactions().get(transaction_id).cloned()
     }
 
-    /// Get the transaction with inputs of an incoming transaction stored in the account
-    /// List might not be complete, if the node pruned the data already
-    pub async fn get_incoming_transaction(&self, transaction_id: &TransactionId) -> Option<Transaction> {
-        self.details()
-            .await
-            .incoming_transactions()
-            .get(transaction_id)
-            .cloned()
-    }
-
     /// Returns all addresses of the account
     pub async fn addresses(&self) -> Result<Vec<AccountAddress>> {
         let account_details = self.details().await;
@@ -420,11 +475,6 @@ impl AccountInner {
         .map(|res| res.get(0).cloned())
     }
 
-    /// Returns all incoming transactions of the account
-    pub async fn incoming_transactions(&self) -> Vec<Transaction> {
-        self.details().await.incoming_transactions.values().cloned().collect()
-    }
-
     /// Returns all transactions of the account
     pub async fn transactions(&self) -> Vec<Transaction> {
         self.details().await.transactions.values().cloned().collect()
@@ -464,6 +514,7 @@ pub(crate) fn build_transaction_from_payload_and_inputs(
         incoming: true,
         note: None,
         inputs,
+        replaced_transaction_id: None,
     })
 }
 
@@ -495,6 +546,9 @@ pub struct AccountDetailsDto {
     pub pending_transactions: HashSet<TransactionId>,
     /// Incoming transactions
     pub incoming_transactions: HashMap<TransactionId, TransactionDto>,
+    /// Conflicting transactions of which the outputs still need to be sent again
+    #[serde(default)]
+    pub transactions_to_reissue: HashSet<TransactionId>,
     /// Foundries for native tokens in outputs
     #[serde(default)]
     pub native_token_foundries: HashMap<FoundryId, FoundryOutputDto>,
@@ -538,6 +592,7 @@ impl TryFromDto for AccountDetails {
                 .map(|(id, o)| Ok((id, Transaction::try_from_dto_with_params(o, &params)?)))
                 .collect::<crate::wallet::Result<_>>()?,
             inaccessible_incoming_transactions: Default::default(),
+            transactions_to_reissue: dto.transactions_to_reissue,
             native_token_foundries: dto
                 .native_token_foundries
                 .into_iter()
@@ -578,6 +633,7 @@ impl From<&AccountDetails> for AccountDetailsDto {
                 .iter()
                 .map(|(id, transaction)| (*id, TransactionDto::from(transaction)))
                 .collect(),
+            transactions_to_reissue: value.transactions_to_reissue.clone(),
             native_token_foundries: value
                 .native_token_foundries()
                 .iter()
@@ -660,6 +716,7 @@ fn serialize() {
         incoming: false,
         note: None,
         inputs: Vec::new(),
+        replaced_transaction_id: None,
     };
 
     let mut incoming_transactions = HashMap::new();
@@ -682,6 +739,7 @@ fn serialize() {
         pending_transactions: HashSet::new(),
         incoming_transactions,
         inaccessible_incoming_transactions: HashSet::new(),
+        transactions_to_reissue: HashSet::new(),
         native_token_foundries: HashMap::new(),
     };
 
@@ -725,6 +783,7 @@ impl AccountDetails {
             pending_transactions: HashSet::new(),
             incoming_transactions: HashMap::new(),
             inaccessible_incoming_transactions: HashSet::new(),
+            transactions_to_reissue: HashSet::new(),
             native_token_foundries: HashMap::new(),
         }
     }
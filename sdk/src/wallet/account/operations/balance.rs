@@ -1,21 +1,34 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 use primitive_types::U256;
 
 use crate::{
     client::secret::SecretManage,
-    types::block::{
-        address::Bech32Address,
-        output::{unlock_condition::UnlockCondition, FoundryId, NativeTokensBuilder, Output, Rent},
-        ConvertTo,
+    types::{
+        block::{
+            address::{Address, Bech32Address},
+            output::{
+                unlock_condition::UnlockCondition, FoundryId, NativeTokensBuilder, NftId, Output, OutputId,
+                OutputMetadata, Rent,
+            },
+            payload::transaction::TransactionEssence,
+            ConvertTo,
+        },
+        TryFromDto,
     },
     wallet::{
         account::{
             operations::helpers::time::can_output_be_unlocked_forever_from_now_on,
-            types::{AddressWithUnspentOutputs, Balance, NativeTokensBalance},
+            types::{
+                AddressWithUnspentOutputs, Balance, BalanceChange, BalancePoint, BalanceTimelineEntry, HistoricBalance,
+                NativeTokensBalance,
+            },
             Account, AccountDetails, OutputsToClaim,
         },
+        core::TokenRegistry,
         Error, Result,
     },
 };
@@ -56,6 +69,325 @@ where
             .await
     }
 
+    /// Get the balance of the account at a milestone index or timestamp in the past. The balance is reconstructed from
+    /// the outputs of the account and the inputs of its transactions, the spent metadata of outputs for which it isn't
+    /// known is requested from the node. If the node already pruned the ledger state of that point, the spent status
+    /// of some outputs can't be determined or inputs of the transactions of the account are unknown, the balance is
+    /// marked as potentially incomplete.
+    ///
+    /// Outputs that were created and spent between two syncs are only known if they are inputs of transactions sent
+    /// by the account. Others, for example received outputs that returned to the sender after their expiration, are
+    /// missing from the balance without being reported.
+    pub async fn historic_balance(&self, point: BalancePoint) -> Result<HistoricBalance> {
+        log::debug!("[BALANCE] historic_balance {point:?}");
+
+        let network_id = self.client().get_network_id().await?;
+        let rent_structure = self.client().get_rent_structure().await?;
+        let pruning_index = self.client().get_info().await?.node_info.status.pruning_index;
+
+        let (mut outputs, account_addresses, native_token_foundries, missing_inputs) = {
+            let account_details = self.details().await;
+            let mut outputs = HashMap::<OutputId, (Output, OutputMetadata)>::new();
+            let mut missing_inputs = false;
+
+            for output_data in account_details.outputs.values() {
+                // Check if output is from the network we're currently connected to
+                if output_data.network_id == network_id {
+                    outputs.insert(
+                        output_data.output_id,
+                        (output_data.output.clone(), output_data.metadata),
+                    );
+                }
+            }
+
+            // Outputs that were created and spent between two syncs are only known as inputs of our transactions
+            for transaction in account_details.transactions.values() {
+                if transaction.incoming || transaction.network_id != network_id {
+                    continue;
+                }
+                let TransactionEssence::Regular(essence) = transaction.payload.essence();
+                // Inputs that were already pruned when the transaction was stored are unknown
+                if transaction.inputs.len() < essence.inputs().len() {
+                    missing_inputs = true;
+                }
+                for input in &transaction.inputs {
+                    if !outputs.contains_key(input.metadata.output_id()) {
+                        outputs.insert(
+                            *input.metadata.output_id(),
+                            (Output::try_from_dto(input.output.clone())?, input.metadata),
+                        );
+                    }
+                }
+            }
+
+            let account_addresses = account_details
+                .public_addresses()
+                .iter()
+                .chain(account_details.internal_addresses())
+                .map(|address| *address.address.inner())
+                .collect::<HashSet<Address>>();
+
+            (
+                outputs,
+                account_addresses,
+                account_details.native_token_foundries.clone(),
+                missing_inputs,
+            )
+        };
+
+        // Request the metadata of spent outputs for which the spending milestone isn't known
+        let missing_metadata = outputs
+            .values()
+            .filter(|(_, metadata)| metadata.is_spent() && metadata.milestone_index_spent().is_none())
+            .map(|(_, metadata)| *metadata.output_id())
+            .collect::<Vec<_>>();
+        if !missing_metadata.is_empty() {
+            for metadata in self
+                .client()
+                .get_outputs_metadata_ignore_errors(&missing_metadata)
+                .await?
+            {
+                if let Some((_, output_metadata)) = outputs.get_mut(metadata.output_id()) {
+                    *output_metadata = metadata;
+                }
+            }
+        }
+
+        let mut potentially_incomplete = missing_inputs;
+        let mut unknown_outputs = Vec::new();
+        let mut unspent_outputs = Vec::new();
+
+        for (output_id, (output, metadata)) in outputs {
+            match point.is_unspent(&metadata) {
+                Some(true) => unspent_outputs.push((output_id, output, metadata)),
+                Some(false) => {}
+                None => {
+                    potentially_incomplete = true;
+                    unknown_outputs.push(output_id);
+                }
+            }
+        }
+
+        let milestone_timestamp = match point {
+            BalancePoint::MilestoneIndex(index) => {
+                if index <= pruning_index {
+                    potentially_incomplete = true;
+                }
+                match self.client().get_milestone_by_index(index).await {
+                    Ok(milestone) => milestone.essence().timestamp(),
+                    Err(e) => {
+                        log::debug!("[BALANCE] couldn't get milestone {index}: {e}");
+                        potentially_incomplete = true;
+                        // Approximate with the latest known booking of an output at that point
+                        unspent_outputs
+                            .iter()
+                            .map(|(_, _, metadata)| metadata.milestone_timestamp_booked())
+                            .max()
+                            .unwrap_or_default()
+                    }
+                }
+            }
+            BalancePoint::MilestoneTimestamp(timestamp) => {
+                match self.client().get_milestone_by_index(pruning_index + 1).await {
+                    Ok(milestone) => {
+                        if timestamp < milestone.essence().timestamp() {
+                            potentially_incomplete = true;
+                        }
+                    }
+                    Err(_) => potentially_incomplete = true,
+                }
+                timestamp
+            }
+        };
+
+        let mut balance = Balance::default();
+        let mut total_rent_amount = 0;
+        let mut total_native_tokens = NativeTokensBuilder::default();
+
+        for (output_id, output, _) in &unspent_outputs {
+            let rent = output.rent_cost(&rent_structure);
+
+            match output {
+                Output::Alias(output) => {
+                    balance.base_coin.total += output.amount();
+                    balance.required_storage_deposit.alias += rent;
+                    total_rent_amount += rent;
+                    total_native_tokens.add_native_tokens(output.native_tokens().clone())?;
+                    balance.aliases.push(output.alias_id_non_null(output_id));
+                }
+                Output::Foundry(output) => {
+                    balance.base_coin.total += output.amount();
+                    balance.required_storage_deposit.foundry += rent;
+                    total_rent_amount += rent;
+                    total_native_tokens.add_native_tokens(output.native_tokens().clone())?;
+                    balance.foundries.push(output.id());
+                }
+                _ => {
+                    let unlock_conditions = output
+                        .unlock_conditions()
+                        .expect("output needs to have unlock conditions");
+
+                    let mut amount = output.amount();
+
+                    if let Some(expiration) = unlock_conditions.expiration() {
+                        if unlock_conditions.is_expired(milestone_timestamp) {
+                            // Expired outputs only count if they returned to us
+                            if !account_addresses.contains(expiration.return_address()) {
+                                continue;
+                            }
+                        } else {
+                            // Only ours if it gets claimed before the expiration, otherwise it returns to the sender
+                            balance.potentially_locked_outputs.insert(*output_id, true);
+                            continue;
+                        }
+                    } else if let Some(sdr) = unlock_conditions.storage_deposit_return() {
+                        if !account_addresses.contains(sdr.return_address()) {
+                            amount -= sdr.amount();
+                        }
+                    }
+
+                    if unlock_conditions.is_time_locked(milestone_timestamp) {
+                        balance.potentially_locked_outputs.insert(*output_id, false);
+                        continue;
+                    }
+
+                    if let Output::Nft(output) = output {
+                        balance.nfts.push(output.nft_id_non_null(output_id));
+                    }
+
+                    balance.base_coin.total += amount;
+
+                    if let Some(native_tokens) = output.native_tokens() {
+                        if output.is_basic() {
+                            balance.required_storage_deposit.basic += rent;
+                            // Basic outputs without native tokens can be spent without burning anything
+                            if !native_tokens.is_empty() {
+                                total_rent_amount += rent;
+                            }
+                        } else {
+                            balance.required_storage_deposit.nft += rent;
+                            total_rent_amount += rent;
+                        }
+                        total_native_tokens.add_native_tokens(native_tokens.clone())?;
+                    }
+                }
+            }
+        }
+
+        let token_registry = self.wallet.token_registry.read().await;
+
+        for native_token in total_native_tokens.finish_set()? {
+            let metadata = native_token_foundries
+                .get(&FoundryId::from(*native_token.token_id()))
+                .and_then(|foundry| foundry.immutable_features().metadata())
+                .cloned();
+
+            balance.native_tokens.push(NativeTokensBalance {
+                token_id: *native_token.token_id(),
+                total: native_token.amount(),
+                available: native_token.amount(),
+                irc30_metadata: token_registry.resolve(native_token.token_id(), metadata.as_ref()),
+                verified: token_registry.is_verified(native_token.token_id()),
+                metadata,
+            })
+        }
+
+        balance.base_coin.available = balance.base_coin.total.saturating_sub(total_rent_amount);
+
+        Ok(HistoricBalance {
+            point,
+            milestone_timestamp,
+            balance,
+            potentially_incomplete,
+            unknown_outputs,
+        })
+    }
+
+    /// Get the timeline of the balance of the account: the assets that become spendable or stop being spendable at
+    /// future milestone timestamps, because a
+    /// [`TimelockUnlockCondition`](crate::types::block::output::unlock_condition::TimelockUnlockCondition) ends or an
+    /// [`ExpirationUnlockCondition`](crate::types::block::output::unlock_condition::ExpirationUnlockCondition)
+    /// changes the owner of an output from or to the account. The entries are sorted by timestamp.
+    pub async fn balance_timeline(&self) -> Result<Vec<BalanceTimelineEntry>> {
+        log::debug!("[BALANCE] balance_timeline");
+
+        let network_id = self.client().get_network_id().await?;
+        let current_time = self.client().get_time_checked().await?;
+        let account_details = self.details().await;
+
+        // Expirations can return outputs to any address of the account, not only to the ones with unspent outputs
+        let account_addresses = account_details
+            .public_addresses()
+            .iter()
+            .chain(account_details.internal_addresses())
+            .map(|address| *address.address.inner())
+            .collect::<HashSet<Address>>();
+
+        // (unlocked, expiring) per timestamp
+        let mut changes = BTreeMap::<u32, (BalanceChangeBuilder, BalanceChangeBuilder)>::new();
+
+        for (output_id, output_data) in account_details.unspent_outputs() {
+            // Check if output is from the network we're currently connected to
+            if output_data.network_id != network_id {
+                continue;
+            }
+
+            let unlock_conditions = match output_data.output.unlock_conditions() {
+                Some(unlock_conditions) => unlock_conditions,
+                None => continue,
+            };
+
+            // Only a timelock or an expiration in the future can change if the output is spendable
+            let mut change_timestamps = [
+                unlock_conditions.timelock().map(|timelock| timelock.timestamp()),
+                unlock_conditions.expiration().map(|expiration| expiration.timestamp()),
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|timestamp| *timestamp > current_time)
+            .collect::<Vec<_>>();
+            change_timestamps.sort_unstable();
+            change_timestamps.dedup();
+
+            let mut spendable = spendable_amount(&output_data.output, &account_addresses, current_time);
+
+            for timestamp in change_timestamps {
+                let spendable_then = spendable_amount(&output_data.output, &account_addresses, timestamp);
+
+                match (spendable, spendable_then) {
+                    (None, Some(amount)) => {
+                        changes
+                            .entry(timestamp)
+                            .or_default()
+                            .0
+                            .add(output_id, &output_data.output, amount)?;
+                    }
+                    (Some(amount), None) => {
+                        changes
+                            .entry(timestamp)
+                            .or_default()
+                            .1
+                            .add(output_id, &output_data.output, amount)?;
+                    }
+                    _ => {}
+                }
+
+                spendable = spendable_then;
+            }
+        }
+
+        changes
+            .into_iter()
+            .map(|(timestamp, (unlocked, expiring))| -> Result<BalanceTimelineEntry> {
+                Ok(BalanceTimelineEntry {
+                    timestamp,
+                    unlocked: unlocked.finish()?,
+                    expiring: expiring.finish()?,
+                })
+            })
+            .collect()
+    }
+
     async fn balance_inner(
         &self,
         addresses_with_unspent_outputs: impl Iterator<Item = &AddressWithUnspentOutputs> + Send,
@@ -66,6 +398,8 @@ where
         let mut balance = Balance::default();
         let mut total_rent_amount = 0;
         let mut total_native_tokens = NativeTokensBuilder::default();
+        // Assets owned by alias and NFT addresses, per owning address
+        let mut chain_balances = HashMap::<Address, BalanceChangeBuilder>::new();
 
         #[cfg(feature = "participation")]
         let voting_output = self.get_voting_output().await?;
@@ -106,6 +440,13 @@ where
 
                             let alias_id = output.alias_id_non_null(output_id);
                             balance.aliases.push(alias_id);
+
+                            add_chain_owned(
+                                &mut chain_balances,
+                                output.state_controller_address(),
+                                output_id,
+                                &data.output,
+                            )?;
                         }
                         Output::Foundry(output) => {
                             // Add amount
@@ -119,15 +460,29 @@ where
                             total_native_tokens.add_native_tokens(output.native_tokens().clone())?;
 
                             balance.foundries.push(output.id());
+
+                            add_chain_owned(
+                                &mut chain_balances,
+                                &Address::from(*output.alias_address()),
+                                output_id,
+                                &data.output,
+                            )?;
                         }
                         _ => {
                             // If there is only an [AddressUnlockCondition], then we can spend the output at any time
                             // without restrictions
-                            if let [UnlockCondition::Address(_)] = output
+                            if let [UnlockCondition::Address(address_unlock_condition)] = output
                                 .unlock_conditions()
                                 .expect("output needs to have unlock conditions")
                                 .as_ref()
                             {
+                                add_chain_owned(
+                                    &mut chain_balances,
+                                    address_unlock_condition.address(),
+                                    output_id,
+                                    output,
+                                )?;
+
                                 // add nft_id for nft outputs
                                 if let Output::Nft(output) = &output {
                                     let nft_id = output.nft_id_non_null(output_id);
@@ -268,9 +623,20 @@ where
             }
         }
 
+        if !chain_balances.is_empty() {
+            let bech32_hrp = self.client().get_bech32_hrp().await?;
+            balance.owned_by_chains = chain_balances
+                .into_iter()
+                .map(|(address, builder)| Ok((Bech32Address::new(bech32_hrp, address), builder.finish()?)))
+                .collect::<Result<_>>()?;
+        }
+
+        let token_registry = self.wallet.token_registry.read().await;
+
         self.finish(
             balance,
             account_details,
+            &token_registry,
             network_id,
             total_rent_amount,
             total_native_tokens,
@@ -281,6 +647,7 @@ where
         &self,
         mut balance: Balance,
         account_details: &AccountDetails,
+        token_registry: &TokenRegistry,
         network_id: u64,
         total_rent_amount: u64,
         total_native_tokens: NativeTokensBuilder,
@@ -336,6 +703,8 @@ where
                 token_id: *native_token.token_id(),
                 total: native_token.amount(),
                 available: native_token.amount() - *locked_native_token_amount.unwrap_or(&U256::from(0u8)),
+                irc30_metadata: token_registry.resolve(native_token.token_id(), metadata.as_ref()),
+                verified: token_registry.is_verified(native_token.token_id()),
                 metadata,
             })
         }
@@ -356,3 +725,79 @@ where
         Ok(balance)
     }
 }
+
+// Returns the amount of an output that the account can spend at a milestone timestamp, or `None` if the account
+// can't spend the output at that time
+fn spendable_amount(output: &Output, account_addresses: &HashSet<Address>, milestone_timestamp: u32) -> Option<u64> {
+    let unlock_conditions = output.unlock_conditions()?;
+
+    if unlock_conditions.is_time_locked(milestone_timestamp) {
+        return None;
+    }
+
+    let address = unlock_conditions.address()?.address();
+    if !account_addresses.contains(unlock_conditions.locked_address(address, milestone_timestamp)) {
+        return None;
+    }
+
+    let mut amount = output.amount();
+    // The storage deposit only needs to be returned as long as the output didn't expire
+    if !unlock_conditions.is_expired(milestone_timestamp) {
+        if let Some(sdr) = unlock_conditions.storage_deposit_return() {
+            if !account_addresses.contains(sdr.return_address()) {
+                amount -= sdr.amount();
+            }
+        }
+    }
+
+    Some(amount)
+}
+
+// Adds an output to the assets owned by its address, if it's an alias or NFT address
+fn add_chain_owned(
+    chain_balances: &mut HashMap<Address, BalanceChangeBuilder>,
+    owner: &Address,
+    output_id: &OutputId,
+    output: &Output,
+) -> Result<()> {
+    if !owner.is_ed25519() {
+        chain_balances
+            .entry(*owner)
+            .or_default()
+            .add(output_id, output, output.amount())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct BalanceChangeBuilder {
+    base_coin: u64,
+    native_tokens: NativeTokensBuilder,
+    nfts: Vec<NftId>,
+    outputs: Vec<OutputId>,
+}
+
+impl BalanceChangeBuilder {
+    fn add(&mut self, output_id: &OutputId, output: &Output, amount: u64) -> Result<()> {
+        self.base_coin += amount;
+        if let Some(native_tokens) = output.native_tokens() {
+            self.native_tokens.add_native_tokens(native_tokens.clone())?;
+        }
+        if let Output::Nft(nft_output) = output {
+            self.nfts.push(nft_output.nft_id_non_null(output_id));
+        }
+        self.outputs.push(*output_id);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<BalanceChange> {
+        Ok(BalanceChange {
+            base_coin: self.base_coin,
+            native_tokens: self.native_tokens.finish_vec()?,
+            nfts: self.nfts,
+            outputs: self.outputs,
+        })
+    }
+}
@@ -18,8 +18,12 @@ pub(crate) mod output_finder;
 /// The module for participation
 #[cfg(feature = "participation")]
 pub(crate) mod participation;
+/// The module for reclaiming storage deposit of over-funded outputs
+pub(crate) mod reclaim_storage_deposit;
 /// The module for retrying blocks or transactions
 pub(crate) mod retry;
+/// The module for sweeping outputs of an external secret manager into an account
+pub(crate) mod sweep;
 /// The module for synchronization of an account
 pub(crate) mod syncing;
 /// The module for transactions
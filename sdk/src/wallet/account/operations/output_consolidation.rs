@@ -1,6 +1,9 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
+use getset::Getters;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ledger_nano")]
@@ -29,7 +32,7 @@ const MIN_OUTPUT_SIZE_IN_ESSENCE: usize = 46;
 use crate::wallet::account::constants::DEFAULT_LEDGER_OUTPUT_CONSOLIDATION_THRESHOLD;
 use crate::wallet::{
     account::{
-        constants::DEFAULT_OUTPUT_CONSOLIDATION_THRESHOLD,
+        constants::{DEFAULT_CONSOLIDATION_MIN_INTERVAL, DEFAULT_OUTPUT_CONSOLIDATION_THRESHOLD},
         operations::{helpers::time::can_output_be_unlocked_now, output_claiming::get_new_native_token_count},
         types::{OutputData, Transaction},
         Account, AddressWithUnspentOutputs, TransactionOptions,
@@ -46,6 +49,9 @@ pub struct ConsolidationParams {
     output_threshold: Option<usize>,
     /// Address to which the consolidated output should be sent.
     target_address: Option<Bech32Address>,
+    /// Doesn't consolidate outputs holding native tokens if set to `true`.
+    #[serde(default)]
+    skip_native_tokens: bool,
 }
 
 impl ConsolidationParams {
@@ -67,6 +73,86 @@ impl ConsolidationParams {
         self.target_address = target_address.into();
         self
     }
+
+    pub fn with_skip_native_tokens(mut self, skip_native_tokens: bool) -> Self {
+        self.skip_native_tokens = skip_native_tokens;
+        self
+    }
+}
+
+/// Consolidation that is applied automatically by the background syncing. Merging outputs releases the storage deposit
+/// of the consumed outputs. Locked outputs, outputs that can't be unlocked now and outputs with a storage deposit return
+/// are not consolidated.
+#[derive(Clone, Debug, Eq, PartialEq, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationPolicy {
+    /// Consolidates if the output number is >= the output_threshold.
+    #[serde(default)]
+    output_threshold: Option<usize>,
+    /// Minimum time between two consolidations, serialized in milliseconds.
+    #[serde(
+        default = "default_consolidation_min_interval",
+        with = "crate::utils::serde::duration_millis"
+    )]
+    min_interval: Duration,
+    /// Address to which the consolidated outputs should be sent.
+    #[serde(default)]
+    target_address: Option<Bech32Address>,
+    /// Also consolidates outputs holding native tokens, with at most `NativeTokens::COUNT_MAX` different ones per
+    /// transaction.
+    #[serde(default)]
+    merge_native_tokens: bool,
+}
+
+fn default_consolidation_min_interval() -> Duration {
+    DEFAULT_CONSOLIDATION_MIN_INTERVAL
+}
+
+impl Default for ConsolidationPolicy {
+    fn default() -> Self {
+        Self {
+            output_threshold: None,
+            min_interval: default_consolidation_min_interval(),
+            target_address: None,
+            merge_native_tokens: false,
+        }
+    }
+}
+
+impl ConsolidationPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_output_threshold(mut self, output_threshold: impl Into<Option<usize>>) -> Self {
+        self.output_threshold = output_threshold.into();
+        self
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn with_target_address(mut self, target_address: impl Into<Option<Bech32Address>>) -> Self {
+        self.target_address = target_address.into();
+        self
+    }
+
+    pub fn with_merge_native_tokens(mut self, merge_native_tokens: bool) -> Self {
+        self.merge_native_tokens = merge_native_tokens;
+        self
+    }
+}
+
+impl From<&ConsolidationPolicy> for ConsolidationParams {
+    fn from(policy: &ConsolidationPolicy) -> Self {
+        Self::new()
+            .with_output_threshold(policy.output_threshold)
+            .with_target_address(policy.target_address)
+            .with_skip_native_tokens(!policy.merge_native_tokens)
+    }
 }
 
 impl<S: 'static + SecretManage> Account<S>
@@ -102,10 +188,71 @@ where
         })
     }
 
+    /// Set the [ConsolidationPolicy] that is applied by the background syncing, `None` disables it.
+    /// If storage is enabled, will persist during restarts.
+    pub async fn set_consolidation_policy(&self, policy: impl Into<Option<ConsolidationPolicy>> + Send) -> Result<()> {
+        let policy = policy.into();
+
+        #[cfg(feature = "storage")]
+        {
+            let index = *self.details().await.index();
+            let storage_manager = self.wallet.storage_manager.read().await;
+            storage_manager.set_consolidation_policy(index, policy.as_ref()).await?;
+        }
+
+        *self.consolidation_policy.lock().await = policy;
+        Ok(())
+    }
+
+    /// Get the [ConsolidationPolicy] that is applied by the background syncing.
+    pub async fn consolidation_policy(&self) -> Option<ConsolidationPolicy> {
+        self.consolidation_policy.lock().await.clone()
+    }
+
+    /// Consolidates outputs according to the [ConsolidationPolicy], if one is set and the last consolidation happened
+    /// at least `min_interval` ago. Outputs that don't fit into a single transaction are consolidated by additional
+    /// transactions, until less outputs than the threshold are left. Returns the sent transactions.
+    pub(crate) async fn apply_consolidation_policy(&self) -> Result<Vec<Transaction>> {
+        let policy = match self.consolidation_policy().await {
+            Some(policy) => policy,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut last_consolidation = self.last_consolidation.lock().await;
+        let time_now = crate::utils::unix_timestamp_now().as_millis();
+        if time_now.saturating_sub(*last_consolidation) < policy.min_interval.as_millis() {
+            log::debug!("[OUTPUT_CONSOLIDATION] consolidated within the minimum interval");
+            return Ok(Vec::new());
+        }
+
+        // The inputs of a sent transaction are locked, so every transaction consolidates other outputs
+        let mut transactions = Vec::new();
+        let result = loop {
+            match self.consolidate_outputs(ConsolidationParams::from(&policy)).await {
+                Ok(transaction) => transactions.push(transaction),
+                Err(crate::wallet::Error::NoOutputsToConsolidate { .. }) => break Ok(()),
+                Err(err) => break Err(err),
+            }
+        };
+
+        if !transactions.is_empty() {
+            *last_consolidation = time_now;
+            #[cfg(feature = "storage")]
+            {
+                let index = *self.details().await.index();
+                let storage_manager = self.wallet.storage_manager.read().await;
+                storage_manager.set_last_consolidation(index, time_now).await?;
+            }
+        }
+
+        result.map(|_| transactions)
+    }
+
     /// Consolidates basic outputs with only an [AddressUnlockCondition] from an account by sending them to a provided
     /// address or to an own address again if the output amount is >= the output_threshold. When `force`
     /// is set to `true`, the threshold is ignored. Only consolidates the amount of outputs that fit into a single
-    /// transaction.
+    /// transaction, outputs with native tokens that would exceed the max native tokens count of the transaction are
+    /// left for another consolidation.
     pub async fn consolidate_outputs(&self, params: ConsolidationParams) -> Result<Transaction> {
         let prepared_transaction = self.prepare_consolidate_outputs(params).await?;
         let consolidation_tx = self.sign_and_submit_transaction(prepared_transaction, None).await?;
@@ -139,6 +286,14 @@ where
                     continue;
                 }
             }
+            if params.skip_native_tokens
+                && output_data
+                    .output
+                    .native_tokens()
+                    .map_or(false, |native_tokens| !native_tokens.is_empty())
+            {
+                continue;
+            }
             let is_locked_output = account_details.locked_outputs.contains(output_id);
             let should_consolidate_output =
                 self.should_consolidate_output(output_data, current_time, account_addresses)?;
@@ -232,9 +387,13 @@ where
         let mut custom_inputs = Vec::with_capacity(max_inputs.into());
         let mut total_native_tokens = NativeTokensBuilder::new();
 
-        for output_data in outputs_to_consolidate.iter().take(max_inputs.into()) {
+        for output_data in &outputs_to_consolidate {
+            if custom_inputs.len() >= max_inputs.into() {
+                break;
+            }
             if let Some(native_tokens) = output_data.output.native_tokens() {
-                // Skip output if the max native tokens count would be exceeded
+                // Skip output if the max native tokens count would be exceeded, it's consolidated by another
+                // transaction
                 if get_new_native_token_count(&total_native_tokens, native_tokens)? > NativeTokens::COUNT_MAX.into() {
                     log::debug!("[OUTPUT_CONSOLIDATION] skipping output to not exceed the max native tokens count");
                     continue;
@@ -264,3 +423,23 @@ where
         self.prepare_transaction(consolidation_output, options).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consolidation_policy_serde() {
+        let policy = ConsolidationPolicy::new()
+            .with_output_threshold(50)
+            .with_min_interval(Duration::from_secs(60));
+        let json = serde_json::to_value(&policy).unwrap();
+
+        assert_eq!(json["minInterval"], 60_000);
+        assert_eq!(serde_json::from_value::<ConsolidationPolicy>(json).unwrap(), policy);
+        assert_eq!(
+            serde_json::from_str::<ConsolidationPolicy>("{}").unwrap(),
+            ConsolidationPolicy::default()
+        );
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{cmp::Reverse, collections::HashSet};
+
+use crate::{
+    client::secret::SecretManage,
+    types::block::{
+        address::{Address, Bech32Address},
+        output::{
+            unlock_condition::{AddressUnlockCondition, UnlockCondition},
+            AliasId, AliasOutputBuilder, BasicOutputBuilder, FoundryOutputBuilder, NftOutputBuilder, Output, OutputId,
+            OUTPUT_COUNT_MAX,
+        },
+        ConvertTo,
+    },
+    wallet::account::{types::Transaction, Account, TransactionOptions},
+};
+
+// An output holding more than its minimum storage deposit
+struct OverFundedOutput {
+    output_id: OutputId,
+    // The output transitioned down to its minimum storage deposit
+    reduced: Output,
+    excess: u64,
+    // The alias that needs to be transitioned as well, for foundry outputs
+    controlling_alias: Option<AliasId>,
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Reclaims the base coins that NFT, alias, foundry and basic outputs of the account hold in excess of their
+    /// minimum storage deposit, for example because the rent structure changed. Every over-funded output is
+    /// transitioned down to its minimum storage deposit and the released funds are sent to the provided address, in as
+    /// few transactions as possible. Basic outputs are only reduced if they hold native tokens, since otherwise their
+    /// whole amount can already be spent, and only outputs without further unlock conditions are considered.
+    ///
+    /// Every transaction is awaited to be included before the next one is sent. Mandatory inputs of the options are
+    /// spent by the first transaction. Returns an empty list if the released funds wouldn't cover the storage deposit
+    /// of the output sending them.
+    /// ```ignore
+    /// let transactions = account.reclaim_storage_deposit(account.addresses().await?[0].address(), None).await?;
+    /// for transaction in transactions {
+    ///     println!("Transaction sent: {}", transaction.transaction_id);
+    /// }
+    /// ```
+    pub async fn reclaim_storage_deposit(
+        &self,
+        address: impl ConvertTo<Bech32Address>,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Vec<Transaction>> {
+        log::debug!("[TRANSACTION] reclaim_storage_deposit");
+        let address = address.convert()?;
+        self.client().bech32_hrp_matches(address.hrp()).await?;
+        let mut options = options.into();
+        // Inputs requested by the caller are only spent by the first transaction
+        let mut mandatory_inputs = options.as_mut().and_then(|options| options.mandatory_inputs.take());
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+
+        let minimum_release_amount = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)
+            .add_unlock_condition(AddressUnlockCondition::new(address))
+            .finish_output(token_supply)?
+            .amount();
+
+        let mut transactions: Vec<Transaction> = Vec::new();
+
+        loop {
+            if let Some(previous_transaction) = transactions.last() {
+                // Transitioned alias outputs of the previous transaction can be needed as inputs
+                self.retry_transaction_until_included(&previous_transaction.transaction_id, None, None)
+                    .await?;
+                self.sync(None).await?;
+            }
+
+            let mut over_funded_outputs = self.over_funded_outputs().await?;
+            // Start with the outputs releasing the most funds
+            over_funded_outputs.sort_by_key(|over_funded_output| Reverse(over_funded_output.excess));
+
+            // Leave room for the output with the released funds and for a remainder
+            let mut output_count = 2;
+            let mut transitioned_aliases = HashSet::new();
+            let mut chunk = Vec::new();
+
+            for over_funded_output in over_funded_outputs {
+                let additional_alias = over_funded_output
+                    .controlling_alias
+                    .filter(|alias_id| !transitioned_aliases.contains(alias_id));
+                let additional_outputs = 1 + additional_alias.is_some() as usize;
+
+                if output_count + additional_outputs > OUTPUT_COUNT_MAX as usize {
+                    break;
+                }
+                output_count += additional_outputs;
+                transitioned_aliases.extend(additional_alias);
+                if let Output::Alias(alias_output) = &over_funded_output.reduced {
+                    // Foundries of this alias don't need an additional output for the alias transition anymore
+                    if !transitioned_aliases.insert(*alias_output.alias_id()) {
+                        output_count -= 1;
+                    }
+                }
+                chunk.push(over_funded_output);
+            }
+
+            let released_amount = chunk
+                .iter()
+                .map(|over_funded_output| over_funded_output.excess)
+                .sum::<u64>();
+            if released_amount < minimum_release_amount {
+                log::debug!("[TRANSACTION] released amount {released_amount} doesn't cover a storage deposit");
+                break;
+            }
+
+            let mut transaction_options = options.clone().unwrap_or_default();
+            transaction_options.mandatory_inputs = Some(
+                mandatory_inputs
+                    .take()
+                    .into_iter()
+                    .flatten()
+                    .chain(chunk.iter().map(|over_funded_output| over_funded_output.output_id))
+                    .collect(),
+            );
+
+            let outputs = chunk
+                .into_iter()
+                .map(|over_funded_output| over_funded_output.reduced)
+                .chain(std::iter::once(
+                    BasicOutputBuilder::new_with_amount(released_amount)
+                        .add_unlock_condition(AddressUnlockCondition::new(address))
+                        .finish_output(token_supply)?,
+                ))
+                .collect::<Vec<_>>();
+
+            transactions.push(self.send_outputs(outputs, transaction_options).await?);
+        }
+
+        Ok(transactions)
+    }
+
+    // Returns the unspent outputs of the account that hold more than their minimum storage deposit.
+    async fn over_funded_outputs(&self) -> crate::wallet::Result<Vec<OverFundedOutput>> {
+        let network_id = self.client().get_network_id().await?;
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+        let account_details = self.details().await;
+
+        let account_addresses = account_details
+            .addresses_with_unspent_outputs()
+            .iter()
+            .map(|address| *address.address.inner())
+            .collect::<HashSet<Address>>();
+
+        let mut over_funded_outputs = Vec::new();
+
+        for (output_id, output_data) in account_details.unspent_outputs() {
+            if output_data.network_id != network_id || account_details.locked_outputs.contains(output_id) {
+                continue;
+            }
+
+            let (reduced, controlling_alias) = match &output_data.output {
+                Output::Basic(basic_output) => {
+                    if basic_output.native_tokens().is_empty()
+                        || !matches!(basic_output.unlock_conditions().as_ref(), [UnlockCondition::Address(_)])
+                    {
+                        continue;
+                    }
+                    (
+                        BasicOutputBuilder::from(basic_output)
+                            .with_minimum_storage_deposit(rent_structure)
+                            .finish_output(token_supply)?,
+                        None,
+                    )
+                }
+                Output::Nft(nft_output) => {
+                    if !matches!(nft_output.unlock_conditions().as_ref(), [UnlockCondition::Address(_)]) {
+                        continue;
+                    }
+                    (
+                        NftOutputBuilder::from(nft_output)
+                            .with_nft_id(nft_output.nft_id_non_null(output_id))
+                            .with_minimum_storage_deposit(rent_structure)
+                            .finish_output(token_supply)?,
+                        None,
+                    )
+                }
+                Output::Alias(alias_output) => {
+                    // A state transition is needed to change the amount
+                    if !account_addresses.contains(alias_output.state_controller_address()) {
+                        continue;
+                    }
+                    (
+                        AliasOutputBuilder::from(alias_output)
+                            .with_alias_id(alias_output.alias_id_non_null(output_id))
+                            .with_state_index(alias_output.state_index() + 1)
+                            .with_minimum_storage_deposit(rent_structure)
+                            .finish_output(token_supply)?,
+                        None,
+                    )
+                }
+                Output::Foundry(foundry_output) => (
+                    FoundryOutputBuilder::from(foundry_output)
+                        .with_minimum_storage_deposit(rent_structure)
+                        .finish_output(token_supply)?,
+                    Some(*foundry_output.alias_address().alias_id()),
+                ),
+                Output::Treasury(_) => continue,
+            };
+
+            if reduced.amount() < output_data.output.amount() {
+                over_funded_outputs.push(OverFundedOutput {
+                    output_id: *output_id,
+                    excess: output_data.output.amount() - reduced.amount(),
+                    reduced,
+                    controlling_alias,
+                });
+            }
+        }
+
+        Ok(over_funded_outputs)
+    }
+}
@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    client::{secret::SecretManage, Error as ClientError},
+    client::{
+        api::{InclusionOptions, InclusionOutcome},
+        secret::SecretManage,
+        Error as ClientError,
+    },
     types::{
         api::core::response::LedgerInclusionState,
         block::{
@@ -11,7 +15,7 @@ use crate::{
         },
     },
     wallet::{
-        account::{types::InclusionState, Account},
+        account::{operations::syncing::transactions::updated_transaction_and_outputs, types::InclusionState, Account},
         Error,
     },
 };
@@ -136,4 +140,82 @@ where
             Err(Error::TransactionNotFound(*transaction_id))
         }
     }
+
+    /// Returns once a transaction sent from the account is included, conflicting or the timeout of the options
+    /// passed, promoting or reattaching its block until then. The block is posted first if the transaction has none.
+    /// The inclusion state and block of the transaction are updated with the outcome. See
+    /// [`Client::await_inclusion()`](crate::client::Client::await_inclusion).
+    pub async fn await_transaction_inclusion(
+        &self,
+        transaction_id: &TransactionId,
+        options: impl Into<Option<InclusionOptions>> + Send,
+    ) -> crate::wallet::Result<InclusionOutcome> {
+        log::debug!("[await_transaction_inclusion]");
+
+        let transaction = self
+            .details()
+            .await
+            .transactions
+            .get(transaction_id)
+            .cloned()
+            .ok_or(Error::TransactionNotFound(*transaction_id))?;
+
+        if transaction.inclusion_state == InclusionState::Confirmed {
+            return Ok(InclusionOutcome::Included {
+                block_id: transaction.block_id.ok_or(Error::MissingParameter("block id"))?,
+            });
+        }
+
+        // A conflicting transaction is only awaited to get the conflict reason of its block
+        if transaction.inclusion_state == InclusionState::UnknownPruned
+            || (transaction.inclusion_state == InclusionState::Conflicting && transaction.block_id.is_none())
+        {
+            return Err(ClientError::TangleInclusion(format!(
+                "transaction id: {} inclusion state: {:?}",
+                transaction_id, transaction.inclusion_state
+            ))
+            .into());
+        }
+
+        let block_id = match transaction.block_id {
+            Some(block_id) => block_id,
+            None => self
+                .client()
+                .build_block()
+                .finish_block(Some(Payload::Transaction(Box::new(transaction.payload.clone()))))
+                .await?
+                .id(),
+        };
+
+        let outcome = self.client().await_inclusion(&block_id, options).await?;
+
+        let mut updated_transactions = Vec::new();
+        let mut spent_output_ids = Vec::new();
+        match outcome {
+            InclusionOutcome::Included { block_id } => updated_transaction_and_outputs(
+                transaction,
+                Some(block_id),
+                InclusionState::Confirmed,
+                &mut updated_transactions,
+                &mut spent_output_ids,
+            ),
+            InclusionOutcome::Conflicting { block_id, .. } => updated_transaction_and_outputs(
+                transaction,
+                Some(block_id),
+                InclusionState::Conflicting,
+                &mut updated_transactions,
+                &mut spent_output_ids,
+            ),
+            // Still pending, but later checks can start from the latest attachment
+            InclusionOutcome::TimedOut { latest_attachment } => {
+                let mut transaction = transaction;
+                transaction.block_id.replace(latest_attachment);
+                updated_transactions.push(transaction);
+            }
+        }
+        self.update_account_with_transactions(updated_transactions, spent_output_ids, Vec::new())
+            .await?;
+
+        Ok(outcome)
+    }
 }
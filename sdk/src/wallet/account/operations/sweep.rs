@@ -0,0 +1,667 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use crypto::keys::bip44::Bip44;
+use packable::PackableExt;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{
+        api::{
+            input_selection::InputSelection,
+            transaction::{
+                validate_transaction_payload_length, MAX_TX_LENGTH_FOR_BLOCK_WITH_8_PARENTS,
+                REFERENCE_ALIAS_NFT_UNLOCK_LENGTH, SINGLE_UNLOCK_LENGTH, UTXO_INPUT_LENGTH,
+            },
+            PreparedTransactionData, SignedTransactionData,
+        },
+        node_api::indexer::query_parameters::QueryParameter,
+        secret::{types::InputSigningData, GenerateAddressOptions, SecretManage},
+    },
+    types::block::{
+        address::{Address, Bech32Address},
+        input::INPUT_COUNT_MAX,
+        output::{
+            unlock_condition::{
+                AddressUnlockCondition, GovernorAddressUnlockCondition, StateControllerAddressUnlockCondition,
+                StorageDepositReturnUnlockCondition,
+            },
+            AliasOutputBuilder, AliasTransition, BasicOutputBuilder, NativeTokens, NativeTokensBuilder,
+            NftOutputBuilder, Output, OutputId, TokenId, OUTPUT_COUNT_MAX,
+        },
+        payload::transaction::TransactionPayload,
+        protocol::ProtocolParameters,
+    },
+    wallet::{
+        account::{
+            constants::DEFAULT_SWEEP_ADDRESS_GAP, operations::output_claiming::get_new_native_token_count,
+            types::Transaction, Account,
+        },
+        Result,
+    },
+};
+
+// Packed length of a native token: token id + amount
+const NATIVE_TOKEN_LENGTH: usize = TokenId::LENGTH + core::mem::size_of::<U256>();
+// Maximum packed length of the inputs, unlocks and outputs of a sweep transaction, leaving room for the other fields
+// of the transaction and the basic output with the base coins
+const MAX_SWEEP_TRANSACTION_LENGTH: usize = MAX_TX_LENGTH_FOR_BLOCK_WITH_8_PARENTS - 256;
+
+/// Parameters for [`Account::sweep()`], describing which addresses of the external secret manager are scanned.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SweepParams {
+    /// The account index of the external secret manager to sweep from.
+    account_index: u32,
+    /// The coin type to generate the addresses with, defaults to the coin type of the account.
+    coin_type: Option<u32>,
+    /// The number of consecutive addresses without outputs after which the scan stops.
+    address_gap: u32,
+}
+
+impl Default for SweepParams {
+    fn default() -> Self {
+        Self {
+            account_index: 0,
+            coin_type: None,
+            address_gap: DEFAULT_SWEEP_ADDRESS_GAP,
+        }
+    }
+}
+
+impl SweepParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_account_index(mut self, account_index: u32) -> Self {
+        self.account_index = account_index;
+        self
+    }
+
+    pub fn with_coin_type(mut self, coin_type: impl Into<Option<u32>>) -> Self {
+        self.coin_type = coin_type.into();
+        self
+    }
+
+    pub fn with_address_gap(mut self, address_gap: u32) -> Self {
+        self.address_gap = address_gap;
+        self
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Sweeps all basic, NFT and alias outputs that can be unlocked by the addresses of an external secret manager,
+    /// for example from a paper wallet, into this account. Outputs with a storage deposit return unlock condition
+    /// are claimed by returning the deposit, expired outputs are claimed if their return address belongs to the
+    /// secret manager. Aliases are moved with a governance transition, so only aliases with a governor address of the
+    /// secret manager are swept. As many transactions as needed are sent, which are signed with the provided secret
+    /// manager and stored in the account.
+    pub async fn sweep<T: SecretManage>(&self, secret_manager: &T, params: SweepParams) -> Result<Vec<Transaction>>
+    where
+        crate::wallet::Error: From<T::Error>,
+    {
+        let prepared_transactions = self.prepare_sweep(secret_manager, params).await?;
+        let mut transactions = Vec::with_capacity(prepared_transactions.len());
+
+        for prepared_transaction_data in prepared_transactions {
+            let unlocks = secret_manager
+                .sign_transaction_essence(&prepared_transaction_data, None)
+                .await?;
+            let transaction_payload = TransactionPayload::new(prepared_transaction_data.essence, unlocks)?;
+
+            validate_transaction_payload_length(&transaction_payload)?;
+
+            let transaction = self
+                .submit_and_store_transaction(
+                    SignedTransactionData {
+                        transaction_payload,
+                        inputs_data: prepared_transaction_data.inputs_data,
+                    },
+                    None,
+                )
+                .await?;
+
+            log::debug!(
+                "[SWEEP] sweep transaction created: block_id: {:?} tx_id: {:?}",
+                transaction.block_id,
+                transaction.transaction_id
+            );
+
+            transactions.push(transaction);
+        }
+
+        Ok(transactions)
+    }
+
+    /// Prepares the transactions for [Account::sweep()](crate::wallet::Account::sweep). The returned transactions
+    /// need to be signed with the same secret manager.
+    pub async fn prepare_sweep<T: SecretManage>(
+        &self,
+        secret_manager: &T,
+        params: SweepParams,
+    ) -> Result<Vec<PreparedTransactionData>>
+    where
+        crate::wallet::Error: From<T::Error>,
+    {
+        log::debug!("[SWEEP] prepare sweep");
+        let (coin_type, target_address) = {
+            let account_details = self.details().await;
+            (
+                params.coin_type.unwrap_or(account_details.coin_type),
+                // PANIC: an account always has a public address.
+                *account_details.public_addresses()[0].address().inner(),
+            )
+        };
+        let protocol_parameters = self.client().get_protocol_parameters().await?;
+        let current_time = self.client().get_time_checked().await?;
+
+        let (inputs, addresses) = self
+            .sweepable_inputs(secret_manager, coin_type, &params, current_time)
+            .await?;
+
+        log::debug!("[SWEEP] found {} outputs to sweep", inputs.len());
+
+        let mut prepared_transactions = Vec::new();
+
+        for batch in batch_sweep_inputs(inputs, current_time)? {
+            let outputs = sweep_outputs(&batch, target_address, &protocol_parameters, current_time)?;
+            let required_inputs = batch.iter().map(|input| *input.output_id()).collect::<HashSet<_>>();
+
+            let selected_transaction_data =
+                InputSelection::new(batch, outputs, addresses.iter().copied(), protocol_parameters.clone())
+                    .required_inputs(required_inputs)
+                    .remainder_address(target_address)
+                    .timestamp(current_time)
+                    .select()?;
+
+            prepared_transactions.push(self.build_transaction_essence(selected_transaction_data, None).await?);
+        }
+
+        Ok(prepared_transactions)
+    }
+
+    /// Scans the public and internal addresses of the secret manager until `address_gap` consecutive addresses without
+    /// outputs are found. Returns the outputs that can be unlocked now and the scanned addresses.
+    async fn sweepable_inputs<T: SecretManage>(
+        &self,
+        secret_manager: &T,
+        coin_type: u32,
+        params: &SweepParams,
+        current_time: u32,
+    ) -> Result<(Vec<InputSigningData>, Vec<Address>)>
+    where
+        crate::wallet::Error: From<T::Error>,
+    {
+        let bech32_hrp = self.client().get_bech32_hrp().await?;
+        let mut chains = HashMap::new();
+        let mut output_ids = HashSet::new();
+        let mut address_range = Some(0..params.address_gap);
+
+        while let Some(range) = address_range {
+            let mut found_outputs = false;
+            let mut new_addresses = false;
+
+            for internal in [false, true] {
+                let addresses = secret_manager
+                    .generate_ed25519_addresses(
+                        coin_type,
+                        params.account_index,
+                        range.clone(),
+                        GenerateAddressOptions {
+                            internal,
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+
+                for (address, key_index) in addresses.into_iter().zip(range.clone()) {
+                    let address = Address::Ed25519(address);
+                    // Secret managers with a single address return it for every index
+                    if chains.contains_key(&address) {
+                        continue;
+                    }
+                    new_addresses = true;
+                    chains.insert(
+                        address,
+                        Bip44::new(coin_type)
+                            .with_account(params.account_index)
+                            .with_change(internal as _)
+                            .with_address_index(key_index),
+                    );
+
+                    let address_output_ids = self
+                        .get_sweepable_output_ids(Bech32Address::new(bech32_hrp, address))
+                        .await?;
+                    found_outputs |= !address_output_ids.is_empty();
+                    output_ids.extend(address_output_ids);
+                }
+            }
+
+            address_range = next_address_range(range, found_outputs, new_addresses);
+        }
+
+        let output_ids = output_ids.into_iter().collect::<Vec<_>>();
+        let mut inputs = Vec::new();
+
+        for output_with_metadata in self.client().get_outputs(&output_ids).await? {
+            let output_id = *output_with_metadata.metadata().output_id();
+            let alias_transition = output_with_metadata
+                .output()
+                .is_alias()
+                .then_some(AliasTransition::Governance);
+
+            match output_with_metadata.output().unlock_conditions() {
+                Some(unlock_conditions) if !unlock_conditions.is_time_locked(current_time) => {}
+                _ => continue,
+            }
+
+            let (required_address, _) = output_with_metadata.output().required_and_unlocked_address(
+                current_time,
+                &output_id,
+                alias_transition,
+            )?;
+
+            // Outputs that expired and went back to a different address, or aliases of which only the state
+            // controller is known, can't be swept.
+            if let Some(chain) = chains.get(&required_address) {
+                inputs.push(InputSigningData {
+                    output: output_with_metadata.output().clone(),
+                    output_metadata: *output_with_metadata.metadata(),
+                    chain: Some(*chain),
+                });
+            }
+        }
+
+        Ok((inputs, chains.into_keys().collect()))
+    }
+
+    /// Returns output ids of basic and NFT outputs that have the address in the `AddressUnlockCondition` or
+    /// `ExpirationUnlockCondition` and of aliases that have the address as governor.
+    async fn get_sweepable_output_ids(&self, bech32_address: Bech32Address) -> Result<Vec<OutputId>> {
+        let client = self.client();
+        let mut output_ids = Vec::new();
+
+        output_ids.extend(
+            client
+                .basic_output_ids([QueryParameter::Address(bech32_address)])
+                .await?
+                .items,
+        );
+        output_ids.extend(
+            client
+                .basic_output_ids([QueryParameter::ExpirationReturnAddress(bech32_address)])
+                .await?
+                .items,
+        );
+        output_ids.extend(
+            client
+                .nft_output_ids([QueryParameter::Address(bech32_address)])
+                .await?
+                .items,
+        );
+        output_ids.extend(
+            client
+                .nft_output_ids([QueryParameter::ExpirationReturnAddress(bech32_address)])
+                .await?
+                .items,
+        );
+        output_ids.extend(
+            client
+                .alias_output_ids([QueryParameter::Governor(bech32_address)])
+                .await?
+                .items,
+        );
+
+        Ok(output_ids)
+    }
+}
+
+// Returns the next range of address indexes to scan, or `None` if the scan is finished because no outputs were found in
+// the last range, the secret manager generated no new addresses or the indexes are exhausted.
+fn next_address_range(last_range: Range<u32>, found_outputs: bool, new_addresses: bool) -> Option<Range<u32>> {
+    let gap = last_range.end - last_range.start;
+    let next_range = last_range.end..last_range.end.saturating_add(gap);
+
+    (found_outputs && new_addresses && !next_range.is_empty()).then_some(next_range)
+}
+
+// Returns the storage deposit return unlock condition of an output, if the deposit still needs to be returned.
+fn storage_deposit_return(output: &Output, current_time: u32) -> Option<&StorageDepositReturnUnlockCondition> {
+    let unlock_conditions = output.unlock_conditions()?;
+
+    if unlock_conditions.is_expired(current_time) {
+        return None;
+    }
+
+    unlock_conditions.storage_deposit_return()
+}
+
+// Returns the storage deposit return amount of an output, if it still needs to be returned.
+fn storage_deposit_return_amount(output: &Output, current_time: u32) -> Option<u64> {
+    storage_deposit_return(output, current_time).map(|sdr| sdr.amount())
+}
+
+// Returns the packed length an input adds to a sweep transaction: the input, its unlock and the outputs it requires.
+// Native tokens are counted separately since they are merged into a single output.
+fn sweep_input_length(input: &InputSigningData, signature_unlock: bool, current_time: u32) -> Result<usize> {
+    let unlock_length = if signature_unlock {
+        SINGLE_UNLOCK_LENGTH
+    } else {
+        REFERENCE_ALIAS_NFT_UNLOCK_LENGTH
+    };
+    let output_length = if input.output.is_basic() {
+        0
+    } else {
+        input.output.packed_len()
+    };
+    let storage_deposit_return_length = match storage_deposit_return(&input.output, current_time) {
+        Some(sdr) => Output::Basic(
+            BasicOutputBuilder::new_with_amount(sdr.amount())
+                .add_unlock_condition(AddressUnlockCondition::new(*sdr.return_address()))
+                .finish()?,
+        )
+        .packed_len(),
+        None => 0,
+    };
+
+    Ok(UTXO_INPUT_LENGTH + unlock_length + output_length + storage_deposit_return_length)
+}
+
+// Splits the inputs into batches that fit into a single transaction. Every NFT and alias input and every storage
+// deposit return requires an output, one output is kept for the base coins and the native tokens of the basic inputs,
+// which can't hold more than the max native tokens count. The packed length of the inputs, unlocks and outputs of a
+// batch is limited so the transaction fits into a block.
+fn batch_sweep_inputs(inputs: Vec<InputSigningData>, current_time: u32) -> Result<Vec<Vec<InputSigningData>>> {
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut batch_output_count = 0;
+    let mut batch_length = 0;
+    let mut batch_chains = HashSet::new();
+    let mut batch_native_tokens = NativeTokensBuilder::new();
+
+    for input in inputs {
+        let output_count = usize::from(!input.output.is_basic())
+            + usize::from(storage_deposit_return_amount(&input.output, current_time).is_some());
+        let native_tokens = match &input.output {
+            Output::Basic(basic_output) => Some(basic_output.native_tokens()),
+            _ => None,
+        };
+
+        let mut native_token_count = batch_native_tokens.len();
+        if let Some(native_tokens) = native_tokens {
+            native_token_count = get_new_native_token_count(&batch_native_tokens, native_tokens)?;
+        }
+        // Inputs with the same address are unlocked with a reference to the first signature
+        let mut length = sweep_input_length(&input, !batch_chains.contains(&input.chain), current_time)?
+            + (native_token_count - batch_native_tokens.len()) * NATIVE_TOKEN_LENGTH;
+
+        if !batch.is_empty()
+            && (batch.len() >= INPUT_COUNT_MAX.into()
+                || batch_output_count + output_count >= OUTPUT_COUNT_MAX.into()
+                || native_token_count > NativeTokens::COUNT_MAX.into()
+                || batch_length + length > MAX_SWEEP_TRANSACTION_LENGTH)
+        {
+            batches.push(std::mem::take(&mut batch));
+            batch_output_count = 0;
+            batch_chains.clear();
+            batch_native_tokens = NativeTokensBuilder::new();
+            // The input is the first one of the new batch
+            length = sweep_input_length(&input, true, current_time)?
+                + native_tokens.map_or(0, |native_tokens| native_tokens.len() * NATIVE_TOKEN_LENGTH);
+            batch_length = 0;
+        }
+
+        if let Some(native_tokens) = native_tokens {
+            batch_native_tokens.add_native_tokens(native_tokens.clone())?;
+        }
+        batch_output_count += output_count;
+        batch_length += length;
+        batch_chains.insert(input.chain);
+        batch.push(input);
+    }
+
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    Ok(batches)
+}
+
+// Creates the outputs of a sweep transaction: NFTs and aliases are transitioned to the target address and everything
+// else, minus storage deposit returns which are added by the input selection, is sent in a basic output.
+fn sweep_outputs(
+    inputs: &[InputSigningData],
+    target_address: Address,
+    protocol_parameters: &ProtocolParameters,
+    current_time: u32,
+) -> Result<Vec<Output>> {
+    let rent_structure = *protocol_parameters.rent_structure();
+    let token_supply = protocol_parameters.token_supply();
+
+    let mut outputs = Vec::new();
+    let mut available_amount = 0;
+    let mut required_amount = 0;
+    let mut native_tokens = NativeTokensBuilder::new();
+
+    for input in inputs {
+        available_amount +=
+            input.output.amount() - storage_deposit_return_amount(&input.output, current_time).unwrap_or_default();
+
+        match &input.output {
+            Output::Basic(basic_output) => {
+                native_tokens.add_native_tokens(basic_output.native_tokens().clone())?;
+            }
+            Output::Nft(nft_output) => {
+                let features = nft_output
+                    .features()
+                    .iter()
+                    .filter(|feature| !feature.is_sender())
+                    .cloned();
+                let output = NftOutputBuilder::from(nft_output)
+                    .with_nft_id(nft_output.nft_id_non_null(input.output_id()))
+                    .with_unlock_conditions([AddressUnlockCondition::new(target_address)])
+                    .with_features(features)
+                    .with_minimum_storage_deposit(rent_structure)
+                    .finish_output(token_supply)?;
+                required_amount += output.amount();
+                outputs.push(output);
+            }
+            Output::Alias(alias_output) => {
+                // Governance transition, the amount and the state can't change.
+                let features = alias_output
+                    .features()
+                    .iter()
+                    .filter(|feature| !feature.is_sender())
+                    .cloned();
+                let output = AliasOutputBuilder::from(alias_output)
+                    .with_alias_id(alias_output.alias_id_non_null(input.output_id()))
+                    .replace_unlock_condition(StateControllerAddressUnlockCondition::new(target_address))
+                    .replace_unlock_condition(GovernorAddressUnlockCondition::new(target_address))
+                    .with_features(features)
+                    .finish_output(token_supply)?;
+                required_amount += output.amount();
+                outputs.push(output);
+            }
+            _ => {}
+        }
+    }
+
+    let remaining_amount =
+        available_amount
+            .checked_sub(required_amount)
+            .ok_or(crate::wallet::Error::InsufficientFunds {
+                available: available_amount,
+                required: required_amount,
+            })?;
+    let native_tokens = native_tokens.finish()?;
+
+    if remaining_amount == 0 && native_tokens.is_empty() {
+        return Ok(outputs);
+    }
+
+    let basic_output = BasicOutputBuilder::new_with_amount(remaining_amount)
+        .add_unlock_condition(AddressUnlockCondition::new(target_address))
+        .with_native_tokens(native_tokens.clone())
+        .finish_output(token_supply)?;
+
+    // An amount too small for its own output is added to an NFT output instead, if there is one.
+    if basic_output
+        .verify_storage_deposit(rent_structure, token_supply)
+        .is_err()
+        && native_tokens.is_empty()
+    {
+        if let Some(Output::Nft(nft_output)) = outputs.iter_mut().find(|output| output.is_nft()) {
+            let amount = nft_output.amount() + remaining_amount;
+            *nft_output = NftOutputBuilder::from(&*nft_output).with_amount(amount).finish()?;
+            return Ok(outputs);
+        }
+    }
+
+    basic_output.verify_storage_deposit(rent_structure, token_supply)?;
+    outputs.push(basic_output);
+
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::block::{
+        address::Ed25519Address,
+        output::{
+            feature::{MetadataFeature, SenderFeature},
+            NativeToken, NftId, OutputMetadata,
+        },
+        payload::transaction::TransactionId,
+        BlockId,
+    };
+
+    const TOKEN_SUPPLY: u64 = 1_813_620_509_061_365;
+
+    fn input(index: u16, output: Output) -> InputSigningData {
+        let output_id = OutputId::new(TransactionId::new([1; TransactionId::LENGTH]), index).unwrap();
+
+        InputSigningData {
+            output,
+            output_metadata: OutputMetadata::new(BlockId::new([0; 32]), output_id, false, None, None, None, 0, 0, 0),
+            chain: Some(Bip44::new(4218)),
+        }
+    }
+
+    fn basic_output(native_token: Option<NativeToken>) -> Output {
+        let mut builder = BasicOutputBuilder::new_with_amount(1_000_000)
+            .add_unlock_condition(AddressUnlockCondition::new(Ed25519Address::new([0; 32])));
+        if let Some(native_token) = native_token {
+            builder = builder.add_native_token(native_token);
+        }
+        builder.finish_output(TOKEN_SUPPLY).unwrap()
+    }
+
+    fn batch_lengths(batches: &[Vec<InputSigningData>]) -> Vec<usize> {
+        batches.iter().map(Vec::len).collect()
+    }
+
+    #[test]
+    fn sweep_batches_are_limited_by_input_count() {
+        let inputs = (0..300).map(|index| input(index, basic_output(None))).collect();
+        let batches = batch_sweep_inputs(inputs, 0).unwrap();
+
+        assert_eq!(batch_lengths(&batches), [128, 128, 44]);
+    }
+
+    #[test]
+    fn sweep_batches_are_limited_by_native_token_count() {
+        let inputs = (0..65)
+            .map(|index| {
+                let token_id = TokenId::new([index as u8; TokenId::LENGTH]);
+                input(index, basic_output(Some(NativeToken::new(token_id, 1).unwrap())))
+            })
+            .collect();
+        let batches = batch_sweep_inputs(inputs, 0).unwrap();
+
+        assert_eq!(batch_lengths(&batches), [64, 1]);
+    }
+
+    #[test]
+    fn sweep_batches_are_limited_by_length() {
+        let nft_output = NftOutputBuilder::new_with_amount(1_000_000, NftId::null())
+            .add_unlock_condition(AddressUnlockCondition::new(Ed25519Address::new([0; 32])))
+            .add_feature(MetadataFeature::new(vec![0; *MetadataFeature::LENGTH_RANGE.end() as usize]).unwrap())
+            .finish_output(TOKEN_SUPPLY)
+            .unwrap();
+        let inputs = (0..10).map(|index| input(index, nft_output.clone())).collect();
+        let batches = batch_sweep_inputs(inputs, 0).unwrap();
+
+        // Only three NFTs with the maximum metadata length fit into a transaction
+        assert_eq!(batch_lengths(&batches), [3, 3, 3, 1]);
+        for batch in batches {
+            let length = batch
+                .iter()
+                .enumerate()
+                .map(|(index, input)| sweep_input_length(input, index == 0, 0).unwrap())
+                .sum::<usize>();
+            assert!(length <= MAX_SWEEP_TRANSACTION_LENGTH);
+        }
+    }
+
+    #[test]
+    fn sweep_outputs_transition_nfts_and_merge_the_remaining_amount() {
+        let target_address = Address::Ed25519(Ed25519Address::new([1; 32]));
+        let nft_output = NftOutputBuilder::new_with_amount(1_000_000, NftId::null())
+            .add_unlock_condition(AddressUnlockCondition::new(Ed25519Address::new([0; 32])))
+            .add_feature(SenderFeature::new(Ed25519Address::new([0; 32])))
+            .finish_output(TOKEN_SUPPLY)
+            .unwrap();
+        let inputs = [input(0, nft_output), input(1, basic_output(None))];
+
+        let outputs = sweep_outputs(&inputs, target_address, &ProtocolParameters::default(), 0).unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        let nft_output = outputs[0].as_nft();
+        assert_eq!(nft_output.address(), &target_address);
+        assert_eq!(nft_output.nft_id(), &NftId::from(inputs[0].output_id()));
+        assert!(nft_output.features().sender().is_none());
+        assert_eq!(outputs[1].amount(), 2_000_000 - outputs[0].amount());
+        assert_eq!(
+            outputs[1].unlock_conditions().unwrap().address().unwrap().address(),
+            &target_address
+        );
+    }
+
+    #[test]
+    fn address_scan_continues_while_outputs_are_found() {
+        assert_eq!(next_address_range(0..20, true, true), Some(20..40));
+        assert_eq!(next_address_range(20..40, true, true), Some(40..60));
+    }
+
+    #[test]
+    fn address_scan_stops_after_a_gap() {
+        assert_eq!(next_address_range(0..20, false, true), None);
+        assert_eq!(next_address_range(0..0, false, false), None);
+    }
+
+    #[test]
+    fn address_scan_stops_without_new_addresses() {
+        // A secret manager with a single address returns the same address for every range
+        assert_eq!(next_address_range(20..40, true, false), None);
+    }
+
+    #[test]
+    fn address_scan_stops_at_the_last_index() {
+        assert_eq!(next_address_range(u32::MAX - 10..u32::MAX, true, true), None);
+        assert_eq!(
+            next_address_range(u32::MAX - 20..u32::MAX - 10, true, true),
+            Some(u32::MAX - 10..u32::MAX)
+        );
+    }
+}
@@ -45,6 +45,8 @@ where
             }
         }
 
+        self.wallet.cache_token_metadata(foundries.values()).await?;
+
         let mut account_details = self.details_mut().await;
         account_details.native_token_foundries = foundries;
 
@@ -76,7 +76,7 @@ where
         // Sync transactions after updating account with outputs, so we can use them to check the transaction
         // status
         if options.sync_pending_transactions {
-            let confirmed_tx_with_unknown_output = self.sync_pending_transactions().await?;
+            let confirmed_tx_with_unknown_output = self.sync_pending_transactions(&options).await?;
             // Sync again if we don't know the output yet, to prevent having no unspent outputs after syncing
             if confirmed_tx_with_unknown_output {
                 log::debug!("[SYNC] a transaction for which no output is known got confirmed, syncing outputs again");
@@ -120,6 +120,24 @@ where
             spent_or_unsynced_output_metadata_map.insert(*output_id, Some(output_metadata_response));
         }
 
+        self.finish_sync_internal(
+            addresses_with_unspent_outputs,
+            outputs_data,
+            spent_or_unsynced_output_metadata_map,
+            options,
+        )
+        .await
+    }
+
+    // Request the incoming transactions and native token foundries of the synced outputs if enabled and update the
+    // account with the new data. Shared with the wallet sync, which requests outputs for many accounts at once.
+    pub(crate) async fn finish_sync_internal(
+        &self,
+        addresses_with_unspent_outputs: Vec<AddressWithUnspentOutputs>,
+        outputs_data: Vec<OutputData>,
+        spent_or_unsynced_output_metadata_map: HashMap<OutputId, Option<OutputMetadata>>,
+        options: &SyncOptions,
+    ) -> crate::wallet::Result<()> {
         if options.sync_incoming_transactions {
             let transaction_ids = outputs_data
                 .iter()
@@ -127,6 +145,8 @@ where
                 .collect();
             // Request and store transaction payload for newly received unspent outputs
             self.request_incoming_transaction_data(transaction_ids).await?;
+            // Inputs that were pruned when the transactions were stored can still be available on permanodes
+            self.request_missing_incoming_transaction_inputs(None).await?;
         }
 
         if options.sync_native_token_foundries {
@@ -11,6 +11,7 @@ const DEFAULT_SYNC_INCOMING_TRANSACTIONS: bool = false;
 const DEFAULT_SYNC_ONLY_MOST_BASIC_OUTPUTS: bool = false;
 const DEFAULT_SYNC_PENDING_TRANSACTIONS: bool = true;
 const DEFAULT_SYNC_NATIVE_TOKEN_FOUNDRIES: bool = false;
+const DEFAULT_REISSUE_CONFLICTING_TRANSACTIONS: bool = false;
 
 /// The synchronization options
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -40,6 +41,10 @@ pub struct SyncOptions {
     /// Checks pending transactions and promotes/reattaches them if necessary.
     #[serde(default = "default_sync_pending_transactions")]
     pub sync_pending_transactions: bool,
+    /// Sends the outputs of own transactions that got conflicting again, with other inputs. Only has an effect if
+    /// `sync_pending_transactions` is enabled. Transactions that got cancelled or replaced are not reissued.
+    #[serde(default = "default_reissue_conflicting_transactions")]
+    pub reissue_conflicting_transactions: bool,
     /// Specifies what outputs should be synced for the ed25519 addresses from the account.
     #[serde(default)]
     pub account: AccountSyncOptions,
@@ -82,6 +87,10 @@ fn default_sync_native_token_foundries() -> bool {
     DEFAULT_SYNC_NATIVE_TOKEN_FOUNDRIES
 }
 
+fn default_reissue_conflicting_transactions() -> bool {
+    DEFAULT_REISSUE_CONFLICTING_TRANSACTIONS
+}
+
 impl Default for SyncOptions {
     fn default() -> Self {
         Self {
@@ -90,6 +99,7 @@ impl Default for SyncOptions {
             address_start_index_internal: default_address_start_index(),
             sync_incoming_transactions: default_sync_incoming_transactions(),
             sync_pending_transactions: default_sync_pending_transactions(),
+            reissue_conflicting_transactions: default_reissue_conflicting_transactions(),
             account: AccountSyncOptions::default(),
             alias: AliasSyncOptions::default(),
             nft: NftSyncOptions::default(),
@@ -1,6 +1,8 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+
 use crypto::keys::bip44::Bip44;
 use instant::Instant;
 
@@ -76,6 +78,26 @@ where
     ) -> crate::wallet::Result<Vec<OutputWithMetadata>> {
         log::debug!("[SYNC] start get_outputs");
         let get_outputs_start_time = Instant::now();
+        let (mut outputs, unknown_outputs) = self.get_known_outputs(output_ids).await;
+
+        if !unknown_outputs.is_empty() {
+            outputs.extend(self.client().get_outputs(&unknown_outputs).await?);
+        }
+
+        log::debug!(
+            "[SYNC] finished get_outputs in {:.2?}",
+            get_outputs_start_time.elapsed()
+        );
+
+        Ok(outputs)
+    }
+
+    /// Splits output ids into the outputs already known by the account, which are set as unspent again, and the ids of
+    /// outputs that still need to be requested from the node
+    pub(crate) async fn get_known_outputs(
+        &self,
+        output_ids: Vec<OutputId>,
+    ) -> (Vec<OutputWithMetadata>, Vec<OutputId>) {
         let mut outputs = Vec::new();
         let mut unknown_outputs = Vec::new();
         let mut unspent_outputs = Vec::new();
@@ -101,18 +123,7 @@ where
             account_details.unspent_outputs.insert(output_id, output_data);
         }
 
-        drop(account_details);
-
-        if !unknown_outputs.is_empty() {
-            outputs.extend(self.client().get_outputs(&unknown_outputs).await?);
-        }
-
-        log::debug!(
-            "[SYNC] finished get_outputs in {:.2?}",
-            get_outputs_start_time.elapsed()
-        );
-
-        Ok(outputs)
+        (outputs, unknown_outputs)
     }
 
     // Try to get transactions and inputs for received outputs
@@ -195,6 +206,84 @@ where
 
         Ok(())
     }
+
+    // Request the inputs of incoming transactions that were already pruned by the nodes when the transactions were
+    // stored, from the permanodes. Only the given transactions are completed, if provided. Returns whether inputs were
+    // found.
+    pub(crate) async fn request_missing_incoming_transaction_inputs(
+        &self,
+        transaction_ids: Option<&HashSet<TransactionId>>,
+    ) -> crate::wallet::Result<bool> {
+        if !self.client().has_permanodes().await {
+            return Ok(false);
+        }
+        log::debug!("[SYNC] request_missing_incoming_transaction_inputs");
+
+        let account_details = self.details().await;
+        let incomplete_transactions = account_details
+            .incoming_transactions
+            .values()
+            .filter(|transaction| {
+                let TransactionEssence::Regular(essence) = transaction.payload.essence();
+                transaction.inputs.len() < essence.inputs().len()
+                    && transaction_ids.map_or(true, |ids| ids.contains(&transaction.transaction_id))
+                    && !account_details
+                        .inaccessible_incoming_transactions
+                        .contains(&transaction.transaction_id)
+            })
+            .map(|transaction| {
+                (
+                    transaction.transaction_id,
+                    transaction.payload.clone(),
+                    transaction.inputs.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+        drop(account_details);
+
+        let mut found_inputs = false;
+        for (transaction_id, transaction_payload, known_inputs) in incomplete_transactions {
+            let inputs_with_meta =
+                match get_unpruned_inputs_for_transaction_payload(self.client(), &transaction_payload).await {
+                    Ok(inputs_with_meta) if inputs_with_meta.len() > known_inputs => inputs_with_meta,
+                    Ok(_) => {
+                        log::debug!("[SYNC] adding {transaction_id} to inaccessible_incoming_transactions");
+                        // The missing inputs were pruned on all nodes, avoid requesting them again on every sync, until
+                        // new client options are provided
+                        self.details_mut()
+                            .await
+                            .inaccessible_incoming_transactions
+                            .insert(transaction_id);
+                        continue;
+                    }
+                    Err(e) => {
+                        // Requested again on the next sync
+                        log::warn!("[SYNC] couldn't request the inputs of {transaction_id}: {e}");
+                        continue;
+                    }
+                };
+            log::debug!(
+                "[SYNC] found {} inputs of {transaction_id}",
+                inputs_with_meta.len() - known_inputs
+            );
+
+            let transaction = build_transaction_from_payload_and_inputs(
+                transaction_id,
+                transaction_payload,
+                inputs_with_meta
+                    .into_iter()
+                    .map(OutputWithMetadataResponse::from)
+                    .collect(),
+            )?;
+            self.details_mut()
+                .await
+                .incoming_transactions
+                .insert(transaction_id, transaction);
+            found_inputs = true;
+        }
+
+        Ok(found_inputs)
+    }
 }
 
 // Try to fetch the inputs of the transaction
@@ -202,9 +291,36 @@ pub(crate) async fn get_inputs_for_transaction_payload(
     client: &Client,
     transaction_payload: &TransactionPayload,
 ) -> crate::wallet::Result<Vec<OutputWithMetadata>> {
+    client
+        .get_outputs_ignore_errors(&input_output_ids(transaction_payload))
+        .await
+        .map_err(|e| e.into())
+}
+
+// Fetches the inputs of the transaction that weren't pruned on all nodes, other errors are returned since the inputs
+// could be found with another request.
+async fn get_unpruned_inputs_for_transaction_payload(
+    client: &Client,
+    transaction_payload: &TransactionPayload,
+) -> crate::wallet::Result<Vec<OutputWithMetadata>> {
+    let output_ids = input_output_ids(transaction_payload);
+    let mut inputs = Vec::new();
+
+    for result in futures::future::join_all(output_ids.iter().map(|output_id| client.get_output(output_id))).await {
+        match result {
+            Ok(input) => inputs.push(input),
+            Err(crate::client::Error::Node(crate::client::node_api::error::Error::NotFound(_))) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(inputs)
+}
+
+fn input_output_ids(transaction_payload: &TransactionPayload) -> Vec<OutputId> {
     let TransactionEssence::Regular(essence) = transaction_payload.essence();
 
-    let output_ids = essence
+    essence
         .inputs()
         .iter()
         .filter_map(|input| {
@@ -214,10 +330,5 @@ pub(crate) async fn get_inputs_for_transaction_payload(
                 None
             }
         })
-        .collect::<Vec<_>>();
-
-    client
-        .get_outputs_ignore_errors(&output_ids)
-        .await
-        .map_err(|e| e.into())
+        .collect()
 }
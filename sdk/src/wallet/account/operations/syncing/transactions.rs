@@ -1,16 +1,28 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+
 use crate::{
     client::secret::SecretManage,
     types::{
         api::core::response::LedgerInclusionState,
-        block::{input::Input, output::OutputId, payload::transaction::TransactionEssence, BlockId},
+        block::{
+            address::Address,
+            input::Input,
+            output::{Output, OutputId},
+            payload::{
+                transaction::{TransactionEssence, TransactionId},
+                Payload,
+            },
+            BlockId,
+        },
     },
     utils::unix_timestamp_now,
     wallet::account::{
+        operations::syncing::SyncOptions,
         types::{InclusionState, Transaction},
-        Account, AccountDetails,
+        Account, AccountDetails, TransactionOptions,
     },
 };
 
@@ -27,7 +39,9 @@ where
     /// output ids that don't need to be locked anymore
     /// Return true if a transaction got confirmed for which we don't have an output already, based on this outputs will
     /// be synced again
-    pub(crate) async fn sync_pending_transactions(&self) -> crate::wallet::Result<bool> {
+    /// If `reissue_conflicting_transactions` is set in the options, the outputs of transactions that got conflicting
+    /// are sent again with other inputs
+    pub(crate) async fn sync_pending_transactions(&self, options: &SyncOptions) -> crate::wallet::Result<bool> {
         log::debug!("[SYNC] sync pending transactions");
         let account_details = self.details().await;
 
@@ -36,6 +50,10 @@ where
         let mut confirmed_unknown_output = false;
 
         if account_details.pending_transactions.is_empty() {
+            drop(account_details);
+            if options.reissue_conflicting_transactions {
+                self.reissue_conflicting_transactions().await?;
+            }
             return Ok(confirmed_unknown_output);
         }
 
@@ -199,6 +217,71 @@ where
                 }
             }
         }
+        // Transactions that spend an input of a confirmed transaction, because one of them cancels or replaces the
+        // other, can't get confirmed anymore
+        let confirmed_input_ids = updated_transactions
+            .iter()
+            .filter(|transaction| transaction.inclusion_state == InclusionState::Confirmed)
+            .flat_map(input_ids)
+            .collect::<HashSet<_>>();
+
+        if !confirmed_input_ids.is_empty() {
+            let spends_confirmed_input = |transaction: &Transaction| {
+                input_ids(transaction).any(|input_id| confirmed_input_ids.contains(&input_id))
+            };
+
+            for transaction in updated_transactions
+                .iter_mut()
+                .filter(|transaction| transaction.inclusion_state != InclusionState::Confirmed)
+            {
+                if spends_confirmed_input(transaction) {
+                    log::debug!("[SYNC] conflicting transaction {}", transaction.transaction_id);
+                    transaction.inclusion_state = InclusionState::Conflicting;
+                }
+            }
+
+            let updated_transaction_ids = updated_transactions
+                .iter()
+                .map(|transaction| transaction.transaction_id)
+                .collect::<HashSet<_>>();
+            transactions_to_reattach.retain(|transaction| !spends_confirmed_input(transaction));
+
+            for transaction_id in account_details
+                .pending_transactions
+                .iter()
+                .filter(|transaction_id| !updated_transaction_ids.contains(transaction_id))
+            {
+                if let Some(transaction) = account_details.transactions.get(transaction_id) {
+                    if transaction.network_id == network_id && spends_confirmed_input(transaction) {
+                        log::debug!("[SYNC] conflicting transaction {transaction_id}");
+                        // Inputs that weren't spent by the confirmed transaction are available again
+                        output_ids_to_unlock
+                            .extend(input_ids(transaction).filter(|input_id| !confirmed_input_ids.contains(input_id)));
+                        let mut transaction = transaction.clone();
+                        transaction.inclusion_state = InclusionState::Conflicting;
+                        updated_transactions.push(transaction);
+                    }
+                }
+            }
+        }
+
+        // Transactions that lost against a foreign transaction, not against one of our own cancelling or replacing
+        // transactions
+        let transactions_to_reissue = if options.reissue_conflicting_transactions {
+            updated_transactions
+                .iter()
+                .filter(|transaction| {
+                    transaction.inclusion_state == InclusionState::Conflicting
+                        && !transaction.incoming
+                        && !input_ids(transaction).any(|input_id| confirmed_input_ids.contains(&input_id))
+                        && !is_replaced(&account_details, transaction)
+                })
+                .map(|transaction| transaction.transaction_id)
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
         drop(account_details);
 
         for mut transaction in transactions_to_reattach {
@@ -208,16 +291,218 @@ where
             updated_transactions.push(transaction);
         }
 
+        // Stored with the account, so a reissue that fails is tried again on the next sync
+        self.details_mut()
+            .await
+            .transactions_to_reissue
+            .extend(transactions_to_reissue);
+
         // updates account with balances, output ids, outputs
         self.update_account_with_transactions(updated_transactions, spent_output_ids, output_ids_to_unlock)
             .await?;
 
+        if options.reissue_conflicting_transactions {
+            self.reissue_conflicting_transactions().await?;
+        }
+
         Ok(confirmed_unknown_output)
     }
+
+    // Reissues the conflicting transactions that still need to be reissued. A failed reissue doesn't fail the whole
+    // syncing, the transaction is kept and tried again on the next sync.
+    async fn reissue_conflicting_transactions(&self) -> crate::wallet::Result<()> {
+        let transactions_to_reissue = {
+            let mut account_details = self.details_mut().await;
+            let transaction_ids = account_details
+                .transactions_to_reissue
+                .iter()
+                .copied()
+                .collect::<Vec<_>>();
+            let mut transactions_to_reissue = Vec::new();
+
+            for transaction_id in transaction_ids {
+                let reissue = account_details
+                    .transactions
+                    .get(&transaction_id)
+                    .filter(|transaction| !is_replaced(&account_details, transaction))
+                    .and_then(|transaction| {
+                        reissuable_outputs(&account_details, transaction)
+                            .map(|outputs| (outputs, reissue_options(transaction)))
+                    });
+
+                match reissue {
+                    Some((outputs, options)) => transactions_to_reissue.push((transaction_id, outputs, options)),
+                    // Already reissued or replaced, or it can't be reissued
+                    None => {
+                        account_details.transactions_to_reissue.remove(&transaction_id);
+                    }
+                }
+            }
+
+            transactions_to_reissue
+        };
+
+        if transactions_to_reissue.is_empty() {
+            return Ok(());
+        }
+
+        let mut reissued_transaction_ids = Vec::new();
+        for (transaction_id, outputs, options) in transactions_to_reissue {
+            match self.reissue_transaction(&transaction_id, outputs, options).await {
+                Ok(()) => reissued_transaction_ids.push(transaction_id),
+                Err(err) => log::warn!(
+                    "[SYNC] couldn't reissue conflicting transaction {transaction_id}, trying again on the next sync: {err}"
+                ),
+            }
+        }
+
+        let mut account_details = self.details_mut().await;
+        for transaction_id in &reissued_transaction_ids {
+            account_details.transactions_to_reissue.remove(transaction_id);
+        }
+        #[cfg(feature = "storage")]
+        {
+            log::debug!(
+                "[SYNC] storing account {} with reissued transactions",
+                account_details.index()
+            );
+            self.save(Some(&account_details)).await?;
+        }
+
+        Ok(())
+    }
+
+    // Sends the outputs of a conflicting transaction again with other inputs and links the new transaction to it
+    async fn reissue_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        outputs: Vec<Output>,
+        options: TransactionOptions,
+    ) -> crate::wallet::Result<()> {
+        log::debug!("[SYNC] reissue conflicting transaction {transaction_id}");
+        let prepared_transaction = self.prepare_transaction(outputs, options.clone()).await?;
+        let transaction = self
+            .sign_and_submit_replacing_transaction(transaction_id, prepared_transaction, Some(options))
+            .await?;
+        log::debug!(
+            "[SYNC] reissued conflicting transaction {transaction_id} as {}",
+            transaction.transaction_id
+        );
+
+        #[cfg(feature = "events")]
+        {
+            let account_index = self.details().await.index;
+            self.emit(
+                account_index,
+                crate::wallet::events::types::WalletEvent::TransactionReissue(
+                    crate::wallet::events::types::TransactionReissueEvent {
+                        transaction_id: *transaction_id,
+                        reissued_transaction_id: transaction.transaction_id,
+                    },
+                ),
+            )
+            .await;
+        }
+        Ok(())
+    }
+}
+
+// Returns the output ids of the inputs of a transaction
+fn input_ids(transaction: &Transaction) -> impl Iterator<Item = OutputId> + '_ {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    essence.inputs().iter().filter_map(|input| match input {
+        Input::Utxo(input) => Some(*input.output_id()),
+        Input::Treasury(_) => None,
+    })
+}
+
+// Returns true if another transaction of the account cancels or replaces this one, or if this transaction replaced
+// one that got confirmed
+fn is_replaced(account: &AccountDetails, transaction: &Transaction) -> bool {
+    account.transactions.values().any(|other| {
+        other.replaced_transaction_id == Some(transaction.transaction_id)
+            || (transaction.replaced_transaction_id == Some(other.transaction_id)
+                && other.inclusion_state == InclusionState::Confirmed)
+    })
+}
+
+// Returns the outputs of a transaction that were sent to other addresses, without remainder and storage deposit return
+// outputs, those are created again by the input selection. Returns None if the transaction transitioned chain
+// outputs, as they can't be created again without the same inputs.
+fn reissuable_outputs(account: &AccountDetails, transaction: &Transaction) -> Option<Vec<Output>> {
+    let account_addresses = account
+        .public_addresses
+        .iter()
+        .chain(account.internal_addresses.iter())
+        .map(|address| *address.address.inner())
+        .collect::<HashSet<Address>>();
+
+    let mut storage_deposit_returns = input_ids(transaction)
+        .filter_map(|input_id| account.outputs.get(&input_id))
+        .filter_map(|output_data| {
+            output_data
+                .output
+                .unlock_conditions()
+                .and_then(|unlock_conditions| unlock_conditions.storage_deposit_return())
+                .map(|sdr| (*sdr.return_address(), sdr.amount()))
+        })
+        .collect::<Vec<_>>();
+
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    let mut outputs = Vec::new();
+
+    for output in essence.outputs() {
+        let basic_output = match output {
+            Output::Basic(basic_output) => basic_output,
+            _ => {
+                log::debug!(
+                    "[SYNC] can't reissue transaction {} with chain outputs",
+                    transaction.transaction_id
+                );
+                return None;
+            }
+        };
+        let address = basic_output.address();
+
+        if account_addresses.contains(address) {
+            continue;
+        }
+
+        if basic_output.unlock_conditions().len() == 1
+            && basic_output.native_tokens().is_empty()
+            && basic_output.features().is_empty()
+        {
+            if let Some(index) = storage_deposit_returns
+                .iter()
+                .position(|(return_address, amount)| return_address == address && *amount == basic_output.amount())
+            {
+                storage_deposit_returns.swap_remove(index);
+                continue;
+            }
+        }
+
+        outputs.push(output.clone());
+    }
+
+    (!outputs.is_empty()).then_some(outputs)
+}
+
+// Keeps the note and tagged data of the conflicting transaction
+fn reissue_options(transaction: &Transaction) -> TransactionOptions {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+
+    TransactionOptions {
+        tagged_data_payload: match essence.payload() {
+            Some(Payload::TaggedData(tagged_data_payload)) => Some(*tagged_data_payload.clone()),
+            _ => None,
+        },
+        note: transaction.note.clone(),
+        ..Default::default()
+    }
 }
 
 // Set the outputs as spent so they will not be used as input again
-fn updated_transaction_and_outputs(
+pub(crate) fn updated_transaction_and_outputs(
     mut transaction: Transaction,
     block_id: Option<BlockId>,
     inclusion_state: InclusionState,
@@ -0,0 +1,508 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashSet, path::Path};
+
+use getset::{CopyGetters, Getters};
+use packable::PackableExt;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{api::InclusionOutcome, secret::SecretManage},
+    types::block::{
+        address::Bech32Address,
+        output::{
+            unlock_condition::{
+                AddressUnlockCondition, ExpirationUnlockCondition, StorageDepositReturnUnlockCondition,
+            },
+            BasicOutputBuilder, MinimumStorageDepositBasicOutput, NativeToken, NativeTokens, Output, TokenId,
+        },
+        payload::transaction::TransactionId,
+        ConvertTo,
+    },
+    wallet::{
+        account::{
+            constants::MAX_AIRDROP_RECIPIENTS_PER_TRANSACTION, operations::transaction::batch_by_output_length,
+            types::InclusionState, Account, TransactionOptions,
+        },
+        Error, Result,
+    },
+};
+
+/// A recipient of an airdrop and the amount of native tokens it receives.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct AirdropRecipient {
+    /// Bech32 encoded address
+    #[getset(get = "pub")]
+    address: Bech32Address,
+    /// Amount of native tokens
+    #[getset(get_copy = "pub")]
+    amount: U256,
+}
+
+impl AirdropRecipient {
+    /// Creates a new instance of [`AirdropRecipient`]
+    pub fn new(address: impl ConvertTo<Bech32Address>, amount: impl Into<U256>) -> Result<Self> {
+        Ok(Self {
+            address: address.convert()?,
+            amount: amount.into(),
+        })
+    }
+
+    /// Reads recipients from a CSV file with an address and an amount per line. Amounts are decimal or, prefixed
+    /// with `0x`, hexadecimal. Empty lines, lines starting with `#` and a header line are skipped.
+    pub fn from_csv_file(path: impl AsRef<Path>) -> Result<Vec<Self>> {
+        let content = std::fs::read_to_string(path)?;
+        let mut recipients = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (address, amount) = line
+                .split_once(',')
+                .ok_or_else(|| Error::AirdropFailed(format!("missing amount in line {}", index + 1)))?;
+            let (address, amount) = (address.trim(), amount.trim());
+
+            let amount = match amount.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                None => U256::from_dec_str(amount).ok(),
+            };
+            let address = Bech32Address::try_from_str(address).ok();
+
+            match (address, amount) {
+                (Some(address), Some(amount)) => recipients.push(Self { address, amount }),
+                // The first line can be a header
+                _ if recipients.is_empty() && index == 0 => {}
+                _ => {
+                    return Err(Error::AirdropFailed(format!(
+                        "invalid address or amount in line {}",
+                        index + 1
+                    )));
+                }
+            }
+        }
+
+        Ok(recipients)
+    }
+
+    /// Reads recipients from a JSON file with an array of recipients.
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Vec<Self>> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+}
+
+/// Params for `airdrop_native_tokens()`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[serde(rename_all = "camelCase")]
+#[getset(get = "pub")]
+pub struct AirdropParams {
+    /// The native token to airdrop
+    token_id: TokenId,
+    /// The recipients
+    recipients: Vec<AirdropRecipient>,
+    /// Expiration in seconds, after which unclaimed outputs return to the return address with their storage deposit.
+    /// If not set, the recipients keep the storage deposit.
+    expiration: Option<u32>,
+    /// Bech32 encoded address to which the storage deposit and unclaimed native tokens return. Default will use the
+    /// first address of the account
+    return_address: Option<Bech32Address>,
+}
+
+impl AirdropParams {
+    /// Creates a new instance of [`AirdropParams`]
+    pub fn new(token_id: TokenId, recipients: impl IntoIterator<Item = AirdropRecipient>) -> Self {
+        Self {
+            token_id,
+            recipients: recipients.into_iter().collect(),
+            expiration: None,
+            return_address: None,
+        }
+    }
+
+    /// Set the expiration in seconds
+    pub fn with_expiration(mut self, expiration_secs: impl Into<Option<u32>>) -> Self {
+        self.expiration = expiration_secs.into();
+        self
+    }
+
+    /// Set the return address
+    pub fn with_return_address(mut self, return_address: impl Into<Option<Bech32Address>>) -> Self {
+        self.return_address = return_address.into();
+        self
+    }
+}
+
+/// The state of an airdrop to a recipient, part of an [`AirdropReport`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct AirdropRecipientReport {
+    /// The recipient
+    #[getset(get = "pub")]
+    #[serde(flatten)]
+    recipient: AirdropRecipient,
+    /// The storage deposit of the output sent to the recipient
+    #[getset(get_copy = "pub")]
+    #[serde(with = "crate::utils::serde::string")]
+    storage_deposit: u64,
+    /// The transaction sending the native tokens, if it was sent
+    #[getset(get_copy = "pub")]
+    transaction_id: Option<TransactionId>,
+    /// Whether the transaction got included
+    #[getset(get_copy = "pub")]
+    included: bool,
+}
+
+/// The report of an airdrop, written after every transaction so an interrupted airdrop can be resumed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct AirdropReport {
+    /// The airdropped native token
+    #[getset(get_copy = "pub")]
+    token_id: TokenId,
+    /// The state of the airdrop per recipient
+    #[getset(get = "pub")]
+    recipients: Vec<AirdropRecipientReport>,
+}
+
+impl AirdropReport {
+    /// Returns whether the native tokens were sent to all recipients.
+    pub fn is_finished(&self) -> bool {
+        self.recipients.iter().all(|recipient| recipient.included)
+    }
+
+    /// Returns the storage deposit still needed for the recipients that didn't receive their native tokens yet.
+    pub fn pending_storage_deposit(&self) -> u64 {
+        self.recipients
+            .iter()
+            .filter(|recipient| recipient.transaction_id.is_none())
+            .map(|recipient| recipient.storage_deposit)
+            .sum()
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Airdrops native tokens to many recipients, each one receiving a basic output with the minimum storage
+    /// deposit. If an expiration is set, the outputs get a [`StorageDepositReturnUnlockCondition`] and an
+    /// [`ExpirationUnlockCondition`], so the storage deposit is returned and unclaimed outputs return after the
+    /// expiration.
+    ///
+    /// The recipients are split into as many transactions as needed to stay within the output count and the length
+    /// limits of a transaction, each one is awaited to be included before the next one is sent. The [`AirdropReport`] is written to `report_path` after every transaction; if the file
+    /// already exists, the airdrop is resumed from it and recipients that already received their tokens are skipped.
+    /// ```ignore
+    /// let recipients = AirdropRecipient::from_csv_file("./recipients.csv")?;
+    /// let params = AirdropParams::new(token_id, recipients).with_expiration(86400 * 30);
+    ///
+    /// let report = account.airdrop_native_tokens(params, "./airdrop-report.json", None).await?;
+    /// println!("Airdrop finished: {}", report.is_finished());
+    /// ```
+    pub async fn airdrop_native_tokens(
+        &self,
+        params: AirdropParams,
+        report_path: impl AsRef<Path> + Send,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> Result<AirdropReport> {
+        log::debug!("[TRANSACTION] airdrop_native_tokens");
+        let report_path = report_path.as_ref();
+        let options = options.into();
+        let token_supply = self.client().get_token_supply().await?;
+
+        let return_address = match params.return_address {
+            Some(return_address) => return_address,
+            None => {
+                self.addresses()
+                    .await?
+                    .first()
+                    .ok_or(Error::FailedToGetRemainder)?
+                    .address
+            }
+        };
+        self.client().bech32_hrp_matches(return_address.hrp()).await?;
+        for recipient in &params.recipients {
+            self.client().bech32_hrp_matches(recipient.address.hrp()).await?;
+        }
+
+        let mut report = if report_path.exists() {
+            let report: AirdropReport = serde_json::from_slice(&std::fs::read(report_path)?)?;
+            if report.token_id != params.token_id
+                || !report
+                    .recipients
+                    .iter()
+                    .map(|recipient| &recipient.recipient)
+                    .eq(params.recipients.iter())
+            {
+                return Err(Error::AirdropFailed(format!(
+                    "report {} belongs to another airdrop",
+                    report_path.display()
+                )));
+            }
+            self.resume_airdrop(report).await?
+        } else {
+            self.new_airdrop_report(&params).await?
+        };
+        report.write(report_path)?;
+
+        for chunk in airdrop_batches(&report, &params, return_address, token_supply)? {
+            let expiration_time = match params.expiration {
+                Some(expiration) => Some(self.client().get_time_checked().await? + expiration),
+                None => None,
+            };
+
+            let outputs = chunk
+                .iter()
+                .map(|index| {
+                    let recipient = &report.recipients[*index];
+                    airdrop_output(
+                        recipient,
+                        params.token_id,
+                        return_address,
+                        expiration_time,
+                        token_supply,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let transaction = self.send_outputs(outputs, options.clone()).await?;
+            for index in &chunk {
+                report.recipients[*index].transaction_id = Some(transaction.transaction_id);
+            }
+            report.write(report_path)?;
+
+            // The remainder of the transaction is needed as input for the next one
+            self.retry_transaction_until_included(&transaction.transaction_id, None, None)
+                .await?;
+            self.sync(None).await?;
+            for index in &chunk {
+                report.recipients[*index].included = true;
+            }
+            report.write(report_path)?;
+        }
+
+        Ok(report)
+    }
+
+    // Creates the report of a new airdrop with the storage deposit for every recipient.
+    async fn new_airdrop_report(&self, params: &AirdropParams) -> Result<AirdropReport> {
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+
+        let recipients = params
+            .recipients
+            .iter()
+            .map(|recipient| {
+                let native_tokens = NativeTokens::from_vec(vec![NativeToken::new(params.token_id, recipient.amount)?])?;
+                let mut minimum_storage_deposit = MinimumStorageDepositBasicOutput::new(rent_structure, token_supply)
+                    .with_native_tokens(native_tokens);
+                if params.expiration.is_some() {
+                    minimum_storage_deposit = minimum_storage_deposit
+                        .with_storage_deposit_return()?
+                        .with_expiration()?;
+                }
+
+                Ok(AirdropRecipientReport {
+                    recipient: recipient.clone(),
+                    storage_deposit: minimum_storage_deposit.finish()?,
+                    transaction_id: None,
+                    included: false,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(AirdropReport {
+            token_id: params.token_id,
+            recipients,
+        })
+    }
+
+    // Checks the transactions of an interrupted airdrop. Recipients of transactions that are conflicting or unknown to
+    // the node are sent their tokens again, if the state of a transaction can't be determined an error is returned so
+    // no recipient is paid twice.
+    async fn resume_airdrop(&self, mut report: AirdropReport) -> Result<AirdropReport> {
+        let transaction_ids = report
+            .recipients
+            .iter()
+            .filter(|recipient| !recipient.included)
+            .filter_map(|recipient| recipient.transaction_id)
+            .collect::<HashSet<_>>();
+
+        for transaction_id in transaction_ids {
+            let included = match self.get_transaction(&transaction_id).await {
+                Some(transaction) => match transaction.inclusion_state {
+                    InclusionState::Confirmed => true,
+                    InclusionState::Conflicting => false,
+                    InclusionState::Pending => match self.await_transaction_inclusion(&transaction_id, None).await? {
+                        InclusionOutcome::Included { .. } => true,
+                        InclusionOutcome::Conflicting { .. } => false,
+                        InclusionOutcome::TimedOut { .. } => {
+                            return Err(Error::AirdropFailed(format!(
+                                "transaction {transaction_id} isn't included yet, the airdrop can be resumed later"
+                            )));
+                        }
+                    },
+                    InclusionState::UnknownPruned => self.has_included_block(&transaction_id).await?,
+                },
+                None => self.has_included_block(&transaction_id).await?,
+            };
+            log::debug!("[TRANSACTION] airdrop transaction {transaction_id} included: {included}");
+
+            for recipient in report
+                .recipients
+                .iter_mut()
+                .filter(|recipient| recipient.transaction_id == Some(transaction_id))
+            {
+                if included {
+                    recipient.included = true;
+                } else {
+                    recipient.transaction_id = None;
+                }
+            }
+        }
+        self.sync(None).await?;
+
+        Ok(report)
+    }
+
+    // Returns whether the node knows a block including the transaction, any error other than not found is returned
+    // since the transaction could still be included.
+    async fn has_included_block(&self, transaction_id: &TransactionId) -> Result<bool> {
+        match self.client().get_included_block(transaction_id).await {
+            Ok(_) => Ok(true),
+            Err(crate::client::Error::Node(crate::client::node_api::error::Error::NotFound(_))) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+// Splits the indexes of the recipients that didn't receive their native tokens yet into batches that fit into a
+// transaction, limited by the output count and the length of the outputs.
+fn airdrop_batches(
+    report: &AirdropReport,
+    params: &AirdropParams,
+    return_address: Bech32Address,
+    token_supply: u64,
+) -> Result<Vec<Vec<usize>>> {
+    // The expiration time is only known when a batch is sent, but it doesn't change the length of the output
+    let expiration_time = params.expiration.map(|_| u32::MAX);
+    let pending = report
+        .recipients
+        .iter()
+        .enumerate()
+        .filter(|(_, recipient)| recipient.transaction_id.is_none())
+        .map(|(index, recipient)| {
+            let output = airdrop_output(
+                recipient,
+                params.token_id,
+                return_address,
+                expiration_time,
+                token_supply,
+            )?;
+            Ok((output.packed_len(), index))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(batch_by_output_length(
+        pending,
+        MAX_AIRDROP_RECIPIENTS_PER_TRANSACTION,
+        0,
+    ))
+}
+
+// Builds the output sending the native tokens to a recipient of an airdrop.
+fn airdrop_output(
+    recipient: &AirdropRecipientReport,
+    token_id: TokenId,
+    return_address: Bech32Address,
+    expiration_time: Option<u32>,
+    token_supply: u64,
+) -> Result<Output> {
+    let mut builder = BasicOutputBuilder::new_with_amount(recipient.storage_deposit)
+        .add_native_token(NativeToken::new(token_id, recipient.recipient.amount)?)
+        .add_unlock_condition(AddressUnlockCondition::new(recipient.recipient.address));
+
+    if let Some(expiration_time) = expiration_time {
+        builder = builder
+            // The full storage deposit is returned, so only the native tokens are sent
+            .add_unlock_condition(StorageDepositReturnUnlockCondition::new(
+                return_address,
+                recipient.storage_deposit,
+                token_supply,
+            )?)
+            .add_unlock_condition(ExpirationUnlockCondition::new(return_address, expiration_time)?);
+    }
+
+    Ok(builder.finish_output(token_supply)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::block::address::{Ed25519Address, Hrp},
+        wallet::account::constants::MAX_OUTPUTS_LENGTH_PER_TRANSACTION,
+    };
+
+    const TOKEN_SUPPLY: u64 = 1_813_620_509_061_365;
+
+    #[test]
+    fn airdrop_batches_are_limited_by_output_length() {
+        let hrp = Hrp::from_str_unchecked("rms");
+        let return_address = Bech32Address::new(hrp, Ed25519Address::new([0; 32]));
+        let recipients = (0..1000u32)
+            .map(|index| {
+                let mut address = [0; 32];
+                address[..4].copy_from_slice(&index.to_be_bytes());
+                AirdropRecipient::new(Bech32Address::new(hrp, Ed25519Address::new(address)), U256::MAX).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let params = AirdropParams::new(TokenId::new([1; TokenId::LENGTH]), recipients).with_expiration(86400);
+        let mut report = AirdropReport {
+            token_id: params.token_id,
+            recipients: params
+                .recipients
+                .iter()
+                .map(|recipient| AirdropRecipientReport {
+                    recipient: recipient.clone(),
+                    storage_deposit: 100_000,
+                    transaction_id: None,
+                    included: false,
+                })
+                .collect(),
+        };
+        // Recipients that already received their native tokens are skipped
+        report.recipients[0].transaction_id = Some(TransactionId::new([0; TransactionId::LENGTH]));
+
+        let batches = airdrop_batches(&report, &params, return_address, TOKEN_SUPPLY).unwrap();
+
+        assert_eq!(batches.concat(), (1..1000).collect::<Vec<_>>());
+        for batch in batches {
+            assert!(batch.len() <= MAX_AIRDROP_RECIPIENTS_PER_TRANSACTION);
+            let outputs_length = batch
+                .iter()
+                .map(|index| {
+                    airdrop_output(
+                        &report.recipients[*index],
+                        params.token_id,
+                        return_address,
+                        Some(u32::MAX),
+                        TOKEN_SUPPLY,
+                    )
+                    .unwrap()
+                    .packed_len()
+                })
+                .sum::<usize>();
+            assert!(outputs_length <= MAX_OUTPUTS_LENGTH_PER_TRANSACTION);
+        }
+    }
+}
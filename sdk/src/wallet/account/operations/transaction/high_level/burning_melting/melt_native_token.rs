@@ -85,7 +85,7 @@ where
     }
 
     /// Find and return unspent `OutputData` for given `alias_id` and `foundry_id`
-    async fn find_alias_and_foundry_output_data(
+    pub(crate) async fn find_alias_and_foundry_output_data(
         &self,
         alias_id: AliasId,
         foundry_id: FoundryId,
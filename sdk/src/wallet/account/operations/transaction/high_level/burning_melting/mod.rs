@@ -10,6 +10,7 @@ use crate::{
 };
 
 pub(crate) mod melt_native_token;
+pub(crate) mod retire_native_token;
 
 impl Account {
     /// A generic `burn()` function that can be used to burn native tokens, nfts, foundries and aliases.
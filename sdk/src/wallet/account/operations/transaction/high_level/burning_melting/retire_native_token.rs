@@ -0,0 +1,168 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use primitive_types::U256;
+
+use crate::{
+    client::secret::SecretManage,
+    types::block::output::{AliasOutputBuilder, FoundryId, Output, OutputId, TokenId, TokenScheme},
+    wallet::{
+        account::{
+            operations::{helpers::time::can_output_be_unlocked_forever_from_now_on, transaction::Transaction},
+            Account, TransactionOptions,
+        },
+        Error,
+    },
+};
+
+/// The result of retiring a native token with
+/// [Account::retire_native_token()](crate::wallet::Account::retire_native_token)
+#[derive(Debug)]
+pub struct RetireNativeTokenTransaction {
+    /// The transaction melting the tokens held by the account
+    pub transaction: Transaction,
+    /// Whether the foundry was destroyed and its storage deposit released to the controlling alias
+    pub foundry_destroyed: bool,
+    /// The circulating supply left after the transaction. The foundry can only be destroyed once it's zero, so if it
+    /// isn't, the remaining tokens are held by other addresses or in outputs the account can't spend at the moment.
+    pub remaining_circulating_supply: U256,
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Retires a native token by melting the whole circulating supply the account can spend.
+    ///
+    /// If the account holds the whole circulating supply, the tokens are melted by destroying the foundry, whose
+    /// storage deposit is released to the controlling alias. Otherwise the held tokens are melted with the foundry and
+    /// the remaining circulating supply is returned, which prevents destroying the foundry.
+    /// ```ignore
+    /// let retired = account.retire_native_token(token_id, None).await?;
+    /// if !retired.foundry_destroyed {
+    ///     println!("Foundry kept, {} tokens are still circulating", retired.remaining_circulating_supply);
+    /// }
+    /// ```
+    pub async fn retire_native_token(
+        &self,
+        token_id: TokenId,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<RetireNativeTokenTransaction> {
+        log::debug!("[TRANSACTION] retire_native_token");
+        let options = options.into();
+        let foundry_id = FoundryId::from(token_id);
+        let alias_id = *foundry_id.alias_address().alias_id();
+        let token_supply = self.client().get_token_supply().await?;
+
+        let (alias_data, foundry_data) = self.find_alias_and_foundry_output_data(alias_id, foundry_id).await?;
+        let controlled_outputs = [alias_data.output_id, foundry_data.output_id];
+        let (alias_output, foundry_output) = match (alias_data.output, foundry_data.output) {
+            (Output::Alias(alias_output), Output::Foundry(foundry_output)) => (alias_output, foundry_output),
+            _ => unreachable!("We already checked the output kinds"),
+        };
+
+        let TokenScheme::Simple(token_scheme) = foundry_output.token_scheme();
+        let circulating_supply = token_scheme.circulating_supply();
+        let (held_amount, holding_outputs) = self
+            .spendable_native_token_outputs(&token_id, &controlled_outputs)
+            .await?;
+
+        if held_amount < circulating_supply {
+            if held_amount.is_zero() {
+                return Err(Error::BurningOrMeltingFailed(format!(
+                    "none of the circulating supply of {circulating_supply} of {token_id} can be spent"
+                )));
+            }
+            log::debug!(
+                "[TRANSACTION] can't destroy foundry {foundry_id}, only {held_amount}/{circulating_supply} can be spent"
+            );
+
+            let transaction = self.melt_native_token(token_id, held_amount, options).await?;
+
+            return Ok(RetireNativeTokenTransaction {
+                transaction,
+                foundry_destroyed: false,
+                remaining_circulating_supply: circulating_supply - held_amount,
+            });
+        }
+
+        // Destroying the foundry melts all circulating tokens in the inputs, its storage deposit goes to the alias
+        let alias_output = AliasOutputBuilder::from(&alias_output)
+            .with_alias_id(alias_id)
+            .with_state_index(alias_output.state_index() + 1)
+            .with_amount(alias_output.amount() + foundry_output.amount())
+            .with_native_tokens(
+                alias_output
+                    .native_tokens()
+                    .iter()
+                    .filter(|native_token| native_token.token_id() != &token_id)
+                    .cloned(),
+            )
+            .finish_output(token_supply)?;
+
+        let mut options = options.unwrap_or_default();
+        let mut burn = options.burn.take().unwrap_or_default().add_foundry(foundry_id);
+        if !held_amount.is_zero() {
+            burn = burn.add_native_token(token_id, held_amount);
+        }
+        options.burn = Some(burn);
+        options.mandatory_inputs = Some(
+            options
+                .mandatory_inputs
+                .take()
+                .into_iter()
+                .flatten()
+                .chain(holding_outputs)
+                .collect(),
+        );
+
+        let transaction = self.send_outputs([alias_output], options).await?;
+
+        Ok(RetireNativeTokenTransaction {
+            transaction,
+            foundry_destroyed: true,
+            remaining_circulating_supply: U256::zero(),
+        })
+    }
+
+    // Returns the amount of a native token in the unspent outputs that the account can spend now and in the future,
+    // and the ids of these outputs. The controlled alias and foundry outputs are unlocked through the alias, so their
+    // native tokens are included too.
+    async fn spendable_native_token_outputs(
+        &self,
+        token_id: &TokenId,
+        controlled_outputs: &[OutputId],
+    ) -> crate::wallet::Result<(U256, Vec<OutputId>)> {
+        let network_id = self.client().get_network_id().await?;
+        let current_time = self.client().get_time_checked().await?;
+        let account_details = self.details().await;
+
+        let mut amount = U256::zero();
+        let mut output_ids = Vec::new();
+
+        for output_data in account_details.unspent_outputs().values() {
+            if output_data.network_id != network_id
+                || account_details.locked_outputs.contains(&output_data.output_id)
+                || (!controlled_outputs.contains(&output_data.output_id)
+                    && !can_output_be_unlocked_forever_from_now_on(
+                        account_details.addresses_with_unspent_outputs(),
+                        &output_data.output,
+                        current_time,
+                    ))
+            {
+                continue;
+            }
+
+            if let Some(native_token) = output_data.output.native_tokens().and_then(|native_tokens| {
+                native_tokens
+                    .iter()
+                    .find(|native_token| native_token.token_id() == token_id)
+            }) {
+                amount += native_token.amount();
+                output_ids.push(output_data.output_id);
+            }
+        }
+
+        Ok((amount, output_ids))
+    }
+}
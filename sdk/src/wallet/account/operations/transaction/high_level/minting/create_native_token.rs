@@ -12,8 +12,10 @@ use crate::{
     types::block::{
         address::AliasAddress,
         output::{
-            feature::MetadataFeature, unlock_condition::ImmutableAliasAddressUnlockCondition, AliasId,
-            AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId, TokenScheme,
+            feature::{Irc30Metadata, MetadataFeature},
+            unlock_condition::ImmutableAliasAddressUnlockCondition,
+            AliasId, AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId,
+            TokenScheme,
         },
     },
     wallet::account::{
@@ -37,6 +39,14 @@ pub struct CreateNativeTokenParams {
     pub foundry_metadata: Option<Vec<u8>>,
 }
 
+impl CreateNativeTokenParams {
+    /// Set the foundry metadata to validated [`Irc30Metadata`]
+    pub fn try_with_irc30_metadata(mut self, irc30_metadata: &Irc30Metadata) -> crate::wallet::Result<Self> {
+        self.foundry_metadata = Some(irc30_metadata.to_bytes()?);
+        Ok(self)
+    }
+}
+
 /// The result of a transaction to create a native token
 #[derive(Debug)]
 pub struct CreateNativeTokenTransaction {
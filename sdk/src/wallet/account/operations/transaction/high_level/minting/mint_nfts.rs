@@ -9,9 +9,9 @@ use crate::{
     types::block::{
         address::Bech32Address,
         output::{
-            feature::{IssuerFeature, MetadataFeature, SenderFeature, TagFeature},
+            feature::{Irc27Metadata, IssuerFeature, MetadataFeature, SenderFeature, TagFeature},
             unlock_condition::AddressUnlockCondition,
-            NftId, NftOutputBuilder,
+            NftId, NftOutputBuilder, Output, RentStructure,
         },
         ConvertTo,
     },
@@ -107,6 +107,47 @@ impl MintNftParams {
         self.immutable_metadata = immutable_metadata.into();
         self
     }
+
+    /// Set the immutable metadata to validated [`Irc27Metadata`]
+    pub fn try_with_irc27_metadata(mut self, irc27_metadata: &Irc27Metadata) -> crate::wallet::Result<Self> {
+        self.immutable_metadata = Some(irc27_metadata.to_bytes()?);
+        Ok(self)
+    }
+
+    /// Builds the output minting the NFT, owned by `address` if the params have no address.
+    pub(crate) fn to_output(
+        &self,
+        address: Bech32Address,
+        rent_structure: RentStructure,
+        token_supply: u64,
+    ) -> crate::wallet::Result<Output> {
+        // NftId needs to be set to 0 for the creation
+        let mut nft_builder = NftOutputBuilder::new_with_minimum_storage_deposit(rent_structure, NftId::null())
+            // Address which will own the nft
+            .add_unlock_condition(AddressUnlockCondition::new(self.address.unwrap_or(address)));
+
+        if let Some(sender) = self.sender {
+            nft_builder = nft_builder.add_feature(SenderFeature::new(sender));
+        }
+
+        if let Some(metadata) = &self.metadata {
+            nft_builder = nft_builder.add_feature(MetadataFeature::new(metadata.clone())?);
+        }
+
+        if let Some(tag) = &self.tag {
+            nft_builder = nft_builder.add_feature(TagFeature::new(tag.clone())?);
+        }
+
+        if let Some(issuer) = self.issuer {
+            nft_builder = nft_builder.add_immutable_feature(IssuerFeature::new(issuer));
+        }
+
+        if let Some(immutable_metadata) = &self.immutable_metadata {
+            nft_builder = nft_builder.add_immutable_feature(MetadataFeature::new(immutable_metadata.clone())?);
+        }
+
+        Ok(nft_builder.finish_output(token_supply)?)
+    }
 }
 
 impl<S: 'static + SecretManage> Account<S>
@@ -165,16 +206,8 @@ where
         let account_addresses = self.addresses().await?;
         let mut outputs = Vec::new();
 
-        for MintNftParams {
-            address,
-            sender,
-            metadata,
-            tag,
-            issuer,
-            immutable_metadata,
-        } in params
-        {
-            let address = match address {
+        for params in params {
+            let address = match params.address {
                 Some(address) => {
                     self.client().bech32_hrp_matches(address.hrp()).await?;
                     address
@@ -188,32 +221,7 @@ where
                 }
             };
 
-            // NftId needs to be set to 0 for the creation
-            let mut nft_builder = NftOutputBuilder::new_with_minimum_storage_deposit(rent_structure, NftId::null())
-                // Address which will own the nft
-                .add_unlock_condition(AddressUnlockCondition::new(address));
-
-            if let Some(sender) = sender {
-                nft_builder = nft_builder.add_feature(SenderFeature::new(sender));
-            }
-
-            if let Some(metadata) = metadata {
-                nft_builder = nft_builder.add_feature(MetadataFeature::new(metadata)?);
-            }
-
-            if let Some(tag) = tag {
-                nft_builder = nft_builder.add_feature(TagFeature::new(tag)?);
-            }
-
-            if let Some(issuer) = issuer {
-                nft_builder = nft_builder.add_immutable_feature(IssuerFeature::new(issuer));
-            }
-
-            if let Some(immutable_metadata) = immutable_metadata {
-                nft_builder = nft_builder.add_immutable_feature(MetadataFeature::new(immutable_metadata)?);
-            }
-
-            outputs.push(nft_builder.finish_output(token_supply)?);
+            outputs.push(params.to_output(address, rent_structure, token_supply)?);
         }
 
         self.prepare_transaction(outputs, options).await
@@ -4,3 +4,4 @@
 pub(crate) mod create_native_token;
 pub(crate) mod mint_native_token;
 pub(crate) mod mint_nfts;
+pub(crate) mod nft_collection;
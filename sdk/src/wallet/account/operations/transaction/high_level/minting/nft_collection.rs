@@ -0,0 +1,383 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, future::Future, path::Path};
+
+use getset::{CopyGetters, Getters};
+use packable::PackableExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{node_api::indexer::query_parameters::QueryParameter, secret::SecretManage},
+    types::block::{
+        address::{Address, Bech32Address, NftAddress},
+        output::{feature::Irc27Metadata, NftId, NftOutput, Output, OutputId},
+        payload::transaction::TransactionEssence,
+    },
+    wallet::account::{
+        constants::MAX_NFT_COLLECTION_ITEMS_PER_TRANSACTION, operations::transaction::batch_by_output_length,
+        types::Transaction, Account, MintNftParams, TransactionOptions,
+    },
+};
+
+impl MintNftParams {
+    /// Reads [`Irc27Metadata`] from the `.json` files of a directory and returns [`MintNftParams`] with it as
+    /// immutable metadata, sorted by file name.
+    pub fn from_irc27_metadata_dir(dir: impl AsRef<Path>) -> crate::wallet::Result<Vec<Self>> {
+        let mut paths = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.retain(|path| path.is_file() && path.extension().map_or(false, |extension| extension == "json"));
+        paths.sort();
+
+        paths
+            .iter()
+            .map(|path| {
+                let metadata = Irc27Metadata::from_bytes(&std::fs::read(path)?).map_err(|e| {
+                    crate::wallet::Error::MintingFailed(format!("invalid metadata in {}: {e}", path.display()))
+                })?;
+                Self::new().try_with_irc27_metadata(&metadata)
+            })
+            .collect()
+    }
+}
+
+/// The result of a transaction to mint a collection NFT
+#[derive(Debug)]
+pub struct MintCollectionNftTransaction {
+    pub nft_id: NftId,
+    pub transaction: Transaction,
+}
+
+/// An NFT of a collection, returned from
+/// [Account::nft_collection_items()](crate::wallet::Account::nft_collection_items).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct NftCollectionItem {
+    /// The id of the NFT
+    #[getset(get = "pub")]
+    nft_id: NftId,
+    /// The latest known output of the NFT
+    #[getset(get = "pub")]
+    output_id: OutputId,
+    /// The address owning the NFT, if it's known to be unspent
+    #[getset(get = "pub")]
+    owner: Option<Bech32Address>,
+    /// Whether the NFT is held by the account
+    #[getset(get_copy = "pub")]
+    held: bool,
+    /// Whether the NFT was minted by the account
+    #[getset(get_copy = "pub")]
+    issued: bool,
+    /// The IRC27 metadata of the NFT, if its immutable metadata follows the standard
+    #[getset(get = "pub")]
+    metadata: Option<Irc27Metadata>,
+}
+
+impl NftCollectionItem {
+    fn new(nft_id: NftId, output_id: OutputId, nft_output: &NftOutput) -> Self {
+        Self {
+            nft_id,
+            output_id,
+            owner: None,
+            held: false,
+            issued: false,
+            metadata: nft_output
+                .immutable_features()
+                .metadata()
+                .and_then(|metadata| Irc27Metadata::try_from(metadata).ok()),
+        }
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Mints a collection NFT, which can then be used as issuer of the NFTs of the collection with
+    /// [Account::mint_collection_items()](crate::wallet::Account::mint_collection_items).
+    /// ```ignore
+    /// let metadata = Irc27Metadata::new("image/png", "https://my.website/collection.png".parse()?, "My Collection");
+    /// let params = MintNftParams::new().try_with_irc27_metadata(&metadata)?;
+    ///
+    /// let transaction = account.mint_collection_nft(params, None).await?;
+    /// println!("Collection NFT minted: {}", transaction.nft_id);
+    /// ```
+    pub async fn mint_collection_nft(
+        &self,
+        params: MintNftParams,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<MintCollectionNftTransaction> {
+        log::debug!("[TRANSACTION] mint_collection_nft");
+        let transaction = self.mint_nfts([params], options).await?;
+
+        let TransactionEssence::Regular(essence) = transaction.payload.essence();
+        let output_index = essence
+            .outputs()
+            .iter()
+            .position(|output| matches!(output, Output::Nft(nft_output) if nft_output.nft_id().is_null()))
+            .ok_or_else(|| crate::wallet::Error::MintingFailed("missing collection NFT output".to_string()))?;
+        let nft_id = NftId::from(&OutputId::new(transaction.transaction_id, output_index as u16)?);
+
+        Ok(MintCollectionNftTransaction { nft_id, transaction })
+    }
+
+    /// Mints the NFTs of a collection with the collection NFT as issuer. The collection NFT needs to be owned by the
+    /// account. The NFTs are split into as many transactions as needed to stay within the output count and the
+    /// length limits of a transaction; since every transaction spends the collection NFT, each one is awaited to be
+    /// included before the next one is sent. If a transaction fails after others were sent,
+    /// [`Error::MintCollectionItemsFailed`](crate::wallet::Error::MintCollectionItemsFailed) is returned with the
+    /// sent transactions.
+    /// ```ignore
+    /// let params = MintNftParams::from_irc27_metadata_dir("./collection")?;
+    ///
+    /// let transactions = account.mint_collection_items(collection_id, params, None).await?;
+    /// for transaction in transactions {
+    ///     println!("Transaction sent: {}", transaction.transaction_id);
+    /// }
+    /// ```
+    pub async fn mint_collection_items<I: IntoIterator<Item = MintNftParams> + Send>(
+        &self,
+        collection_id: NftId,
+        params: I,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Vec<Transaction>>
+    where
+        I::IntoIter: Send,
+    {
+        log::debug!("[TRANSACTION] mint_collection_items");
+        let options = options.into();
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+        let issuer = Bech32Address::new(self.client().get_bech32_hrp().await?, NftAddress::new(collection_id));
+        // Only used to compute the length of outputs without an address, all addresses have the same length
+        let address = self
+            .addresses()
+            .await?
+            .first()
+            .ok_or(crate::wallet::Error::FailedToGetRemainder)?
+            .address;
+        // The collection NFT output is part of every transaction
+        let reserved_length = self
+            .unspent_nft_output(&collection_id)
+            .await?
+            .map_or(0, |output_data| output_data.output.packed_len());
+
+        let items = params
+            .into_iter()
+            .map(|params| {
+                let params = params.with_issuer(issuer);
+                let output_length = params.to_output(address, rent_structure, token_supply)?.packed_len();
+                Ok((output_length, params))
+            })
+            .collect::<crate::wallet::Result<Vec<_>>>()?;
+        let batches = batch_by_output_length(items, MAX_NFT_COLLECTION_ITEMS_PER_TRANSACTION, reserved_length);
+
+        send_batches(batches, |batch, previous_transaction: Option<&Transaction>| {
+            let previous_transaction_id = previous_transaction.map(|transaction| transaction.transaction_id);
+            let options = options.clone();
+            async move {
+                if let Some(previous_transaction_id) = previous_transaction_id {
+                    // The collection NFT output of the previous transaction is needed as input
+                    self.retry_transaction_until_included(&previous_transaction_id, None, None)
+                        .await?;
+                    self.sync(None).await?;
+                }
+
+                self.mint_nfts(batch, options).await
+            }
+        })
+        .await
+        .map_err(|(transactions, error)| {
+            if transactions.is_empty() {
+                error
+            } else {
+                crate::wallet::Error::MintCollectionItemsFailed {
+                    transactions,
+                    error: Box::new(error),
+                }
+            }
+        })
+    }
+
+    /// Returns the NFTs of a collection that were minted or are held by the account, with their current owners if
+    /// they are still unspent.
+    pub async fn nft_collection_items(&self, collection_id: NftId) -> crate::wallet::Result<Vec<NftCollectionItem>> {
+        log::debug!("[nft_collection_items]");
+        let bech32_hrp = self.client().get_bech32_hrp().await?;
+        let issuer = Address::from(NftAddress::new(collection_id));
+        let is_item = |nft_output: &NftOutput| {
+            nft_output
+                .immutable_features()
+                .issuer()
+                .map_or(false, |feature| feature.address() == &issuer)
+        };
+        let mut items = HashMap::new();
+
+        {
+            let account_details = self.details().await;
+
+            // NFTs minted by the account, also if they were sent to other addresses
+            for transaction in account_details.transactions().values().filter(|tx| !tx.incoming) {
+                let TransactionEssence::Regular(essence) = transaction.payload.essence();
+                for (index, output) in essence.outputs().iter().enumerate() {
+                    if let Output::Nft(nft_output) = output {
+                        if nft_output.nft_id().is_null() && is_item(nft_output) {
+                            let output_id = OutputId::new(transaction.transaction_id, index as u16)?;
+                            let nft_id = NftId::from(&output_id);
+                            items
+                                .entry(nft_id)
+                                .or_insert_with(|| NftCollectionItem::new(nft_id, output_id, nft_output))
+                                .issued = true;
+                        }
+                    }
+                }
+            }
+
+            // NFTs held by the account
+            for output_data in account_details.unspent_outputs().values() {
+                if let Output::Nft(nft_output) = &output_data.output {
+                    if is_item(nft_output) {
+                        let nft_id = nft_output.nft_id_non_null(&output_data.output_id);
+                        let item = items
+                            .entry(nft_id)
+                            .or_insert_with(|| NftCollectionItem::new(nft_id, output_data.output_id, nft_output));
+                        item.held = true;
+                        item.output_id = output_data.output_id;
+                        item.owner = Some(Bech32Address::new(bech32_hrp, output_data.address));
+                    }
+                }
+            }
+        }
+
+        // Current owners of the items that aren't held by the account
+        let output_ids = self
+            .client()
+            .nft_output_ids([QueryParameter::Issuer(Bech32Address::new(bech32_hrp, issuer))])
+            .await?
+            .items;
+
+        for output_with_metadata in self.client().get_outputs(&output_ids).await? {
+            if let Output::Nft(nft_output) = output_with_metadata.output() {
+                let output_id = output_with_metadata.metadata().output_id();
+                let nft_id = nft_output.nft_id_non_null(output_id);
+
+                if let Some(item) = items.get_mut(&nft_id) {
+                    if !item.held {
+                        item.output_id = *output_id;
+                        item.owner = Some(Bech32Address::new(bech32_hrp, *nft_output.address()));
+                    }
+                }
+            }
+        }
+
+        let mut items = items.into_values().collect::<Vec<_>>();
+        items.sort_by_key(|item| item.output_id);
+
+        Ok(items)
+    }
+}
+
+// Sends the batches one after the other, each one with the result of the previous one. If sending a batch fails,
+// the results of the already sent batches are returned with the error.
+async fn send_batches<B, T, Fut>(
+    batches: Vec<B>,
+    mut send: impl FnMut(B, Option<&T>) -> Fut + Send,
+) -> Result<Vec<T>, (Vec<T>, crate::wallet::Error)>
+where
+    Fut: Future<Output = crate::wallet::Result<T>> + Send,
+{
+    let mut results = Vec::new();
+
+    for batch in batches {
+        match send(batch, results.last()).await {
+            Ok(result) => results.push(result),
+            Err(error) => return Err((results, error)),
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::block::{
+        address::{Ed25519Address, Hrp},
+        output::RentStructure,
+    };
+    use crate::wallet::account::constants::MAX_OUTPUTS_LENGTH_PER_TRANSACTION;
+
+    const TOKEN_SUPPLY: u64 = 1_813_620_509_061_365;
+
+    #[test]
+    fn collection_items_are_batched_by_length() {
+        let address = Bech32Address::new(Hrp::from_str_unchecked("rms"), Ed25519Address::new([0; 32]));
+        let issuer = Bech32Address::new(Hrp::from_str_unchecked("rms"), NftAddress::new(NftId::null()));
+        let metadata = Irc27Metadata::new(
+            "image/png",
+            "https://my.website/collection/item.png".parse().unwrap(),
+            "x".repeat(2048),
+        );
+        let params = MintNftParams::new()
+            .try_with_irc27_metadata(&metadata)
+            .unwrap()
+            .with_issuer(issuer);
+        let output_length = params
+            .to_output(address, RentStructure::default(), TOKEN_SUPPLY)
+            .unwrap()
+            .packed_len();
+        // The collection NFT output
+        let reserved_length = MintNftParams::new()
+            .to_output(address, RentStructure::default(), TOKEN_SUPPLY)
+            .unwrap()
+            .packed_len();
+
+        let items = (0..MAX_NFT_COLLECTION_ITEMS_PER_TRANSACTION).map(|index| (output_length, index));
+        let batches = batch_by_output_length(items, MAX_NFT_COLLECTION_ITEMS_PER_TRANSACTION, reserved_length);
+
+        assert!(batches.len() > 1);
+        assert_eq!(
+            batches.concat(),
+            (0..MAX_NFT_COLLECTION_ITEMS_PER_TRANSACTION).collect::<Vec<_>>()
+        );
+        for batch in batches {
+            assert!(batch.len() * output_length + reserved_length <= MAX_OUTPUTS_LENGTH_PER_TRANSACTION);
+        }
+    }
+
+    #[tokio::test]
+    async fn sent_batches_are_returned_when_a_later_batch_fails() {
+        let result = send_batches(vec![1, 2, 3], |batch: u32, previous: Option<&u32>| {
+            let previous = previous.copied();
+            async move {
+                if batch == 3 {
+                    return Err(crate::wallet::Error::MintingFailed("batch 3".to_string()));
+                }
+                // Every batch gets the result of the previous one
+                assert_eq!(previous, (batch > 1).then(|| (batch - 1) * 10));
+                Ok(batch * 10)
+            }
+        })
+        .await;
+
+        let (sent, error) = result.unwrap_err();
+        assert_eq!(sent, [10, 20]);
+        assert!(matches!(error, crate::wallet::Error::MintingFailed(message) if message == "batch 3"));
+
+        let result = send_batches(vec![1, 2], |_: u32, _: Option<&u32>| async {
+            Err::<u32, _>(crate::wallet::Error::MintingFailed("batch 1".to_string()))
+        })
+        .await;
+
+        let (sent, _) = result.unwrap_err();
+        assert!(sent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn all_batches_are_sent() {
+        let result = send_batches(vec![1, 2, 3], |batch: u32, _: Option<&u32>| async move { Ok(batch) }).await;
+
+        assert_eq!(result.unwrap(), [1, 2, 3]);
+    }
+}
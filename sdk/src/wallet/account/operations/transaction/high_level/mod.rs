@@ -1,9 +1,13 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod airdrop;
 pub(crate) mod burning_melting;
 pub(crate) mod create_alias;
 pub(crate) mod minting;
+pub(crate) mod replace_transaction;
 pub(crate) mod send;
+pub(crate) mod send_from_chain;
 pub(crate) mod send_native_tokens;
 pub(crate) mod send_nft;
+pub(crate) mod update_alias;
@@ -0,0 +1,167 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::{api::PreparedTransactionData, secret::SecretManage},
+    types::block::{
+        output::{Output, OutputId},
+        payload::transaction::{TransactionEssence, TransactionId},
+    },
+    wallet::{
+        account::{
+            types::{InclusionState, Transaction},
+            Account, TransactionOptions,
+        },
+        Error, Result,
+    },
+};
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Cancels a pending transaction by sending its inputs back to the account in a conflicting transaction. Only one
+    /// of both transactions can get confirmed, the other one will be marked as conflicting during syncing.
+    /// ```ignore
+    /// let transaction = account.cancel_transaction(&transaction_id, None).await?;
+    /// ```
+    pub async fn cancel_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> Result<Transaction> {
+        let options = options.into();
+        let prepared_transaction = self.prepare_cancel_transaction(transaction_id, options.clone()).await?;
+
+        self.sign_and_submit_replacing_transaction(transaction_id, prepared_transaction, options)
+            .await
+    }
+
+    /// Prepares the transaction for [Account::cancel_transaction()](crate::wallet::Account::cancel_transaction).
+    pub async fn prepare_cancel_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_cancel_transaction");
+
+        // Without outputs, the input selection transitions the chain outputs and sends everything else to the
+        // remainder address
+        self.prepare_replacing_transaction(transaction_id, Vec::new(), options.into(), true)
+            .await
+    }
+
+    /// Replaces a pending transaction with a conflicting transaction that spends the same inputs, and others if
+    /// needed, to create the new outputs. Only one of both transactions can get confirmed, the other one will be
+    /// marked as conflicting during syncing.
+    /// ```ignore
+    /// let outputs = [BasicOutputBuilder::new_with_amount(1_000_000)
+    ///     .add_unlock_condition(AddressUnlockCondition::new(address))
+    ///     .finish_output(account.client().get_token_supply().await?)?];
+    ///
+    /// let transaction = account.replace_transaction(&transaction_id, outputs, None).await?;
+    /// ```
+    pub async fn replace_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        outputs: impl Into<Vec<Output>> + Send,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> Result<Transaction> {
+        let options = options.into();
+        let prepared_transaction = self
+            .prepare_replace_transaction(transaction_id, outputs, options.clone())
+            .await?;
+
+        self.sign_and_submit_replacing_transaction(transaction_id, prepared_transaction, options)
+            .await
+    }
+
+    /// Prepares the transaction for [Account::replace_transaction()](crate::wallet::Account::replace_transaction).
+    pub async fn prepare_replace_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        outputs: impl Into<Vec<Output>> + Send,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_replace_transaction");
+        self.prepare_replacing_transaction(transaction_id, outputs.into(), options.into(), false)
+            .await
+    }
+
+    // Prepares a transaction spending the inputs of a pending transaction, the inputs are locked again if it fails.
+    async fn prepare_replacing_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        outputs: Vec<Output>,
+        options: Option<TransactionOptions>,
+        allow_empty_outputs: bool,
+    ) -> Result<PreparedTransactionData> {
+        let mut options = options.unwrap_or_default();
+
+        let input_ids = {
+            let mut account_details = self.details_mut().await;
+            let transaction = account_details
+                .transactions
+                .get(transaction_id)
+                .ok_or(Error::TransactionNotFound(*transaction_id))?;
+
+            if transaction.incoming || transaction.inclusion_state != InclusionState::Pending {
+                return Err(Error::TransactionNotPending(*transaction_id));
+            }
+
+            let TransactionEssence::Regular(essence) = transaction.payload.essence();
+            let input_ids = essence
+                .inputs()
+                .iter()
+                .map(|input| *input.as_utxo().output_id())
+                .collect::<Vec<OutputId>>();
+
+            // The inputs are locked for the transaction to replace, so they need to be released to be selected again
+            for input_id in &input_ids {
+                account_details.locked_outputs.remove(input_id);
+            }
+
+            input_ids
+        };
+
+        let mut mandatory_inputs = options.mandatory_inputs.take().unwrap_or_default();
+        for input_id in &input_ids {
+            if !mandatory_inputs.contains(input_id) {
+                mandatory_inputs.push(*input_id);
+            }
+        }
+        options.mandatory_inputs = Some(mandatory_inputs);
+        options.custom_inputs = None;
+
+        match self
+            .prepare_transaction_internal(outputs, Some(options), None, allow_empty_outputs)
+            .await
+        {
+            Ok(prepared_transaction) => Ok(prepared_transaction),
+            Err(err) => {
+                // The transaction to replace is still pending, so its inputs need to be locked again
+                self.details_mut().await.locked_outputs.extend(input_ids);
+                Err(err)
+            }
+        }
+    }
+
+    // Signs and submits a transaction that replaces another one and links them.
+    pub(crate) async fn sign_and_submit_replacing_transaction(
+        &self,
+        replaced_transaction_id: &TransactionId,
+        prepared_transaction: PreparedTransactionData,
+        options: Option<TransactionOptions>,
+    ) -> Result<Transaction> {
+        let transaction = self
+            .sign_and_submit_transaction_internal(prepared_transaction, options, Some(*replaced_transaction_id))
+            .await?;
+
+        log::debug!(
+            "[TRANSACTION] transaction {} replaces {replaced_transaction_id}",
+            transaction.transaction_id
+        );
+
+        Ok(transaction)
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use crate::{
+    client::{api::PreparedTransactionData, secret::SecretManage},
+    types::block::{
+        address::{Address, Bech32Address},
+        output::{unlock_condition::UnlockCondition, Output, OutputId},
+        ConvertTo,
+    },
+    wallet::account::{
+        types::{AccountAddress, Transaction},
+        Account, RemainderValueStrategy, TransactionOptions,
+    },
+};
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Sends outputs that are funded only by the assets owned by an alias or NFT address of the account, see
+    /// [Balance::owned_by_chains()](crate::wallet::account::types::Balance::owned_by_chains). The alias or NFT output
+    /// is transitioned to unlock the owned outputs that are needed and the remainder is sent back to the alias or NFT
+    /// address, so the remainder value strategy and the inputs of the options are ignored.
+    /// ```ignore
+    /// let outputs = [BasicOutputBuilder::new_with_amount(1_000_000)
+    ///     .add_unlock_condition(AddressUnlockCondition::new(recipient_address))
+    ///     .finish_output(token_supply)?];
+    ///
+    /// let transaction = account.send_outputs_from_chain(alias_address, outputs, None).await?;
+    /// println!("Transaction sent: {}", transaction.transaction_id);
+    /// ```
+    pub async fn send_outputs_from_chain(
+        &self,
+        chain_address: impl ConvertTo<Bech32Address>,
+        outputs: impl Into<Vec<Output>> + Send,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        let options = options.into();
+        let prepared_transaction = self
+            .prepare_send_outputs_from_chain(chain_address, outputs, options.clone())
+            .await?;
+
+        self.sign_and_submit_transaction(prepared_transaction, options).await
+    }
+
+    /// Prepares the transaction for
+    /// [Account::send_outputs_from_chain()](crate::wallet::Account::send_outputs_from_chain).
+    pub async fn prepare_send_outputs_from_chain(
+        &self,
+        chain_address: impl ConvertTo<Bech32Address>,
+        outputs: impl Into<Vec<Output>> + Send,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_send_outputs_from_chain");
+        let chain_address = chain_address.convert()?;
+        self.client().bech32_hrp_matches(chain_address.hrp()).await?;
+        let (chain_output_id, available_inputs) = self.chain_owned_inputs(&chain_address).await?;
+
+        let mut options = options.into().unwrap_or_default();
+        options.custom_inputs = None;
+        options.mandatory_inputs = Some(vec![chain_output_id]);
+        // The key index isn't used for a custom remainder address
+        options.remainder_value_strategy = RemainderValueStrategy::CustomAddress(AccountAddress {
+            address: chain_address,
+            key_index: 0,
+            internal: false,
+            used: true,
+        });
+
+        // Input selection only picks the outputs owned by the chain address that are needed for the outputs
+        self.prepare_transaction_internal(outputs.into(), Some(options), Some(available_inputs), false)
+            .await
+    }
+
+    // Returns the id of the alias or NFT output of the address and the ids of the inputs that can be selected, which
+    // are the chain output and the unspent outputs owned by the address that can be spent without restrictions.
+    async fn chain_owned_inputs(
+        &self,
+        chain_address: &Bech32Address,
+    ) -> crate::wallet::Result<(OutputId, HashSet<OutputId>)> {
+        let network_id = self.client().get_network_id().await?;
+        let chain_output = match chain_address.inner() {
+            Address::Alias(alias_address) => self.unspent_alias_output(alias_address.alias_id()).await?,
+            Address::Nft(nft_address) => self.unspent_nft_output(nft_address.nft_id()).await?,
+            Address::Ed25519(_) => None,
+        }
+        .ok_or(crate::wallet::Error::ChainAddressNotOwned(*chain_address))?;
+
+        let account_details = self.details().await;
+        let owned_outputs = account_details
+            .unspent_outputs()
+            .values()
+            .filter(|output_data| {
+                output_data.network_id == network_id
+                    && !account_details.locked_outputs.contains(&output_data.output_id)
+                    && output_data
+                        .output
+                        .unlock_conditions()
+                        .map_or(false, |unlock_conditions| {
+                            matches!(
+                                unlock_conditions.as_ref(),
+                                [UnlockCondition::Address(address)] if address.address() == chain_address.inner()
+                            )
+                        })
+            })
+            .map(|output_data| output_data.output_id);
+
+        Ok((
+            chain_output.output_id,
+            std::iter::once(chain_output.output_id).chain(owned_outputs).collect(),
+        ))
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{api::PreparedTransactionData, secret::SecretManage},
+    types::block::{
+        address::Bech32Address,
+        output::{
+            feature::{MetadataFeature, SenderFeature},
+            unlock_condition::{GovernorAddressUnlockCondition, StateControllerAddressUnlockCondition},
+            AliasId, AliasOutput, AliasOutputBuilder, Output,
+        },
+    },
+    wallet::account::{types::Transaction, Account, TransactionOptions},
+};
+
+/// Params for `update_alias_governance()`. Fields that aren't set are kept from the current alias output.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasGovernanceParams {
+    /// Bech32 encoded address of the new state controller
+    #[getset(get = "pub")]
+    state_controller_address: Option<Bech32Address>,
+    /// Bech32 encoded address of the new governor
+    #[getset(get = "pub")]
+    governor_address: Option<Bech32Address>,
+    /// New alias sender feature
+    #[getset(get = "pub")]
+    sender: Option<Bech32Address>,
+    /// Whether to remove the sender feature
+    #[getset(get_copy = "pub")]
+    #[serde(default)]
+    remove_sender: bool,
+    /// New alias metadata feature
+    #[getset(get = "pub")]
+    #[serde(default, with = "crate::utils::serde::option_prefix_hex_bytes")]
+    metadata: Option<Vec<u8>>,
+    /// Whether to remove the metadata feature
+    #[getset(get_copy = "pub")]
+    #[serde(default)]
+    remove_metadata: bool,
+}
+
+impl AliasGovernanceParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the address of the new state controller
+    pub fn with_state_controller_address(mut self, address: impl Into<Option<Bech32Address>>) -> Self {
+        self.state_controller_address = address.into();
+        self
+    }
+
+    /// Set the address of the new governor
+    pub fn with_governor_address(mut self, address: impl Into<Option<Bech32Address>>) -> Self {
+        self.governor_address = address.into();
+        self
+    }
+
+    /// Set the sender feature
+    pub fn with_sender(mut self, sender: Bech32Address) -> Self {
+        self.sender = Some(sender);
+        self.remove_sender = false;
+        self
+    }
+
+    /// Remove the sender feature
+    pub fn without_sender(mut self) -> Self {
+        self.sender = None;
+        self.remove_sender = true;
+        self
+    }
+
+    /// Set the metadata feature
+    pub fn with_metadata(mut self, metadata: Vec<u8>) -> Self {
+        self.metadata = Some(metadata);
+        self.remove_metadata = false;
+        self
+    }
+
+    /// Remove the metadata feature
+    pub fn without_metadata(mut self) -> Self {
+        self.metadata = None;
+        self.remove_metadata = true;
+        self
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Updates the state metadata of an alias with a state transition, which needs to be signed by the state
+    /// controller. The amount of the alias is increased if the new state metadata requires a higher storage deposit.
+    /// ```ignore
+    /// let transaction = account
+    ///     .update_alias_state_metadata(alias_id, b"some alias state metadata".to_vec(), None)
+    ///     .await?;
+    /// println!("Transaction sent: {}", transaction.transaction_id);
+    /// ```
+    pub async fn update_alias_state_metadata(
+        &self,
+        alias_id: AliasId,
+        state_metadata: Vec<u8>,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        let options = options.into();
+        let prepared_transaction = self
+            .prepare_update_alias_state_metadata(alias_id, state_metadata, options.clone())
+            .await?;
+
+        self.sign_and_submit_transaction(prepared_transaction, options).await
+    }
+
+    /// Prepares the transaction for
+    /// [Account::update_alias_state_metadata()](crate::wallet::Account::update_alias_state_metadata).
+    pub async fn prepare_update_alias_state_metadata(
+        &self,
+        alias_id: AliasId,
+        state_metadata: Vec<u8>,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_update_alias_state_metadata");
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+        let alias_output = self.alias_output_to_update(&alias_id).await?;
+
+        let builder = AliasOutputBuilder::from(&alias_output)
+            .with_alias_id(alias_id)
+            .with_state_index(alias_output.state_index() + 1)
+            .with_state_metadata(state_metadata);
+        // Only keep the current amount if it still covers the storage deposit
+        let output = builder
+            .clone()
+            .with_minimum_storage_deposit(rent_structure)
+            .finish_output(token_supply)?;
+        let output = if output.amount() < alias_output.amount() {
+            builder.with_amount(alias_output.amount()).finish_output(token_supply)?
+        } else {
+            output
+        };
+
+        self.prepare_transaction([output], options).await
+    }
+
+    /// Updates the governance of an alias with a governance transition, which needs to be signed by the governor. The
+    /// state controller and governor addresses can be rotated and the sender and metadata features set or removed.
+    /// The amount of the alias can't change in a governance transition, so it needs to cover the storage deposit of
+    /// the updated alias.
+    /// ```ignore
+    /// let params = AliasGovernanceParams::new()
+    ///     .with_state_controller_address(new_state_controller_address)
+    ///     .with_metadata(b"some alias metadata".to_vec());
+    ///
+    /// let transaction = account.update_alias_governance(alias_id, params, None).await?;
+    /// println!("Transaction sent: {}", transaction.transaction_id);
+    /// ```
+    pub async fn update_alias_governance(
+        &self,
+        alias_id: AliasId,
+        params: AliasGovernanceParams,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        let options = options.into();
+        let prepared_transaction = self
+            .prepare_update_alias_governance(alias_id, params, options.clone())
+            .await?;
+
+        self.sign_and_submit_transaction(prepared_transaction, options).await
+    }
+
+    /// Prepares the transaction for
+    /// [Account::update_alias_governance()](crate::wallet::Account::update_alias_governance).
+    pub async fn prepare_update_alias_governance(
+        &self,
+        alias_id: AliasId,
+        params: AliasGovernanceParams,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_update_alias_governance");
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+        let alias_output = self.alias_output_to_update(&alias_id).await?;
+
+        let AliasGovernanceParams {
+            state_controller_address,
+            governor_address,
+            sender,
+            remove_sender,
+            metadata,
+            remove_metadata,
+        } = params;
+
+        // The state index stays the same, which makes it a governance transition
+        let mut builder = AliasOutputBuilder::from(&alias_output)
+            .with_alias_id(alias_id)
+            .with_features(alias_output.features().iter().cloned().filter(|feature| {
+                !((remove_sender && feature.is_sender()) || (remove_metadata && feature.is_metadata()))
+            }));
+
+        if let Some(address) = state_controller_address {
+            self.client().bech32_hrp_matches(address.hrp()).await?;
+            builder = builder.replace_unlock_condition(StateControllerAddressUnlockCondition::new(address));
+        }
+        if let Some(address) = governor_address {
+            self.client().bech32_hrp_matches(address.hrp()).await?;
+            builder = builder.replace_unlock_condition(GovernorAddressUnlockCondition::new(address));
+        }
+        if let Some(sender) = sender {
+            self.client().bech32_hrp_matches(sender.hrp()).await?;
+            builder = builder.replace_feature(SenderFeature::new(sender));
+        }
+        if let Some(metadata) = metadata {
+            builder = builder.replace_feature(MetadataFeature::new(metadata)?);
+        }
+
+        let output = builder.finish_output(token_supply)?;
+        output.verify_storage_deposit(rent_structure, token_supply)?;
+
+        self.prepare_transaction([output], options).await
+    }
+
+    /// Transfers the governance of an alias to another address, see
+    /// [Account::update_alias_governance()](crate::wallet::Account::update_alias_governance).
+    pub async fn transfer_alias_governance(
+        &self,
+        alias_id: AliasId,
+        governor_address: Bech32Address,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        self.update_alias_governance(
+            alias_id,
+            AliasGovernanceParams::new().with_governor_address(governor_address),
+            options,
+        )
+        .await
+    }
+
+    // Gets the unspent alias output of the account with the given id.
+    async fn alias_output_to_update(&self, alias_id: &AliasId) -> crate::wallet::Result<AliasOutput> {
+        match self.unspent_alias_output(alias_id).await? {
+            Some(output_data) => match output_data.output {
+                Output::Alias(alias_output) => Ok(alias_output),
+                _ => unreachable!("filtered by alias id"),
+            },
+            None => Err(crate::wallet::Error::AliasNotFoundInUnspentOutputs(*alias_id)),
+        }
+    }
+}
@@ -23,14 +23,18 @@ impl<S: 'static + SecretManage> Account<S>
 where
     crate::wallet::Error: From<S::Error>,
 {
-    /// Selects inputs for a transaction and locks them in the account, so they don't get used again
+    /// Selects inputs for a transaction and locks them in the account, so they don't get used again. If
+    /// `available_inputs` is set, inputs are only selected from them.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn select_inputs(
         &self,
         outputs: Vec<Output>,
         custom_inputs: Option<HashSet<OutputId>>,
         mandatory_inputs: Option<HashSet<OutputId>>,
+        available_inputs: Option<HashSet<OutputId>>,
         remainder_address: Option<Address>,
         burn: Option<&Burn>,
+        allow_empty_outputs: bool,
     ) -> crate::wallet::Result<Selected> {
         log::debug!("[TRANSACTION] select_inputs");
         // Voting output needs to be requested before to prevent a deadlock
@@ -79,6 +83,7 @@ where
             burn,
             custom_inputs.as_ref(),
             mandatory_inputs.as_ref(),
+            available_inputs.as_ref(),
         )?;
 
         // if custom inputs are provided we should only use them (validate if we have the outputs in this account and
@@ -100,7 +105,8 @@ where
                 protocol_parameters.clone(),
             )
             .required_inputs(custom_inputs)
-            .forbidden_inputs(forbidden_inputs);
+            .forbidden_inputs(forbidden_inputs)
+            .allow_empty_outputs(allow_empty_outputs);
 
             if let Some(address) = remainder_address {
                 input_selection = input_selection.remainder_address(address);
@@ -135,7 +141,8 @@ where
                 protocol_parameters.clone(),
             )
             .required_inputs(mandatory_inputs)
-            .forbidden_inputs(forbidden_inputs);
+            .forbidden_inputs(forbidden_inputs)
+            .allow_empty_outputs(allow_empty_outputs);
 
             if let Some(address) = remainder_address {
                 input_selection = input_selection.remainder_address(address);
@@ -166,7 +173,8 @@ where
             addresses,
             protocol_parameters.clone(),
         )
-        .forbidden_inputs(forbidden_inputs);
+        .forbidden_inputs(forbidden_inputs)
+        .allow_empty_outputs(allow_empty_outputs);
 
         if let Some(address) = remainder_address {
             input_selection = input_selection.remainder_address(address);
@@ -230,13 +238,22 @@ fn filter_inputs(
     burn: Option<&Burn>,
     custom_inputs: Option<&HashSet<OutputId>>,
     mandatory_inputs: Option<&HashSet<OutputId>>,
+    available_inputs: Option<&HashSet<OutputId>>,
 ) -> crate::wallet::Result<Vec<InputSigningData>> {
     let mut available_outputs_signing_data = Vec::new();
 
     for output_data in available_outputs {
-        if !custom_inputs
-            .map(|inputs| inputs.contains(&output_data.output_id))
-            .unwrap_or(false)
+        // Explicitly available inputs are already filtered by the caller
+        let is_available_input = match available_inputs {
+            Some(inputs) if !inputs.contains(&output_data.output_id) => continue,
+            Some(_) => true,
+            None => false,
+        };
+
+        if !is_available_input
+            && !custom_inputs
+                .map(|inputs| inputs.contains(&output_data.output_id))
+                .unwrap_or(false)
             && !mandatory_inputs
                 .map(|inputs| inputs.contains(&output_data.output_id))
                 .unwrap_or(false)
@@ -21,11 +21,12 @@ use crate::{
         api::core::response::OutputWithMetadataResponse,
         block::{
             output::{dto::OutputDto, Output},
-            payload::transaction::TransactionPayload,
+            payload::transaction::{TransactionId, TransactionPayload},
             semantic::ConflictReason,
         },
     },
     wallet::account::{
+        constants::MAX_OUTPUTS_LENGTH_PER_TRANSACTION,
         types::{InclusionState, Transaction},
         Account,
     },
@@ -106,6 +107,17 @@ where
     ) -> crate::wallet::Result<Transaction> {
         log::debug!("[TRANSACTION] sign_and_submit_transaction");
 
+        self.sign_and_submit_transaction_internal(prepared_transaction_data, options.into(), None)
+            .await
+    }
+
+    // Signs and submits a transaction, linking it to the transaction it replaces, if any.
+    pub(crate) async fn sign_and_submit_transaction_internal(
+        &self,
+        prepared_transaction_data: PreparedTransactionData,
+        options: Option<TransactionOptions>,
+        replaced_transaction_id: Option<TransactionId>,
+    ) -> crate::wallet::Result<Transaction> {
         let signed_transaction_data = match self.sign_transaction_essence(&prepared_transaction_data).await {
             Ok(res) => res,
             Err(err) => {
@@ -115,7 +127,7 @@ where
             }
         };
 
-        self.submit_and_store_transaction(signed_transaction_data, options)
+        self.submit_and_store_transaction_internal(signed_transaction_data, options, replaced_transaction_id)
             .await
     }
 
@@ -129,8 +141,19 @@ where
             "[TRANSACTION] submit_and_store_transaction {}",
             signed_transaction_data.transaction_payload.id()
         );
-        let options = options.into();
 
+        self.submit_and_store_transaction_internal(signed_transaction_data, options.into(), None)
+            .await
+    }
+
+    // Validates the transaction, submits it to a node and stores it in the account, linked to the transaction it
+    // replaces, if any.
+    async fn submit_and_store_transaction_internal(
+        &self,
+        signed_transaction_data: SignedTransactionData,
+        options: Option<TransactionOptions>,
+        replaced_transaction_id: Option<TransactionId>,
+    ) -> crate::wallet::Result<Transaction> {
         // Validate transaction before sending and storing it
         let local_time = self.client().get_time_checked().await?;
 
@@ -186,6 +209,7 @@ where
             incoming: false,
             note: options.and_then(|o| o.note),
             inputs,
+            replaced_transaction_id,
         };
 
         let mut account_details = self.details_mut().await;
@@ -215,3 +239,65 @@ where
         Ok(())
     }
 }
+
+/// Splits items, each one sent in an output with the given packed length, into batches for separate transactions.
+/// A batch has at most `max_outputs` items and the length of its outputs doesn't exceed
+/// [`MAX_OUTPUTS_LENGTH_PER_TRANSACTION`] minus `reserved_length`, which is needed for other outputs of the
+/// transactions. An item that doesn't fit on its own is put in its own batch, so building its transaction fails.
+pub(crate) fn batch_by_output_length<T>(
+    items: impl IntoIterator<Item = (usize, T)>,
+    max_outputs: usize,
+    reserved_length: usize,
+) -> Vec<Vec<T>> {
+    let max_length = MAX_OUTPUTS_LENGTH_PER_TRANSACTION.saturating_sub(reserved_length);
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut batch_length = 0;
+
+    for (output_length, item) in items {
+        if !batch.is_empty() && (batch.len() >= max_outputs || batch_length + output_length > max_length) {
+            batches.push(std::mem::take(&mut batch));
+            batch_length = 0;
+        }
+        batch_length += output_length;
+        batch.push(item);
+    }
+
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_are_limited_by_output_count() {
+        let batches = batch_by_output_length((0..300).map(|item| (100, item)), 127, 0);
+
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), [127, 127, 46]);
+        assert_eq!(batches.concat(), (0..300).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn batches_are_limited_by_output_length() {
+        let length = MAX_OUTPUTS_LENGTH_PER_TRANSACTION / 4;
+        let batches = batch_by_output_length((0..10).map(|item| (length, item)), 127, 0);
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), [4, 4, 2]);
+
+        // The length reserved for another output leaves room for one item less
+        let batches = batch_by_output_length((0..10).map(|item| (length, item)), 127, length);
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), [3, 3, 3, 1]);
+    }
+
+    #[test]
+    fn oversized_items_get_their_own_batch() {
+        let items = [(10, 0), (MAX_OUTPUTS_LENGTH_PER_TRANSACTION + 1, 1), (10, 2)];
+        let batches = batch_by_output_length(items, 127, 0);
+
+        assert_eq!(batches, [vec![0], vec![1], vec![2]]);
+    }
+}
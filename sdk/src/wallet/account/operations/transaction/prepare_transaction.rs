@@ -12,7 +12,7 @@ use crate::{
     client::{api::PreparedTransactionData, secret::SecretManage},
     types::block::{
         input::INPUT_COUNT_RANGE,
-        output::{Output, OUTPUT_COUNT_RANGE},
+        output::{Output, OutputId, OUTPUT_COUNT_RANGE},
     },
     wallet::account::{
         operations::transaction::{RemainderValueStrategy, TransactionOptions},
@@ -29,10 +29,21 @@ where
         &self,
         outputs: impl Into<Vec<Output>> + Send,
         options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<PreparedTransactionData> {
+        self.prepare_transaction_internal(outputs.into(), options.into(), None, false)
+            .await
+    }
+
+    /// Get inputs and build the transaction essence, only selecting from `available_inputs` if set and without outputs
+    /// if `allow_empty_outputs` is set, so the value of the inputs is sent to the remainder address.
+    pub(crate) async fn prepare_transaction_internal(
+        &self,
+        outputs: Vec<Output>,
+        options: Option<TransactionOptions>,
+        available_inputs: Option<HashSet<OutputId>>,
+        allow_empty_outputs: bool,
     ) -> crate::wallet::Result<PreparedTransactionData> {
         log::debug!("[TRANSACTION] prepare_transaction");
-        let options = options.into();
-        let outputs = outputs.into();
         let prepare_transaction_start_time = Instant::now();
         let rent_structure = self.client().get_rent_structure().await?;
         let token_supply = self.client().get_token_supply().await?;
@@ -46,7 +57,10 @@ where
 
         // Validate the number of outputs. The validation shouldn't be performed if [`Burn`] is present.
         // The outputs will be generated by the input selection algorithm (ISA).
-        if !OUTPUT_COUNT_RANGE.contains(&(outputs.len() as u16)) && !is_burn_present {
+        if !OUTPUT_COUNT_RANGE.contains(&(outputs.len() as u16))
+            && !is_burn_present
+            && !(outputs.is_empty() && allow_empty_outputs)
+        {
             return Err(crate::types::block::Error::InvalidOutputCount(
                 TryIntoBoundedU16Error::Truncated(outputs.len()),
             ))?;
@@ -111,8 +125,10 @@ where
                     .as_ref()
                     .and_then(|options| options.mandatory_inputs.as_ref())
                     .map(|inputs| HashSet::from_iter(inputs.clone())),
+                available_inputs,
                 remainder_address,
                 options.as_ref().and_then(|options| options.burn.as_ref()),
+                allow_empty_outputs,
             )
             .await?;
 
@@ -7,7 +7,13 @@ use getset::{CopyGetters, Getters};
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
-use crate::types::block::output::{feature::MetadataFeature, AliasId, FoundryId, NftId, OutputId, TokenId};
+use crate::types::block::{
+    address::Bech32Address,
+    output::{
+        feature::{Irc30Metadata, MetadataFeature},
+        AliasId, FoundryId, NativeToken, NftId, OutputId, OutputMetadata, TokenId,
+    },
+};
 
 /// The balance of an account, returned from [`crate::wallet::account::Account::sync()`] and
 /// [`crate::wallet::account::Account::balance()`].
@@ -32,6 +38,10 @@ pub struct Balance {
     /// [`ExpirationUnlockCondition`](crate::types::block::output::unlock_condition::ExpirationUnlockCondition) this
     /// can change at any time
     pub(crate) potentially_locked_outputs: HashMap<OutputId, bool>,
+    /// Assets of the balance that are owned by alias and NFT addresses of the account and can only be spent by
+    /// unlocking the alias or NFT output, per owning address
+    #[serde(default)]
+    pub(crate) owned_by_chains: HashMap<Bech32Address, BalanceChange>,
 }
 
 impl std::ops::AddAssign for Balance {
@@ -54,9 +64,70 @@ impl std::ops::AddAssign for Balance {
         self.nfts.extend(rhs.nfts);
         self.aliases.extend(rhs.aliases);
         self.foundries.extend(rhs.foundries);
+        self.owned_by_chains.extend(rhs.owned_by_chains);
     }
 }
 
+/// A point in the history of the ledger, used to request a [`HistoricBalance`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum BalancePoint {
+    /// The ledger state after the milestone with this index.
+    MilestoneIndex(u32),
+    /// The ledger state after the last milestone with a timestamp not later than this one.
+    MilestoneTimestamp(u32),
+}
+
+impl BalancePoint {
+    /// Returns whether an output with this metadata was unspent at this point. Returns `None` if the output was spent,
+    /// but the milestone of the spending is unknown.
+    pub(crate) fn is_unspent(&self, metadata: &OutputMetadata) -> Option<bool> {
+        let (booked, spent) = match *self {
+            Self::MilestoneIndex(index) => (
+                metadata.milestone_index_booked() <= index,
+                metadata.milestone_index_spent().map(|spent| spent <= index),
+            ),
+            Self::MilestoneTimestamp(timestamp) => (
+                metadata.milestone_timestamp_booked() <= timestamp,
+                metadata.milestone_timestamp_spent().map(|spent| spent <= timestamp),
+            ),
+        };
+
+        if !booked {
+            Some(false)
+        } else if !metadata.is_spent() {
+            Some(true)
+        } else {
+            spent.map(|spent| !spent)
+        }
+    }
+}
+
+/// The balance of an account at a point in the past, returned from
+/// [`crate::wallet::account::Account::historic_balance()`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricBalance {
+    /// The point the balance was reconstructed for
+    #[getset(get_copy = "pub")]
+    pub(crate) point: BalancePoint,
+    /// The milestone timestamp the unlock conditions were evaluated with
+    #[getset(get_copy = "pub")]
+    pub(crate) milestone_timestamp: u32,
+    /// The balance at the point. The available amounts only take the required storage deposit into account.
+    #[getset(get = "pub")]
+    pub(crate) balance: Balance,
+    /// If `true`, the balance may be incomplete, because the node already pruned the ledger state of the point, the
+    /// spent status of some outputs couldn't be determined or inputs of transactions of the account are unknown.
+    /// Outputs that were created and spent between two syncs without being spent by the account are never known.
+    #[getset(get_copy = "pub")]
+    pub(crate) potentially_incomplete: bool,
+    /// Outputs that were booked before the point, but for which it's unknown if they were spent at that time. They
+    /// are not part of the balance.
+    #[getset(get = "pub")]
+    pub(crate) unknown_outputs: Vec<OutputId>,
+}
+
 /// Base coin fields for [`Balance`]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, CopyGetters)]
 #[serde(rename_all = "camelCase")]
@@ -107,6 +178,41 @@ impl std::ops::AddAssign for RequiredStorageDeposit {
     }
 }
 
+/// Assets held by a group of outputs of an account, for example the assets of which the spendability changes at the
+/// same time in a [`BalanceTimelineEntry`] or the assets owned by an alias or NFT address in a [`Balance`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    /// Amount of the base coin, without storage deposits that have to be returned
+    #[getset(get_copy = "pub")]
+    pub(crate) base_coin: u64,
+    /// Native tokens
+    #[getset(get = "pub")]
+    pub(crate) native_tokens: Vec<NativeToken>,
+    /// Nfts
+    #[getset(get = "pub")]
+    pub(crate) nfts: Vec<NftId>,
+    /// The outputs holding the assets
+    #[getset(get = "pub")]
+    pub(crate) outputs: Vec<OutputId>,
+}
+
+/// The assets of an account that become spendable or stop being spendable at a future milestone timestamp, returned
+/// from [`crate::wallet::account::Account::balance_timeline()`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceTimelineEntry {
+    /// The milestone timestamp from which on the change applies
+    #[getset(get_copy = "pub")]
+    pub(crate) timestamp: u32,
+    /// Assets that become spendable, because a timelock ends or an expiration returns them to the account
+    #[getset(get = "pub")]
+    pub(crate) unlocked: BalanceChange,
+    /// Assets that stop being spendable, because an expiration returns them to their sender
+    #[getset(get = "pub")]
+    pub(crate) expiring: BalanceChange,
+}
+
 /// Native tokens fields for [`Balance`]
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
 #[serde(rename_all = "camelCase")]
@@ -124,6 +230,34 @@ pub struct NativeTokensBalance {
     #[getset(get = "pub")]
     #[serde(with = "crate::utils::serde::option_string")]
     pub(crate) metadata: Option<MetadataFeature>,
+    /// IRC30 metadata of the token from the token registry of the wallet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) irc30_metadata: Option<Irc30Metadata>,
+    /// Whether the metadata of the token comes from the verified tokens of the token registry, not from a user
+    /// provided override
+    #[getset(get_copy = "pub")]
+    #[serde(default)]
+    pub(crate) verified: bool,
+}
+
+impl NativeTokensBalance {
+    /// Returns the [`Irc30Metadata`] of the token from the token registry of the wallet or, if it's not known there,
+    /// parsed from its foundry metadata if that follows the standard.
+    pub fn irc30_metadata(&self) -> Option<Irc30Metadata> {
+        self.irc30_metadata.clone().or_else(|| {
+            self.metadata
+                .as_ref()
+                .and_then(|metadata| Irc30Metadata::try_from(metadata).ok())
+        })
+    }
+
+    /// Formats an amount of the token with the decimals and symbol of its IRC30 metadata, if known.
+    pub fn format_amount(&self, amount: U256) -> String {
+        match self.irc30_metadata() {
+            Some(irc30_metadata) => irc30_metadata.format_amount(amount),
+            None => amount.to_string(),
+        }
+    }
 }
 
 impl Default for NativeTokensBalance {
@@ -133,6 +267,8 @@ impl Default for NativeTokensBalance {
             total: U256::from(0u8),
             available: U256::from(0u8),
             metadata: None,
+            irc30_metadata: None,
+            verified: false,
         }
     }
 }
@@ -144,6 +280,10 @@ impl std::ops::AddAssign for NativeTokensBalance {
         if self.metadata.is_none() {
             self.metadata = rhs.metadata;
         }
+        if self.irc30_metadata.is_none() {
+            self.irc30_metadata = rhs.irc30_metadata;
+        }
+        self.verified |= rhs.verified;
     }
 }
 
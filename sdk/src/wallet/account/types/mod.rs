@@ -14,7 +14,10 @@ use serde::{Deserialize, Deserializer, Serialize};
 
 pub use self::{
     address::{AccountAddress, AddressWithUnspentOutputs},
-    balance::{Balance, BaseCoinBalance, NativeTokensBalance, RequiredStorageDeposit},
+    balance::{
+        Balance, BalanceChange, BalancePoint, BalanceTimelineEntry, BaseCoinBalance, HistoricBalance,
+        NativeTokensBalance, RequiredStorageDeposit,
+    },
 };
 use crate::{
     client::secret::types::InputSigningData,
@@ -22,7 +25,7 @@ use crate::{
         api::core::response::OutputWithMetadataResponse,
         block::{
             address::{dto::AddressDto, Address},
-            output::{dto::OutputDto, AliasTransition, Output, OutputId, OutputMetadata},
+            output::{dto::OutputDto, feature::Irc27Metadata, AliasTransition, Output, OutputId, OutputMetadata},
             payload::transaction::{dto::TransactionPayloadDto, TransactionId, TransactionPayload},
             BlockId, Error as BlockError,
         },
@@ -52,6 +55,17 @@ pub struct OutputData {
 }
 
 impl OutputData {
+    /// Returns the [`Irc27Metadata`] of an NFT output, if its immutable metadata follows the standard.
+    pub fn irc27_metadata(&self) -> Option<Irc27Metadata> {
+        match &self.output {
+            Output::Nft(nft_output) => nft_output
+                .immutable_features()
+                .metadata()
+                .and_then(|metadata| Irc27Metadata::try_from(metadata).ok()),
+            _ => None,
+        }
+    }
+
     pub fn input_signing_data(
         &self,
         account: &AccountDetails,
@@ -172,6 +186,8 @@ pub struct Transaction {
     /// from the node.
     // serde(default) is needed so it doesn't break with old dbs
     pub inputs: Vec<OutputWithMetadataResponse>,
+    /// The transaction that is double-spent by this one to cancel or replace it
+    pub replaced_transaction_id: Option<TransactionId>,
 }
 
 /// Dto for a transaction with metadata
@@ -195,6 +211,9 @@ pub struct TransactionDto {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
     pub inputs: Vec<OutputWithMetadataResponse>,
+    /// The transaction that is double-spent by this one to cancel or replace it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_transaction_id: Option<TransactionId>,
 }
 
 impl From<&Transaction> for TransactionDto {
@@ -209,6 +228,7 @@ impl From<&Transaction> for TransactionDto {
             incoming: value.incoming,
             note: value.note.clone(),
             inputs: value.inputs.clone(),
+            replaced_transaction_id: value.replaced_transaction_id,
         }
     }
 }
@@ -237,6 +257,7 @@ impl TryFromDto for Transaction {
             incoming: dto.incoming,
             note: dto.note,
             inputs: dto.inputs,
+            replaced_transaction_id: dto.replaced_transaction_id,
         })
     }
 }
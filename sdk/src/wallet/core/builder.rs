@@ -139,6 +139,11 @@ where
             crate::wallet::storage::adapter::rocksdb::RocksdbStorageAdapter::new(storage_options.path.clone())?;
         #[cfg(all(not(feature = "rocksdb"), feature = "storage"))]
         let storage = Memory::default();
+        // The cache backend isn't stored with the client options, so the cached data is kept in the wallet database,
+        // encrypted like the other records
+        #[cfg(all(feature = "rocksdb", feature = "storage"))]
+        let cache_backend =
+            crate::wallet::storage::Storage::new(storage.clone(), storage_options.encryption_key.clone());
 
         #[cfg(feature = "storage")]
         let mut storage_manager = StorageManager::new(storage, storage_options.encryption_key.clone()).await?;
@@ -206,18 +211,31 @@ where
         unlock_unused_inputs(&mut accounts)?;
         #[cfg(not(feature = "storage"))]
         let accounts = Vec::new();
+
+        #[cfg(feature = "storage")]
+        let token_registry = storage_manager.get_token_registry().await?.unwrap_or_default();
+        #[cfg(not(feature = "storage"))]
+        let token_registry = super::TokenRegistry::default();
+
+        let client_options = self
+            .client_options
+            .clone()
+            .ok_or(crate::wallet::Error::MissingParameter("client_options"))?;
+        #[cfg(all(feature = "rocksdb", feature = "storage"))]
+        let client_options = if client_options.cache_backend.is_none() {
+            client_options.with_cache_backend(cache_backend)
+        } else {
+            client_options
+        };
+
         let wallet_inner = Arc::new(WalletInner {
             background_syncing_status: AtomicUsize::new(0),
-            client: self
-                .client_options
-                .clone()
-                .ok_or(crate::wallet::Error::MissingParameter("client_options"))?
-                .finish()
-                .await?,
+            client: client_options.finish().await?,
             coin_type: AtomicU32::new(coin_type),
             secret_manager: self
                 .secret_manager
                 .ok_or(crate::wallet::Error::MissingParameter("secret_manager"))?,
+            token_registry: tokio::sync::RwLock::new(token_registry),
             #[cfg(feature = "events")]
             event_emitter,
             #[cfg(feature = "storage")]
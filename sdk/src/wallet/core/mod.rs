@@ -3,6 +3,7 @@
 
 pub(crate) mod builder;
 pub(crate) mod operations;
+pub(crate) mod token_registry;
 
 use std::sync::{
     atomic::{AtomicU32, AtomicUsize},
@@ -12,7 +13,7 @@ use std::sync::{
 use crypto::keys::bip39::{Mnemonic, MnemonicRef};
 use tokio::sync::RwLock;
 
-pub use self::builder::WalletBuilder;
+pub use self::{builder::WalletBuilder, token_registry::TokenRegistry};
 #[cfg(feature = "events")]
 use crate::wallet::events::{
     types::{Event, WalletEventType},
@@ -25,7 +26,7 @@ use crate::{
         secret::{SecretManage, SecretManager},
         verify_mnemonic, Client,
     },
-    wallet::account::{builder::AccountBuilder, operations::syncing::SyncOptions, types::Balance, Account},
+    wallet::account::{builder::AccountBuilder, types::Balance, Account},
 };
 
 /// The wallet, used to create and get accounts. One wallet can hold many accounts, but they should
@@ -77,6 +78,7 @@ pub struct WalletInner<S: SecretManage = SecretManager> {
     pub(crate) client: Client,
     pub(crate) coin_type: AtomicU32,
     pub(crate) secret_manager: Arc<RwLock<S>>,
+    pub(crate) token_registry: RwLock<TokenRegistry>,
     #[cfg(feature = "events")]
     pub(crate) event_emitter: tokio::sync::RwLock<EventEmitter>,
     #[cfg(feature = "storage")]
@@ -153,17 +155,6 @@ where
 
         Ok(balance)
     }
-
-    /// Sync all accounts
-    pub async fn sync(&self, options: Option<SyncOptions>) -> crate::wallet::Result<Balance> {
-        let mut balance = Balance::default();
-
-        for account in self.accounts.read().await.iter() {
-            balance += account.sync(options.clone()).await?;
-        }
-
-        Ok(balance)
-    }
 }
 
 impl<S: SecretManage> WalletInner<S> {
@@ -18,6 +18,8 @@ where
     crate::wallet::Error: From<S::Error>,
 {
     /// Start the background syncing process for all accounts, default interval is 7 seconds
+    /// After syncing, the outputs of accounts with a [ConsolidationPolicy](crate::wallet::account::ConsolidationPolicy)
+    /// are consolidated
     pub async fn start_background_syncing(
         &self,
         options: Option<SyncOptions>,
@@ -49,14 +51,20 @@ where
             runtime.block_on(async {
                 'outer: loop {
                     log::debug!("[background_syncing]: syncing accounts");
-                    for account in wallet.accounts.read().await.iter() {
+                    let accounts = wallet.accounts.read().await.clone();
+                    for account in accounts {
                         // Check if the process should stop before syncing each account so it stops faster
                         if wallet.background_syncing_status.load(Ordering::Relaxed) == 2 {
                             log::debug!("[background_syncing]: stopping");
                             break 'outer;
                         }
+                        // A failing account doesn't prevent syncing and consolidating the other accounts
                         match account.sync(options.clone()).await {
-                            Ok(_) => {}
+                            Ok(_) => {
+                                if let Err(err) = account.apply_consolidation_policy().await {
+                                    log::debug!("[background_syncing] consolidation error: {}", err);
+                                }
+                            }
                             Err(err) => log::debug!("[background_syncing] error: {}", err),
                         };
                     }
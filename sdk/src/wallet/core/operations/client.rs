@@ -44,6 +44,8 @@ where
             pow_worker_count,
             #[cfg(not(target_family = "wasm"))]
             max_parallel_api_requests,
+            cache,
+            cache_backend,
         } = client_options;
         self.client
             .update_node_manager(node_manager_builder.build(HashMap::new()))
@@ -53,6 +55,7 @@ where
         *self.client.remote_pow_timeout.write().await = remote_pow_timeout;
         #[cfg(not(target_family = "wasm"))]
         self.client.request_pool.resize(max_parallel_api_requests).await;
+        self.client.update_cache(cache, cache_backend).await;
         #[cfg(not(target_family = "wasm"))]
         {
             *self.client.pow_worker_count.write().await = pow_worker_count;
@@ -13,5 +13,7 @@ pub(crate) mod storage;
 pub(crate) mod stronghold;
 #[cfg(feature = "stronghold")]
 pub(crate) mod stronghold_backup;
+pub(crate) mod syncing;
+pub(crate) mod token_registry;
 #[cfg(debug_assertions)]
 pub(crate) mod verify_integrity;
@@ -0,0 +1,467 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+
+use futures::{StreamExt, TryStreamExt};
+use instant::Instant;
+
+use crate::{
+    client::secret::SecretManage,
+    types::block::{
+        address::{Address, AliasAddress, NftAddress, ToBech32Ext},
+        output::{Output, OutputId, OutputMetadata, OutputWithMetadata},
+    },
+    utils::unix_timestamp_now,
+    wallet::{
+        account::{
+            constants::MIN_SYNC_INTERVAL,
+            operations::syncing::SyncOptions,
+            types::{AddressWithUnspentOutputs, Balance, OutputData},
+            Account,
+        },
+        Wallet,
+    },
+};
+
+/// The data collected for a single account while the outputs of multiple accounts are synced together.
+#[derive(Default)]
+struct AccountSyncState {
+    /// Synced addresses that have unspent outputs
+    addresses_with_unspent_outputs: Vec<AddressWithUnspentOutputs>,
+    /// Unspent outputs of the account
+    outputs_data: Vec<OutputData>,
+    /// Outputs the account knew before, which got spent or aren't synced anymore because of other sync options
+    spent_or_not_synced_output_ids: Vec<OutputId>,
+}
+
+impl<S: 'static + SecretManage> Wallet<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Sync all accounts. The requests of all accounts are planned together, so every indexer query and every output
+    /// is only requested once, with at most `max_parallel_api_requests` requests in parallel.
+    /// If no options are provided, the default sync options of each account are used.
+    pub async fn sync(&self, options: Option<SyncOptions>) -> crate::wallet::Result<Balance> {
+        let accounts = self.accounts.read().await.clone();
+
+        self.sync_accounts(&accounts, options).await
+    }
+
+    /// Sync the provided accounts together and return their combined balance.
+    pub(crate) async fn sync_accounts(
+        &self,
+        accounts: &[Account<S>],
+        options: Option<SyncOptions>,
+    ) -> crate::wallet::Result<Balance> {
+        log::debug!("[SYNC] start syncing {} accounts", accounts.len());
+        let sync_start_time = Instant::now();
+        let mut balance = Balance::default();
+
+        // Prevent syncing the accounts simultaneously with `Account::sync()`, the locks are held until the end
+        let mut accounts_to_sync = Vec::new();
+        let mut last_synced_guards = Vec::new();
+        for account in accounts {
+            let options = match &options {
+                Some(options) => options.clone(),
+                None => account.default_sync_options().await,
+            };
+
+            let last_synced = account.last_synced.lock().await;
+            let time_now = unix_timestamp_now().as_millis();
+            if !options.force_syncing && time_now - *last_synced < MIN_SYNC_INTERVAL {
+                log::debug!(
+                    "[SYNC] account synced within the latest {} ms, only calculating balance",
+                    MIN_SYNC_INTERVAL
+                );
+                balance += account.balance().await?;
+                continue;
+            }
+
+            accounts_to_sync.push((account, options));
+            last_synced_guards.push(last_synced);
+        }
+
+        self.sync_accounts_outputs(&accounts_to_sync).await?;
+
+        // Sync transactions after updating the accounts with outputs, so we can use them to check the transaction
+        // status
+        let mut accounts_to_resync = Vec::new();
+        for (account, options) in &accounts_to_sync {
+            if options.sync_pending_transactions && account.sync_pending_transactions(options).await? {
+                accounts_to_resync.push((*account, options.clone()));
+            }
+        }
+        // Sync again if we don't know the output yet, to prevent having no unspent outputs after syncing
+        if !accounts_to_resync.is_empty() {
+            log::debug!("[SYNC] a transaction for which no output is known got confirmed, syncing outputs again");
+            self.sync_accounts_outputs(&accounts_to_resync).await?;
+        }
+
+        let time_now = unix_timestamp_now().as_millis();
+        for ((account, _), mut last_synced) in accounts_to_sync.iter().zip(last_synced_guards) {
+            balance += account.balance().await?;
+            *last_synced = time_now;
+        }
+
+        log::debug!(
+            "[SYNC] finished syncing {} accounts in {:.2?}",
+            accounts.len(),
+            sync_start_time.elapsed()
+        );
+        Ok(balance)
+    }
+
+    // Request the outputs of all accounts together, so indexer queries and outputs that are relevant for multiple
+    // accounts are only requested once, then update each account with its part of the results
+    async fn sync_accounts_outputs(&self, accounts: &[(&Account<S>, SyncOptions)]) -> crate::wallet::Result<()> {
+        #[cfg(not(target_family = "wasm"))]
+        let max_parallel_requests = self.client.request_pool.size().await.max(1);
+        #[cfg(target_family = "wasm")]
+        let max_parallel_requests = 1;
+
+        let account_options_groups = group_sync_options(accounts.iter().map(|(_, options)| options));
+
+        let mut states = Vec::with_capacity(accounts.len());
+        let mut addresses_to_sync = Vec::with_capacity(accounts.len());
+        for (account, options) in accounts {
+            addresses_to_sync.push(account.get_addresses_to_sync(options).await?);
+            states.push(AccountSyncState::default());
+        }
+        log::debug!(
+            "[SYNC] addresses_to_sync {}",
+            addresses_to_sync.iter().map(Vec::len).sum::<usize>()
+        );
+
+        // First request the output ids of the Ed25519 addresses of all accounts
+        let queries = plan_queries(
+            &account_options_groups,
+            addresses_to_sync
+                .iter()
+                .enumerate()
+                .flat_map(|(account_index, addresses)| {
+                    addresses
+                        .iter()
+                        .map(move |address| (account_index, address.address.inner))
+                }),
+        );
+        let output_ids = self
+            .request_output_ids(accounts, queries, max_parallel_requests)
+            .await?;
+
+        let mut pending_outputs = Vec::with_capacity(accounts.len());
+        for (account_index, addresses) in addresses_to_sync.into_iter().enumerate() {
+            let state = &mut states[account_index];
+            for mut address in addresses {
+                let new_output_ids = output_ids
+                    .get(&(account_options_groups[account_index], address.address.inner))
+                    .cloned()
+                    .unwrap_or_default();
+                // outputs we had before, but now not anymore, got spent or are alias/nft/foundries that don't get
+                // synced anymore because of other sync options
+                state.spent_or_not_synced_output_ids.extend(
+                    address
+                        .output_ids
+                        .iter()
+                        .filter(|output_id| !new_output_ids.contains(output_id)),
+                );
+                // only keep addresses with outputs
+                if !new_output_ids.is_empty() {
+                    address.output_ids = new_output_ids;
+                    state.addresses_with_unspent_outputs.push(address);
+                }
+            }
+            pending_outputs.push(
+                state
+                    .addresses_with_unspent_outputs
+                    .iter()
+                    .map(|address| (address.clone(), address.output_ids.clone()))
+                    .collect(),
+            );
+        }
+
+        let bech32_hrp = self.client.get_bech32_hrp().await?;
+
+        // Then request the outputs of the alias and nft addresses we got, in a loop until no new alias or nft outputs
+        // are found
+        loop {
+            let new_outputs_data = self
+                .request_outputs_data(accounts, pending_outputs, max_parallel_requests)
+                .await?;
+
+            let mut new_alias_and_nft_addresses = Vec::with_capacity(accounts.len());
+            for (state, new_outputs_data) in states.iter_mut().zip(new_outputs_data) {
+                // Cache the alias and nft address with the related ed2559 address, so we can update the account
+                // address with the new output ids
+                let mut addresses = HashMap::new();
+                for output_data in &new_outputs_data {
+                    match &output_data.output {
+                        Output::Alias(alias_output) => {
+                            let alias_address =
+                                AliasAddress::from(alias_output.alias_id_non_null(&output_data.output_id));
+                            addresses.insert(Address::Alias(alias_address), output_data.address);
+                        }
+                        Output::Nft(nft_output) => {
+                            let nft_address = NftAddress::from(nft_output.nft_id_non_null(&output_data.output_id));
+                            addresses.insert(Address::Nft(nft_address), output_data.address);
+                        }
+                        _ => {}
+                    }
+                }
+                state.outputs_data.extend(new_outputs_data);
+                new_alias_and_nft_addresses.push(addresses);
+            }
+            let queries = plan_queries(
+                &account_options_groups,
+                new_alias_and_nft_addresses
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(account_index, addresses)| {
+                        addresses.keys().map(move |address| (account_index, *address))
+                    }),
+            );
+
+            log::debug!("[SYNC] new_alias_and_nft_addresses: {new_alias_and_nft_addresses:?}");
+            if queries.is_empty() {
+                break;
+            }
+
+            let output_ids = self
+                .request_output_ids(accounts, queries, max_parallel_requests)
+                .await?;
+
+            pending_outputs = Vec::with_capacity(accounts.len());
+            for (account_index, addresses) in new_alias_and_nft_addresses.into_iter().enumerate() {
+                let state = &mut states[account_index];
+                let mut account_pending_outputs = Vec::new();
+                for (alias_or_nft_address, ed25519_address) in addresses {
+                    let new_output_ids = output_ids
+                        .get(&(account_options_groups[account_index], alias_or_nft_address))
+                        .cloned()
+                        .unwrap_or_default();
+
+                    // Update address with unspent outputs
+                    let address_with_unspent_outputs = state
+                        .addresses_with_unspent_outputs
+                        .iter_mut()
+                        .find(|a| a.address.inner == ed25519_address)
+                        .ok_or_else(|| {
+                            crate::wallet::Error::AddressNotFoundInAccount(ed25519_address.to_bech32(bech32_hrp))
+                        })?;
+                    address_with_unspent_outputs
+                        .output_ids
+                        .extend(new_output_ids.iter().copied());
+
+                    account_pending_outputs.push((address_with_unspent_outputs.clone(), new_output_ids));
+                }
+                pending_outputs.push(account_pending_outputs);
+            }
+        }
+
+        // Request the metadata of possibly spent outputs once for all accounts
+        let mut spent_or_not_synced_output_ids = HashSet::new();
+        for state in &mut states {
+            let unspent_output_ids = state.outputs_data.iter().map(|o| o.output_id).collect::<HashSet<_>>();
+            state
+                .spent_or_not_synced_output_ids
+                .retain(|o| !unspent_output_ids.contains(o));
+            spent_or_not_synced_output_ids.extend(state.spent_or_not_synced_output_ids.iter().copied());
+        }
+        log::debug!("[SYNC] spent_or_not_synced_outputs: {spent_or_not_synced_output_ids:?}");
+        // Failed requests are ignored, because an output could be pruned and then we can't get the metadata
+        let output_metadata = self
+            .client
+            .get_outputs_metadata_ignore_errors(&spent_or_not_synced_output_ids.into_iter().collect::<Vec<_>>())
+            .await?
+            .into_iter()
+            .map(|metadata| (*metadata.output_id(), metadata))
+            .collect::<HashMap<OutputId, OutputMetadata>>();
+
+        // Fan the results back into the accounts
+        for ((account, options), state) in accounts.iter().zip(states) {
+            let spent_or_unsynced_output_metadata_map = state
+                .spent_or_not_synced_output_ids
+                .into_iter()
+                .map(|output_id| (output_id, output_metadata.get(&output_id).copied()))
+                .collect();
+
+            account
+                .finish_sync_internal(
+                    state.addresses_with_unspent_outputs,
+                    state.outputs_data,
+                    spent_or_unsynced_output_metadata_map,
+                    options,
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // Run the deduplicated indexer queries for (options group, address) pairs, each query is sent with the account that
+    // needs it first
+    async fn request_output_ids(
+        &self,
+        accounts: &[(&Account<S>, SyncOptions)],
+        queries: HashMap<(usize, Address), usize>,
+        max_parallel_requests: usize,
+    ) -> crate::wallet::Result<HashMap<(usize, Address), Vec<OutputId>>> {
+        log::debug!("[SYNC] request output ids for {} addresses", queries.len());
+        let request_output_ids_start_time = Instant::now();
+
+        let output_ids = futures::stream::iter(queries)
+            .map(|(query, account_index)| {
+                let (account, options) = &accounts[account_index];
+                async move {
+                    let output_ids = account.get_output_ids_for_address(query.1, options).await?;
+                    crate::wallet::Result::Ok((query, output_ids))
+                }
+            })
+            .buffer_unordered(max_parallel_requests)
+            .try_collect()
+            .await?;
+
+        log::debug!(
+            "[SYNC] finished request_output_ids in {:.2?}",
+            request_output_ids_start_time.elapsed()
+        );
+        Ok(output_ids)
+    }
+
+    // Get the outputs for the pending output ids of each account's addresses. Outputs already known by an account are
+    // loaded from it, all other outputs are requested only once, even if multiple accounts need them
+    async fn request_outputs_data(
+        &self,
+        accounts: &[(&Account<S>, SyncOptions)],
+        pending_outputs: Vec<Vec<(AddressWithUnspentOutputs, Vec<OutputId>)>>,
+        max_parallel_requests: usize,
+    ) -> crate::wallet::Result<Vec<Vec<OutputData>>> {
+        log::debug!("[SYNC] start request_outputs_data");
+        let request_outputs_start_time = Instant::now();
+
+        let mut known_outputs = Vec::with_capacity(accounts.len());
+        let mut unknown_output_ids = HashSet::new();
+        for ((account, _), addresses) in accounts.iter().zip(&pending_outputs) {
+            let mut account_known_outputs = Vec::with_capacity(addresses.len());
+            for (_, output_ids) in addresses {
+                let (outputs, unknown) = account.get_known_outputs(output_ids.clone()).await;
+                unknown_output_ids.extend(unknown.iter().copied());
+                account_known_outputs.push((outputs, unknown));
+            }
+            known_outputs.push(account_known_outputs);
+        }
+
+        let unknown_outputs = futures::stream::iter(unknown_output_ids)
+            .map(|output_id| async move {
+                self.client
+                    .get_output(&output_id)
+                    .await
+                    .map(|output| (output_id, output))
+            })
+            .buffer_unordered(max_parallel_requests)
+            .try_collect::<HashMap<OutputId, OutputWithMetadata>>()
+            .await?;
+
+        let mut outputs_data = Vec::with_capacity(accounts.len());
+        for (((account, _), addresses), account_known_outputs) in
+            accounts.iter().zip(pending_outputs).zip(known_outputs)
+        {
+            let mut account_outputs_data = Vec::new();
+            for ((address, _), (mut outputs, unknown)) in addresses.into_iter().zip(account_known_outputs) {
+                outputs.extend(
+                    unknown
+                        .iter()
+                        .filter_map(|output_id| unknown_outputs.get(output_id).cloned()),
+                );
+                account_outputs_data.extend(account.output_response_to_output_data(outputs, &address).await?);
+            }
+            outputs_data.push(account_outputs_data);
+        }
+
+        log::debug!(
+            "[SYNC] finished request_outputs_data in {:.2?}",
+            request_outputs_start_time.elapsed()
+        );
+        Ok(outputs_data)
+    }
+}
+
+/// Returns the index of the group of equal sync options for each account. Accounts with the same sync options get the
+/// same output ids for an address, so they can share the indexer queries.
+fn group_sync_options<'a>(options: impl IntoIterator<Item = &'a SyncOptions>) -> Vec<usize> {
+    let mut options_groups: Vec<&SyncOptions> = Vec::new();
+    options
+        .into_iter()
+        .map(|options| {
+            options_groups.iter().position(|o| *o == options).unwrap_or_else(|| {
+                options_groups.push(options);
+                options_groups.len() - 1
+            })
+        })
+        .collect()
+}
+
+/// Returns the deduplicated indexer queries for the (account index, address) pairs, mapping each (options group,
+/// address) query to the first account that needs it.
+fn plan_queries(
+    account_options_groups: &[usize],
+    addresses: impl IntoIterator<Item = (usize, Address)>,
+) -> HashMap<(usize, Address), usize> {
+    let mut queries = HashMap::new();
+    for (account_index, address) in addresses {
+        queries
+            .entry((account_options_groups[account_index], address))
+            .or_insert(account_index);
+    }
+    queries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::block::address::Ed25519Address;
+
+    #[test]
+    fn accounts_with_equal_options_share_a_group() {
+        let default_options = SyncOptions::default();
+        let other_options = SyncOptions {
+            sync_incoming_transactions: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            group_sync_options([
+                &default_options,
+                &other_options,
+                &default_options.clone(),
+                &other_options
+            ]),
+            vec![0, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn queries_are_deduplicated_per_options_group() {
+        let address_0 = Address::Ed25519(Ed25519Address::new([0; 32]));
+        let address_1 = Address::Ed25519(Ed25519Address::new([1; 32]));
+        // Accounts 0 and 2 share the options, account 1 has other options
+        let account_options_groups = [0, 1, 0];
+
+        let queries = plan_queries(
+            &account_options_groups,
+            [
+                (0, address_0),
+                (1, address_0),
+                (2, address_0),
+                (2, address_1),
+                (0, address_1),
+            ],
+        );
+
+        assert_eq!(queries.len(), 3);
+        // A shared query is sent with the first account that needs it
+        assert_eq!(queries[&(0, address_0)], 0);
+        assert_eq!(queries[&(1, address_0)], 1);
+        assert_eq!(queries[&(0, address_1)], 2);
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::secret::SecretManage,
+    types::block::output::{feature::Irc30Metadata, FoundryOutput, TokenId},
+    wallet::core::{TokenRegistry, WalletInner},
+};
+
+impl<S: SecretManage> WalletInner<S> {
+    /// Returns a copy of the native token registry.
+    pub async fn token_registry(&self) -> TokenRegistry {
+        self.token_registry.read().await.clone()
+    }
+
+    /// Returns the metadata of a native token from the registry, if known.
+    pub async fn token_metadata(&self, token_id: &TokenId) -> Option<Irc30Metadata> {
+        self.token_registry.read().await.get(token_id).cloned()
+    }
+
+    /// Sets or, if `None`, removes a user provided override of the metadata of a native token. Overrides take
+    /// precedence over verified tokens and foundry metadata.
+    pub async fn set_token_metadata_override(
+        &self,
+        token_id: TokenId,
+        metadata: impl Into<Option<Irc30Metadata>> + Send,
+    ) -> crate::wallet::Result<()> {
+        let mut token_registry = self.token_registry.write().await;
+        token_registry.set_override(token_id, metadata);
+
+        #[cfg(feature = "storage")]
+        self.storage_manager
+            .read()
+            .await
+            .set_token_registry(&token_registry)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replaces the list of verified native tokens. Verified tokens take precedence over foundry metadata.
+    pub async fn set_verified_tokens(
+        &self,
+        tokens: impl IntoIterator<Item = (TokenId, Irc30Metadata)> + Send,
+    ) -> crate::wallet::Result<()> {
+        let mut token_registry = self.token_registry.write().await;
+        token_registry.set_verified(tokens);
+
+        #[cfg(feature = "storage")]
+        self.storage_manager
+            .read()
+            .await
+            .set_token_registry(&token_registry)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Caches the IRC30 metadata of the tokens of the given foundries.
+    pub(crate) async fn cache_token_metadata<'a>(
+        &self,
+        foundries: impl IntoIterator<Item = &'a FoundryOutput> + Send,
+    ) -> crate::wallet::Result<()> {
+        let mut token_registry = self.token_registry.write().await;
+        let mut changed = false;
+
+        for foundry in foundries {
+            changed |= token_registry.cache_foundry(foundry);
+        }
+
+        if changed {
+            log::debug!("[TOKEN REGISTRY] cached new token metadata");
+            #[cfg(feature = "storage")]
+            self.storage_manager
+                .read()
+                .await
+                .set_token_registry(&token_registry)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
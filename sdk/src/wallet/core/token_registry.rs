@@ -0,0 +1,139 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::block::output::{
+    feature::{Irc30Metadata, MetadataFeature},
+    FoundryOutput, TokenId,
+};
+
+/// A registry of [`Irc30Metadata`] of native tokens.
+///
+/// Metadata is looked up in the following order:
+/// 1. user provided overrides;
+/// 2. verified token lists;
+/// 3. metadata parsed from the immutable metadata feature of the token foundry, cached during syncing.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRegistry {
+    #[serde(default)]
+    overrides: HashMap<TokenId, Irc30Metadata>,
+    #[serde(default)]
+    verified: HashMap<TokenId, Irc30Metadata>,
+    #[serde(default)]
+    cached: HashMap<TokenId, Irc30Metadata>,
+}
+
+impl TokenRegistry {
+    /// Returns the metadata of a native token, if known.
+    pub fn get(&self, token_id: &TokenId) -> Option<&Irc30Metadata> {
+        self.overrides
+            .get(token_id)
+            .or_else(|| self.verified.get(token_id))
+            .or_else(|| self.cached.get(token_id))
+    }
+
+    /// Returns whether the metadata of a native token comes from the verified tokens, which isn't the case if a user
+    /// provided override replaces it with other metadata.
+    pub fn is_verified(&self, token_id: &TokenId) -> bool {
+        match (self.verified.get(token_id), self.overrides.get(token_id)) {
+            (Some(verified), Some(metadata)) => verified == metadata,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Returns the user provided overrides.
+    pub fn overrides(&self) -> &HashMap<TokenId, Irc30Metadata> {
+        &self.overrides
+    }
+
+    /// Returns the verified tokens.
+    pub fn verified(&self) -> &HashMap<TokenId, Irc30Metadata> {
+        &self.verified
+    }
+
+    /// Returns the metadata parsed from token foundries.
+    pub fn cached(&self) -> &HashMap<TokenId, Irc30Metadata> {
+        &self.cached
+    }
+
+    /// Sets or, if `None`, removes the override of the metadata of a native token.
+    pub fn set_override(&mut self, token_id: TokenId, metadata: impl Into<Option<Irc30Metadata>>) {
+        match metadata.into() {
+            Some(metadata) => self.overrides.insert(token_id, metadata),
+            None => self.overrides.remove(&token_id),
+        };
+    }
+
+    /// Replaces the verified tokens.
+    pub fn set_verified(&mut self, tokens: impl IntoIterator<Item = (TokenId, Irc30Metadata)>) {
+        self.verified = tokens.into_iter().collect();
+    }
+
+    /// Returns the metadata of a native token, falling back to parsing the given foundry metadata for tokens that
+    /// weren't cached yet.
+    pub(crate) fn resolve(
+        &self,
+        token_id: &TokenId,
+        foundry_metadata: Option<&MetadataFeature>,
+    ) -> Option<Irc30Metadata> {
+        self.get(token_id)
+            .cloned()
+            .or_else(|| foundry_metadata.and_then(|metadata| Irc30Metadata::try_from(metadata).ok()))
+    }
+
+    /// Caches the metadata of the token of a foundry, if its immutable metadata follows the IRC30 standard. Returns
+    /// whether the registry changed.
+    pub(crate) fn cache_foundry(&mut self, foundry: &FoundryOutput) -> bool {
+        let token_id = foundry.token_id();
+
+        match foundry
+            .immutable_features()
+            .metadata()
+            .and_then(|metadata| Irc30Metadata::try_from(metadata).ok())
+        {
+            Some(metadata) => self.cached.insert(token_id, metadata.clone()).as_ref() != Some(&metadata),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precedence() {
+        let token_id = TokenId::from([1; TokenId::LENGTH]);
+        let mut registry = TokenRegistry::default();
+        assert!(registry.get(&token_id).is_none());
+
+        registry
+            .cached
+            .insert(token_id, Irc30Metadata::new("Cached Token", "CT", 6));
+        assert_eq!(registry.get(&token_id).unwrap().symbol(), "CT");
+
+        registry.set_verified([(token_id, Irc30Metadata::new("Verified Token", "VT", 6))]);
+        assert_eq!(registry.get(&token_id).unwrap().symbol(), "VT");
+        assert!(registry.is_verified(&token_id));
+
+        registry.set_override(token_id, Irc30Metadata::new("Override Token", "OT", 2));
+        assert_eq!(registry.get(&token_id).unwrap().symbol(), "OT");
+        assert!(!registry.is_verified(&token_id));
+
+        // An override with the verified metadata keeps the token verified
+        registry.set_override(token_id, Irc30Metadata::new("Verified Token", "VT", 6));
+        assert!(registry.is_verified(&token_id));
+
+        registry.set_override(token_id, None);
+        assert_eq!(registry.get(&token_id).unwrap().symbol(), "VT");
+        assert!(registry.is_verified(&token_id));
+
+        let json = serde_json::to_string(&registry).unwrap();
+        assert_eq!(serde_json::from_str::<TokenRegistry>(&json).unwrap(), registry);
+    }
+}
@@ -8,7 +8,10 @@ use serde::{
     Serialize,
 };
 
-use crate::types::block::{address::Bech32Address, payload::transaction::TransactionId};
+use crate::{
+    types::block::{address::Bech32Address, output::AliasId, payload::transaction::TransactionId},
+    wallet::account::types::Transaction,
+};
 
 /// The wallet error type.
 #[derive(Debug, thiserror::Error)]
@@ -22,6 +25,12 @@ pub enum Error {
     /// Address not found in account
     #[error("address {0} not found in account")]
     AddressNotFoundInAccount(Bech32Address),
+    /// Airdrop failed
+    #[error("airdrop failed: {0}")]
+    AirdropFailed(String),
+    /// Alias not found in unspent outputs
+    #[error("alias {0} not found in unspent outputs")]
+    AliasNotFoundInUnspentOutputs(AliasId),
     /// Errors during backup creation or restoring
     #[error("backup failed {0}")]
     Backup(&'static str),
@@ -31,6 +40,9 @@ pub enum Error {
     /// Burning or melting failed
     #[error("burning or melting failed: {0}")]
     BurningOrMeltingFailed(String),
+    /// No alias or NFT output of the account for an address
+    #[error("no alias or nft output of the account for address {0}")]
+    ChainAddressNotOwned(Bech32Address),
     /// Client error.
     #[error("`{0}`")]
     Client(Box<crate::client::Error>),
@@ -70,6 +82,14 @@ pub enum Error {
     /// Error migrating storage or backup
     #[error("migration failed {0}")]
     Migration(String),
+    /// Minting the items of an NFT collection failed after the transactions of the first batches were sent
+    #[error("minting collection items failed after {} sent transactions: {error}", transactions.len())]
+    MintCollectionItemsFailed {
+        /// The sent transactions
+        transactions: Vec<Transaction>,
+        /// The error of the failed batch
+        error: Box<Self>,
+    },
     /// Minting failed
     #[error("minting failed {0}")]
     MintingFailed(String),
@@ -109,6 +129,9 @@ pub enum Error {
     /// Transaction not found
     #[error("transaction {0} not found")]
     TransactionNotFound(TransactionId),
+    /// Transaction is not pending
+    #[error("transaction {0} is not pending")]
+    TransactionNotPending(TransactionId),
     // TODO more precise error
     /// Voting error
     #[cfg(feature = "participation")]
@@ -36,6 +36,7 @@ pub enum WalletEvent {
     SpentOutput(Box<SpentOutputEvent>),
     TransactionInclusion(TransactionInclusionEvent),
     TransactionProgress(TransactionProgressEvent),
+    TransactionReissue(TransactionReissueEvent),
 }
 
 impl Serialize for WalletEvent {
@@ -58,6 +59,7 @@ impl Serialize for WalletEvent {
             T3(&'a SpentOutputEvent),
             T4(&'a TransactionInclusionEvent),
             T5(TransactionProgressEvent_<'a>),
+            T6(&'a TransactionReissueEvent),
         }
         #[derive(Serialize)]
         struct TypedWalletEvent_<'a> {
@@ -92,6 +94,10 @@ impl Serialize for WalletEvent {
                 kind: WalletEventType::TransactionProgress as u8,
                 event: WalletEvent_::T5(TransactionProgressEvent_ { progress: e }),
             },
+            Self::TransactionReissue(e) => TypedWalletEvent_ {
+                kind: WalletEventType::TransactionReissue as u8,
+                event: WalletEvent_::T6(e),
+            },
         };
         event.serialize(serializer)
     }
@@ -143,6 +149,10 @@ impl<'de> Deserialize<'de> for WalletEvent {
                         })?
                         .progress,
                 ),
+                WalletEventType::TransactionReissue => Self::TransactionReissue(
+                    TransactionReissueEvent::deserialize(value)
+                        .map_err(|e| serde::de::Error::custom(format!("cannot deserialize TransactionReissue: {e}")))?,
+                ),
             },
         )
     }
@@ -160,6 +170,7 @@ pub enum WalletEventType {
     SpentOutput = 3,
     TransactionInclusion = 4,
     TransactionProgress = 5,
+    TransactionReissue = 6,
 }
 
 impl TryFrom<u8> for WalletEventType {
@@ -174,6 +185,7 @@ impl TryFrom<u8> for WalletEventType {
             3 => Self::SpentOutput,
             4 => Self::TransactionInclusion,
             5 => Self::TransactionProgress,
+            6 => Self::TransactionReissue,
             _ => return Err(format!("invalid event type {value}")),
         };
         Ok(event_type)
@@ -206,6 +218,15 @@ pub struct TransactionInclusionEvent {
     pub inclusion_state: InclusionState,
 }
 
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReissueEvent {
+    /// The conflicting transaction.
+    pub transaction_id: TransactionId,
+    /// The transaction that recreates the outputs of the conflicting transaction with other inputs.
+    pub reissued_transaction_id: TransactionId,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum TransactionProgressEvent {
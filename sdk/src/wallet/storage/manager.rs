@@ -8,7 +8,8 @@ use crate::{
     client::storage::StorageAdapter,
     types::TryFromDto,
     wallet::{
-        account::{AccountDetails, AccountDetailsDto, SyncOptions},
+        account::{AccountDetails, AccountDetailsDto, ConsolidationPolicy, SyncOptions},
+        core::TokenRegistry,
         migration::migrate,
         storage::{constants::*, DynStorageAdapter, Storage},
     },
@@ -27,10 +28,7 @@ impl StorageManager {
         storage: impl DynStorageAdapter + 'static,
         encryption_key: impl Into<Option<Zeroizing<[u8; 32]>>> + Send,
     ) -> crate::wallet::Result<Self> {
-        let storage = Storage {
-            inner: Box::new(storage) as _,
-            encryption_key: encryption_key.into(),
-        };
+        let storage = Storage::new(storage, encryption_key);
         migrate(&storage).await?;
 
         // Get the db version or set it
@@ -112,6 +110,48 @@ impl StorageManager {
         let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_SYNC_OPTIONS}");
         self.get(&key).await
     }
+
+    pub(crate) async fn set_consolidation_policy(
+        &self,
+        account_index: u32,
+        consolidation_policy: Option<&ConsolidationPolicy>,
+    ) -> crate::wallet::Result<()> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_CONSOLIDATION_POLICY}");
+        match consolidation_policy {
+            Some(consolidation_policy) => self.set(&key, consolidation_policy).await,
+            None => self.delete(&key).await,
+        }
+    }
+
+    pub(crate) async fn get_consolidation_policy(
+        &self,
+        account_index: u32,
+    ) -> crate::wallet::Result<Option<ConsolidationPolicy>> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_CONSOLIDATION_POLICY}");
+        self.get(&key).await
+    }
+
+    pub(crate) async fn set_last_consolidation(
+        &self,
+        account_index: u32,
+        last_consolidation: u128,
+    ) -> crate::wallet::Result<()> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_LAST_CONSOLIDATION}");
+        self.set(&key, &last_consolidation).await
+    }
+
+    pub(crate) async fn get_last_consolidation(&self, account_index: u32) -> crate::wallet::Result<Option<u128>> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_LAST_CONSOLIDATION}");
+        self.get(&key).await
+    }
+
+    pub(crate) async fn set_token_registry(&self, token_registry: &TokenRegistry) -> crate::wallet::Result<()> {
+        self.set(TOKEN_REGISTRY_KEY, token_registry).await
+    }
+
+    pub(crate) async fn get_token_registry(&self) -> crate::wallet::Result<Option<TokenRegistry>> {
+        self.get(TOKEN_REGISTRY_KEY).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -178,6 +218,19 @@ mod tests {
         assert!(storage_manager.get_accounts().await.unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn set_get_last_consolidation() {
+        let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
+        assert_eq!(storage_manager.get_last_consolidation(0).await.unwrap(), None);
+
+        storage_manager.set_last_consolidation(0, 1_690_000_000_000).await.unwrap();
+        assert_eq!(
+            storage_manager.get_last_consolidation(0).await.unwrap(),
+            Some(1_690_000_000_000)
+        );
+        assert_eq!(storage_manager.get_last_consolidation(1).await.unwrap(), None);
+    }
+
     #[tokio::test]
     async fn save_get_wallet_data() {
         let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
@@ -31,6 +31,19 @@ pub struct Storage {
     encryption_key: Option<Zeroizing<[u8; 32]>>,
 }
 
+impl Storage {
+    /// Creates a storage encrypting the records with the key, if any.
+    pub(crate) fn new(
+        inner: impl DynStorageAdapter + 'static,
+        encryption_key: impl Into<Option<Zeroizing<[u8; 32]>>>,
+    ) -> Self {
+        Self {
+            inner: Box::new(inner) as _,
+            encryption_key: encryption_key.into(),
+        }
+    }
+}
+
 #[async_trait]
 impl StorageAdapter for Storage {
     type Error = crate::wallet::Error;
@@ -1,7 +1,18 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_sdk::client::{Client, ClientBuilder};
+use iota_sdk::{
+    client::{
+        node_api::indexer::query_parameters::QueryParameter,
+        node_manager::{
+            auth::AuthProvider,
+            node::NodeAuth,
+            transport::{HttpMethod, HttpRequest, HttpResponse, HttpTransport},
+        },
+        Client, ClientBuilder, Error,
+    },
+    Url,
+};
 
 #[tokio::test]
 async fn invalid_url() {
@@ -61,3 +72,189 @@ async fn client_builder() {
 
     let _client_builder = serde_json::from_str::<ClientBuilder>(client_builder_json).unwrap();
 }
+
+// Records the requests and answers all of them with an empty response
+#[derive(Clone, Debug, Default)]
+struct RecordingTransport(std::sync::Arc<std::sync::Mutex<Vec<HttpRequest>>>);
+
+#[async_trait::async_trait]
+impl HttpTransport for RecordingTransport {
+    async fn send(&self, request: HttpRequest) -> iota_sdk::client::node_api::error::Result<HttpResponse> {
+        self.0.lock().unwrap().push(request);
+        Ok(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: Vec::new(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn custom_http_transport() {
+    let transport = RecordingTransport::default();
+
+    let client = Client::builder()
+        .with_node("http://localhost:14265")
+        .unwrap()
+        .with_http_transport(transport.clone())
+        .finish()
+        .await
+        .unwrap();
+
+    assert!(client.get_health("http://localhost:14265").await.unwrap());
+
+    let requests = transport.0.lock().unwrap();
+    let health_request = requests.last().unwrap();
+    assert_eq!(health_request.method, HttpMethod::Get);
+    assert_eq!(health_request.url.as_str(), "http://localhost:14265/health");
+    // The nodes are synced with the custom transport as well
+    assert!(requests.iter().any(|request| request.url.path() == "/api/core/v2/info"));
+}
+
+// Issues a new token every time the previous one is rejected
+#[derive(Debug, Default)]
+struct RefreshingAuthProvider {
+    refreshes: std::sync::Mutex<usize>,
+}
+
+#[async_trait::async_trait]
+impl AuthProvider for RefreshingAuthProvider {
+    async fn credentials(&self, _node_url: &Url) -> iota_sdk::client::node_api::error::Result<Option<NodeAuth>> {
+        Ok(Some(NodeAuth {
+            jwt: Some(format!("token-{}", self.refreshes.lock().unwrap())),
+            basic_auth_name_pwd: None,
+        }))
+    }
+
+    async fn on_unauthorized(&self, _node_url: &Url) -> iota_sdk::client::node_api::error::Result<bool> {
+        *self.refreshes.lock().unwrap() += 1;
+        Ok(true)
+    }
+}
+
+// Only accepts the token issued after the first refresh
+#[derive(Debug)]
+struct AuthorizingTransport;
+
+#[async_trait::async_trait]
+impl HttpTransport for AuthorizingTransport {
+    async fn send(&self, request: HttpRequest) -> iota_sdk::client::node_api::error::Result<HttpResponse> {
+        let authorized = request
+            .headers
+            .iter()
+            .any(|(name, value)| name == "authorization" && value == "Bearer token-1");
+        Ok(HttpResponse {
+            status: if authorized { 200 } else { 401 },
+            headers: Vec::new(),
+            body: Vec::new(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn auth_provider_refreshes_rejected_credentials() {
+    let client = Client::builder()
+        .with_node("http://localhost:14265")
+        .unwrap()
+        .with_http_transport(AuthorizingTransport)
+        .with_auth_provider(RefreshingAuthProvider::default())
+        .finish()
+        .await
+        .unwrap();
+
+    assert!(client.get_health("http://localhost:14265").await.unwrap());
+}
+
+// Answers indexer requests with the same output id, except for the nodes in `diverging`
+#[derive(Clone, Debug, Default)]
+struct IndexerTransport {
+    diverging: Vec<&'static str>,
+    requests: std::sync::Arc<std::sync::Mutex<Vec<HttpRequest>>>,
+}
+
+#[async_trait::async_trait]
+impl HttpTransport for IndexerTransport {
+    async fn send(&self, request: HttpRequest) -> iota_sdk::client::node_api::error::Result<HttpResponse> {
+        let index = if self.diverging.contains(&request.url.host_str().unwrap()) {
+            "0100"
+        } else {
+            "0000"
+        };
+        let body = if request.url.path() == "/api/indexer/v1/outputs/basic" {
+            self.requests.lock().unwrap().push(request);
+            format!(
+                r#"{{"ledgerIndex":1,"cursor":null,"items":["0x{}{index}"]}}"#,
+                "52".repeat(32)
+            )
+            .into_bytes()
+        } else {
+            Vec::new()
+        };
+        Ok(HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body,
+        })
+    }
+}
+
+async fn quorum_client(transport: IndexerTransport) -> Client {
+    Client::builder()
+        .with_nodes(&["http://node0:14265", "http://node1:14265", "http://node2:14265"])
+        .unwrap()
+        .with_ignore_node_health()
+        .with_quorum(true)
+        .with_min_quorum_size(3)
+        .with_quorum_threshold(100)
+        .with_http_transport(transport)
+        .finish()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn indexer_request_with_query_reaches_quorum() {
+    let transport = IndexerTransport::default();
+    let client = quorum_client(transport.clone()).await;
+
+    let output_ids = client
+        .basic_output_ids([QueryParameter::HasExpiration(false)])
+        .await
+        .unwrap();
+    assert_eq!(output_ids.items.len(), 1);
+
+    // Every node was asked for the same page
+    let requests = transport.requests.lock().unwrap();
+    let mut hosts = requests
+        .iter()
+        .map(|request| {
+            assert_eq!(request.url.query(), Some("hasExpiration=false"));
+            request.url.host_str().unwrap().to_string()
+        })
+        .collect::<Vec<_>>();
+    hosts.sort();
+    assert_eq!(hosts, ["node0", "node1", "node2"]);
+}
+
+#[tokio::test]
+async fn indexer_request_with_query_fails_without_quorum() {
+    let transport = IndexerTransport {
+        diverging: vec!["node1"],
+        ..Default::default()
+    };
+    let client = quorum_client(transport).await;
+
+    let error = client
+        .basic_output_ids([QueryParameter::HasExpiration(false)])
+        .await
+        .unwrap_err();
+    match error {
+        Error::QuorumThresholdError {
+            quorum_size, report, ..
+        } => {
+            assert_eq!(quorum_size, 2);
+            assert_eq!(report.nodes_by_response_hash().len(), 2);
+        }
+        error => panic!("unexpected error: {error}"),
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use iota_sdk::{
+    client::{
+        api::{InclusionOptions, InclusionOutcome},
+        node_manager::transport::{HttpRequest, HttpResponse, HttpTransport},
+        Client,
+    },
+    types::{
+        api::core::response::{BlockMetadataResponse, LedgerInclusionState},
+        block::{rand::parents::rand_parents, Block, BlockBuilder, BlockDto},
+    },
+};
+
+/// Serves a block and answers its metadata requests with the queued inclusion states, the last one is repeated.
+/// `None` stands for a node that didn't receive the block, the first `missing_block_requests` requests of the block
+/// itself are answered as if it wasn't received either.
+#[derive(Debug)]
+struct InclusionTransport {
+    block: Block,
+    inclusion_states: Mutex<VecDeque<Option<LedgerInclusionState>>>,
+    missing_block_requests: AtomicUsize,
+}
+
+impl InclusionTransport {
+    fn new(inclusion_states: impl IntoIterator<Item = Option<LedgerInclusionState>>) -> Self {
+        Self {
+            block: BlockBuilder::new(rand_parents()).finish().unwrap(),
+            inclusion_states: Mutex::new(inclusion_states.into_iter().collect()),
+            missing_block_requests: AtomicUsize::new(0),
+        }
+    }
+
+    fn inclusion_state(&self) -> Option<LedgerInclusionState> {
+        let mut inclusion_states = self.inclusion_states.lock().unwrap();
+        if inclusion_states.len() > 1 {
+            inclusion_states.pop_front().unwrap()
+        } else {
+            inclusion_states[0]
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpTransport for InclusionTransport {
+    async fn send(&self, request: HttpRequest) -> iota_sdk::client::node_api::error::Result<HttpResponse> {
+        let block_path = format!("/api/core/v2/blocks/{}", self.block.id());
+        let path = request.url.path();
+        let body = if path == format!("{block_path}/metadata") {
+            self.inclusion_state().map(|ledger_inclusion_state| {
+                serde_json::to_vec(&BlockMetadataResponse {
+                    block_id: self.block.id(),
+                    parents: self.block.parents().iter().copied().collect(),
+                    is_solid: true,
+                    referenced_by_milestone_index: Some(1),
+                    milestone_index: None,
+                    ledger_inclusion_state: Some(ledger_inclusion_state),
+                    conflict_reason: (ledger_inclusion_state == LedgerInclusionState::Conflicting).then_some(1),
+                    white_flag_index: None,
+                    should_promote: None,
+                    should_reattach: None,
+                })
+                .unwrap()
+            })
+        } else if path == block_path {
+            let missing = self
+                .missing_block_requests
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |requests| requests.checked_sub(1))
+                .is_ok();
+            (!missing).then(|| serde_json::to_vec(&BlockDto::from(&self.block)).unwrap())
+        } else {
+            None
+        };
+        Ok(match body {
+            Some(body) => HttpResponse {
+                status: 200,
+                headers: Vec::new(),
+                body,
+            },
+            None => HttpResponse {
+                status: 404,
+                headers: Vec::new(),
+                body: Vec::new(),
+            },
+        })
+    }
+}
+
+async fn await_inclusion(transport: InclusionTransport, timeout: Duration) -> (Block, InclusionOutcome) {
+    let block = transport.block.clone();
+    let client = Client::builder()
+        .with_node("http://localhost:14265")
+        .unwrap()
+        .with_ignore_node_health()
+        .with_http_transport(transport)
+        .finish()
+        .await
+        .unwrap();
+    let outcome = client
+        .await_inclusion(
+            &block.id(),
+            InclusionOptions {
+                timeout,
+                interval: Duration::from_millis(10),
+                promote_and_reattach: false,
+            },
+        )
+        .await
+        .unwrap();
+
+    (block, outcome)
+}
+
+#[tokio::test]
+async fn await_inclusion_of_block_not_found_yet() {
+    let transport = InclusionTransport::new([None, None, Some(LedgerInclusionState::NoTransaction)]);
+    let (block, outcome) = await_inclusion(transport, Duration::from_secs(10)).await;
+
+    assert_eq!(outcome, InclusionOutcome::Included { block_id: block.id() });
+}
+
+#[tokio::test]
+async fn await_inclusion_of_block_not_received_yet() {
+    let transport = InclusionTransport::new([Some(LedgerInclusionState::NoTransaction)]);
+    transport.missing_block_requests.store(2, Ordering::SeqCst);
+    let (block, outcome) = await_inclusion(transport, Duration::from_secs(10)).await;
+
+    assert_eq!(outcome, InclusionOutcome::Included { block_id: block.id() });
+}
+
+#[tokio::test]
+async fn await_inclusion_of_block_never_received_times_out() {
+    let transport = InclusionTransport::new([Some(LedgerInclusionState::NoTransaction)]);
+    transport.missing_block_requests.store(usize::MAX, Ordering::SeqCst);
+    let (block, outcome) = await_inclusion(transport, Duration::from_millis(100)).await;
+
+    assert_eq!(
+        outcome,
+        InclusionOutcome::TimedOut {
+            latest_attachment: block.id()
+        }
+    );
+}
+
+#[tokio::test]
+async fn await_inclusion_of_conflicting_block() {
+    let transport = InclusionTransport::new([Some(LedgerInclusionState::Conflicting)]);
+    let (block, outcome) = await_inclusion(transport, Duration::from_secs(10)).await;
+
+    assert!(matches!(outcome, InclusionOutcome::Conflicting { block_id, .. } if block_id == block.id()));
+}
+
+#[tokio::test]
+async fn await_inclusion_times_out() {
+    let transport = InclusionTransport::new([None]);
+    let (block, outcome) = await_inclusion(transport, Duration::from_millis(100)).await;
+
+    assert_eq!(
+        outcome,
+        InclusionOutcome::TimedOut {
+            latest_attachment: block.id()
+        }
+    );
+}
@@ -7,12 +7,14 @@ mod common;
 mod consolidation;
 mod error;
 mod high_level;
+mod inclusion;
 mod input_selection;
 mod input_signing_data;
 mod mnemonic;
 #[cfg(feature = "mqtt")]
 mod mqtt;
 mod node_api;
+mod output_ids_stream;
 mod secret_manager;
 mod signing;
 mod transactions;
@@ -1,7 +1,12 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_sdk::client::mqtt::{Error, Topic};
+use std::str::FromStr;
+
+use iota_sdk::{
+    client::mqtt::{Error, MqttTopic, Topic, UnlockConditionTopic},
+    types::block::{address::Bech32Address, output::OutputId, BlockId},
+};
 
 #[test]
 fn valid_topics() {
@@ -80,3 +85,48 @@ fn invalid_topics() {
         Err(Error::InvalidTopic(_))
     ));
 }
+
+#[test]
+fn typed_topics() {
+    let output_id =
+        OutputId::from_str("0x36845227a59864ac12d3d2389fcb4ea0bdd1a5d1d4ed464bde3154216c3246c40a00").unwrap();
+    let block_id = BlockId::from_str("0x36845227a59864ac12d3d2389fcb4ea0bdd1a5d1d4ed464bde3154216c3246c4").unwrap();
+    let address = Bech32Address::from_str("iota1qrwfnskm4f7utdrxqnkfntfqxehtpj8s0kf68zkcwm0yrhuemzjp5sjfw5v").unwrap();
+
+    assert_eq!(
+        Topic::try_from(MqttTopic::output(output_id)).unwrap().as_str(),
+        "outputs/0x36845227a59864ac12d3d2389fcb4ea0bdd1a5d1d4ed464bde3154216c3246c40a00"
+    );
+    assert_eq!(
+        Topic::try_from(MqttTopic::block_metadata(block_id)).unwrap().as_str(),
+        "block-metadata/0x36845227a59864ac12d3d2389fcb4ea0bdd1a5d1d4ed464bde3154216c3246c4"
+    );
+    assert_eq!(
+        Topic::try_from(MqttTopic::address_outputs(address.clone()))
+            .unwrap()
+            .as_str(),
+        "outputs/unlock/+/iota1qrwfnskm4f7utdrxqnkfntfqxehtpj8s0kf68zkcwm0yrhuemzjp5sjfw5v"
+    );
+    assert_eq!(
+        Topic::try_from(MqttTopic::unlock_condition_outputs(
+            UnlockConditionTopic::StorageReturn,
+            address,
+            true
+        ))
+        .unwrap()
+        .as_str(),
+        "outputs/unlock/storage-return/iota1qrwfnskm4f7utdrxqnkfntfqxehtpj8s0kf68zkcwm0yrhuemzjp5sjfw5v/spent"
+    );
+    assert_eq!(
+        Topic::try_from(MqttTopic::TaggedDataBlocks {
+            tag: Some(vec![0x01, 0x23])
+        })
+        .unwrap()
+        .as_str(),
+        "blocks/tagged-data/0x0123"
+    );
+    assert!(matches!(
+        Topic::try_from(MqttTopic::TaggedDataBlocks { tag: Some(Vec::new()) }),
+        Err(Error::InvalidTopic(_))
+    ));
+}
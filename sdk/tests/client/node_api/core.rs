@@ -4,7 +4,7 @@
 // These are E2E test samples, so they are ignored by default.
 
 use iota_sdk::{
-    client::{api::GetAddressesOptions, node_api::indexer::query_parameters::QueryParameter, Client, NodeInfoWrapper},
+    client::{api::GetAddressesOptions, node_api::indexer::query_parameters::QueryParameter, NodeInfoWrapper},
     types::block::{
         output::{Output, OutputId},
         payload::Payload,
@@ -30,7 +30,11 @@ async fn test_get_health() {
 #[ignore]
 #[tokio::test]
 async fn test_get_info() {
-    let r = Client::get_node_info(NODE_LOCAL, None).await.unwrap();
+    let r = setup_client_with_node_health_ignored()
+        .await
+        .get_info_from(NODE_LOCAL, None)
+        .await
+        .unwrap();
     println!("{r:#?}");
 }
 
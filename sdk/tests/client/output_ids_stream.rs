@@ -0,0 +1,262 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use futures::{StreamExt, TryStreamExt};
+use iota_sdk::{
+    client::{
+        node_api::{error::Error as NodeApiError, indexer::query_parameters::QueryParameter},
+        node_manager::transport::{HttpRequest, HttpResponse, HttpTransport},
+        Client, Error,
+    },
+    types::{
+        api::{core::response::OutputWithMetadataResponse, plugins::indexer::OutputIdsResponse},
+        block::{
+            address::Ed25519Address,
+            output::{
+                unlock_condition::AddressUnlockCondition, BasicOutputBuilder, OutputId, OutputMetadata,
+                OutputWithMetadata,
+            },
+            payload::transaction::TransactionId,
+            BlockId,
+        },
+    },
+};
+
+use crate::client::TOKEN_SUPPLY;
+
+const BASIC_OUTPUT_IDS_PATH: &str = "/api/indexer/v1/outputs/basic";
+
+/// Serves pages of basic output ids, page `n` has the cursor `n`, and the outputs of the ids. Output requests take a
+/// while, so concurrent requests overlap.
+#[derive(Debug, Default)]
+struct IndexerTransport {
+    pages: Vec<Vec<OutputId>>,
+    // Pages answered with a server error
+    failing_pages: Vec<usize>,
+    outputs: HashMap<OutputId, OutputWithMetadataResponse>,
+    requested_cursors: Mutex<Vec<Option<String>>>,
+    output_requests_in_flight: AtomicUsize,
+    max_output_requests_in_flight: AtomicUsize,
+}
+
+impl IndexerTransport {
+    fn new(page_sizes: impl IntoIterator<Item = usize>) -> Self {
+        let mut index = 0;
+        let pages = page_sizes
+            .into_iter()
+            .map(|page_size| {
+                (0..page_size)
+                    .map(|_| {
+                        index += 1;
+                        OutputId::new(TransactionId::new([index; TransactionId::LENGTH]), 0).unwrap()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let outputs = pages
+            .iter()
+            .flatten()
+            .map(|output_id| {
+                let output = BasicOutputBuilder::new_with_amount(1_000_000)
+                    .add_unlock_condition(AddressUnlockCondition::new(Ed25519Address::new([0; 32])))
+                    .finish_output(TOKEN_SUPPLY)
+                    .unwrap();
+                let metadata = OutputMetadata::new(BlockId::new([0; 32]), *output_id, false, None, None, None, 0, 0, 0);
+                (
+                    *output_id,
+                    OutputWithMetadataResponse::from(OutputWithMetadata::new(output, metadata)),
+                )
+            })
+            .collect();
+
+        Self {
+            pages,
+            outputs,
+            ..Default::default()
+        }
+    }
+
+    fn output_ids(&self) -> Vec<OutputId> {
+        self.pages.concat()
+    }
+
+    fn page(&self, request: &HttpRequest) -> HttpResponse {
+        let cursor = request
+            .url
+            .query_pairs()
+            .find(|(key, _)| key == "cursor")
+            .map(|(_, cursor)| cursor.into_owned());
+        let index = cursor.as_deref().map_or(0, |cursor| cursor.parse().unwrap());
+        self.requested_cursors.lock().unwrap().push(cursor);
+
+        if self.failing_pages.contains(&index) {
+            return response(500, b"internal error".to_vec());
+        }
+        let page = OutputIdsResponse {
+            ledger_index: 1,
+            cursor: (index + 1 < self.pages.len()).then(|| (index + 1).to_string()),
+            items: self.pages[index].clone(),
+        };
+        response(200, serde_json::to_vec(&page).unwrap())
+    }
+
+    async fn output(&self, output_id: &str) -> HttpResponse {
+        let in_flight = self.output_requests_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_output_requests_in_flight
+            .fetch_max(in_flight, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.output_requests_in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        match self.outputs.get(&output_id.parse().unwrap()) {
+            Some(output) => response(200, serde_json::to_vec(output).unwrap()),
+            None => response(404, Vec::new()),
+        }
+    }
+}
+
+fn response(status: u16, body: Vec<u8>) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: Vec::new(),
+        body,
+    }
+}
+
+// Shares the transport with the test, so it can be inspected after the client used it
+#[derive(Debug)]
+struct SharedTransport(Arc<IndexerTransport>);
+
+#[async_trait::async_trait]
+impl HttpTransport for SharedTransport {
+    async fn send(&self, request: HttpRequest) -> iota_sdk::client::node_api::error::Result<HttpResponse> {
+        let path = request.url.path();
+        Ok(if path == BASIC_OUTPUT_IDS_PATH {
+            self.0.page(&request)
+        } else if let Some(output_id) = path.strip_prefix("/api/core/v2/outputs/") {
+            self.0.output(output_id).await
+        } else {
+            response(404, Vec::new())
+        })
+    }
+}
+
+async fn client(transport: IndexerTransport) -> (Client, Arc<IndexerTransport>) {
+    let transport = Arc::new(transport);
+    let client = Client::builder()
+        .with_node("http://localhost:14265")
+        .unwrap()
+        .with_ignore_node_health()
+        .with_http_transport(SharedTransport(transport.clone()))
+        .finish()
+        .await
+        .unwrap();
+
+    (client, transport)
+}
+
+#[tokio::test]
+async fn output_ids_stream_follows_the_cursor() {
+    let (client, transport) = client(IndexerTransport::new([2, 2, 1])).await;
+
+    let pages = client
+        .basic_output_ids_stream([QueryParameter::PageSize(2)])
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(pages.len(), 3);
+    assert_eq!(
+        pages.iter().flat_map(|page| page.items.clone()).collect::<Vec<_>>(),
+        transport.output_ids()
+    );
+    assert_eq!(pages[0].cursor.as_deref(), Some("1"));
+    assert_eq!(pages[2].cursor, None);
+    assert_eq!(
+        *transport.requested_cursors.lock().unwrap(),
+        [None, Some("1".to_owned()), Some("2".to_owned())]
+    );
+}
+
+#[tokio::test]
+async fn output_ids_stream_ends_on_the_last_page() {
+    let (client, transport) = client(IndexerTransport::new([0])).await;
+
+    let output_ids = client.basic_output_ids_stream([]).unwrap();
+    futures::pin_mut!(output_ids);
+
+    let page = output_ids.try_next().await.unwrap().unwrap();
+    assert!(page.items.is_empty());
+    assert!(output_ids.try_next().await.unwrap().is_none());
+    // No request is sent after the last page
+    assert_eq!(transport.requested_cursors.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn output_ids_stream_passes_errors_through() {
+    let mut transport = IndexerTransport::new([2, 2, 2]);
+    transport.failing_pages = vec![1];
+    let (client, transport) = client(transport).await;
+
+    let output_ids = client.basic_output_ids_stream([]).unwrap();
+    futures::pin_mut!(output_ids);
+
+    assert_eq!(output_ids.try_next().await.unwrap().unwrap().items, transport.pages[0]);
+    assert!(matches!(
+        output_ids.try_next().await,
+        Err(Error::Node(NodeApiError::ResponseError { code: 500, .. }))
+    ));
+    // The stream ends after an error
+    assert!(output_ids.next().await.is_none());
+}
+
+#[tokio::test]
+async fn get_outputs_stream_limits_parallel_requests() {
+    let (client, transport) = client(IndexerTransport::new([4, 4, 4])).await;
+
+    let output_ids = client.basic_output_ids_stream([]).unwrap();
+    let outputs = client
+        .get_outputs_stream(output_ids, 3)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    // The outputs keep the order of the output ids
+    assert_eq!(
+        outputs
+            .iter()
+            .map(|output| *output.metadata().output_id())
+            .collect::<Vec<_>>(),
+        transport.output_ids()
+    );
+    let max_in_flight = transport.max_output_requests_in_flight.load(Ordering::SeqCst);
+    assert!(
+        max_in_flight > 1 && max_in_flight <= 3,
+        "{max_in_flight} requests in flight"
+    );
+}
+
+#[tokio::test]
+async fn get_outputs_stream_passes_errors_through() {
+    let mut transport = IndexerTransport::new([2, 2]);
+    // The output of the last id is unknown
+    let missing_output_id = *transport.pages[1].last().unwrap();
+    transport.outputs.remove(&missing_output_id);
+    let (client, _) = client(transport).await;
+
+    let output_ids = client.basic_output_ids_stream([]).unwrap();
+    let results = client.get_outputs_stream(output_ids, 2).collect::<Vec<_>>().await;
+
+    assert_eq!(results.len(), 4);
+    assert!(results[..3].iter().all(Result::is_ok));
+    assert!(matches!(results[3], Err(Error::Node(NodeApiError::NotFound(_)))));
+}
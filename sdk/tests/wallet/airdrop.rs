@@ -0,0 +1,104 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    wallet::{
+        account::{AirdropParams, AirdropRecipient},
+        CreateNativeTokenParams, Result,
+    },
+    U256,
+};
+
+use crate::wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down};
+
+#[test]
+fn airdrop_recipients_from_csv() -> Result<()> {
+    let path = "test-storage/airdrop_recipients_from_csv";
+    setup(path)?;
+    std::fs::create_dir_all(path)?;
+
+    let address = "rms1qpllaj0pyveqfkwxmnngz2c488hfdtmfrj3wfkgxtk4gtyrax0jaxzt70zy";
+    std::fs::write(
+        format!("{path}/recipients.csv"),
+        format!("address,amount\n{address},100\n\n# comment\n{address}, 0xff\n"),
+    )?;
+
+    let recipients = AirdropRecipient::from_csv_file(format!("{path}/recipients.csv"))?;
+    assert_eq!(
+        recipients,
+        [
+            AirdropRecipient::new(address, 100)?,
+            AirdropRecipient::new(address, 255)?
+        ]
+    );
+
+    std::fs::write(format!("{path}/recipients.json"), serde_json::to_string(&recipients)?)?;
+    assert_eq!(
+        AirdropRecipient::from_json_file(format!("{path}/recipients.json"))?,
+        recipients
+    );
+
+    std::fs::write(format!("{path}/invalid.csv"), format!("{address},100\n{address},abc\n"))?;
+    assert!(AirdropRecipient::from_csv_file(format!("{path}/invalid.csv")).is_err());
+
+    tear_down(path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn airdrop_native_tokens() -> Result<()> {
+    let storage_path = "test-storage/airdrop_native_tokens";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = &create_accounts_with_funds(&wallet, 1).await?[0];
+    let recipient_account = wallet.create_account().finish().await?;
+
+    let tx = account.create_alias_output(None, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let create_tx = account
+        .create_native_token(
+            CreateNativeTokenParams {
+                alias_id: None,
+                circulating_supply: U256::from(100),
+                maximum_supply: U256::from(100),
+                foundry_metadata: None,
+            },
+            None,
+        )
+        .await?;
+    account
+        .retry_transaction_until_included(&create_tx.transaction.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let recipients = recipient_account
+        .addresses()
+        .await?
+        .into_iter()
+        .map(|address| AirdropRecipient::new(address, 10))
+        .collect::<Result<Vec<_>>>()?;
+    let params = AirdropParams::new(create_tx.token_id, recipients).with_expiration(3600);
+    let report_path = format!("{storage_path}/airdrop-report.json");
+
+    let report = account
+        .airdrop_native_tokens(params.clone(), &report_path, None)
+        .await?;
+    assert!(report.is_finished());
+    assert_eq!(report.pending_storage_deposit(), 0);
+
+    // Resuming a finished airdrop doesn't send anything
+    let transactions = account.transactions().await.len();
+    let resumed_report = account.airdrop_native_tokens(params, &report_path, None).await?;
+    assert_eq!(resumed_report, report);
+    assert_eq!(account.transactions().await.len(), transactions);
+
+    let balance = recipient_account.sync(None).await?;
+    assert_eq!(balance.potentially_locked_outputs().len(), 1);
+
+    tear_down(storage_path)
+}
@@ -2,12 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use iota_sdk::{
-    types::block::output::{
-        feature::SenderFeature,
-        unlock_condition::{AddressUnlockCondition, ExpirationUnlockCondition},
-        BasicOutputBuilder, UnlockCondition,
+    types::block::{
+        address::{AliasAddress, Bech32Address},
+        output::{
+            feature::SenderFeature,
+            unlock_condition::{AddressUnlockCondition, ExpirationUnlockCondition},
+            BasicOutputBuilder, UnlockCondition,
+        },
+    },
+    wallet::{
+        account::types::{Balance, BalancePoint},
+        Result,
     },
-    wallet::{account::types::Balance, Result},
 };
 
 use crate::wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down};
@@ -165,6 +171,112 @@ async fn balance_expiration() -> Result<()> {
     tear_down(storage_path)
 }
 
+#[ignore]
+#[tokio::test]
+async fn balance_timeline() -> Result<()> {
+    let storage_path = "test-storage/balance_timeline";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+
+    let account_0 = &create_accounts_with_funds(&wallet, 1).await?[0];
+    let account_1 = wallet.create_account().finish().await?;
+
+    let amount = 1_000_000;
+    let expiration_time = account_0.client().get_time_checked().await? + 3600;
+    let token_supply = account_0.client().get_token_supply().await?;
+    let outputs = [BasicOutputBuilder::new_with_amount(amount)
+        // Send to account 1 with expiration back to account 0
+        .with_unlock_conditions([
+            UnlockCondition::Address(AddressUnlockCondition::new(
+                *account_1.addresses().await?[0].address().as_ref(),
+            )),
+            UnlockCondition::Expiration(ExpirationUnlockCondition::new(
+                *account_0.addresses().await?[0].address().as_ref(),
+                expiration_time,
+            )?),
+        ])
+        .finish_output(token_supply)?];
+
+    let tx = account_0.send_outputs(outputs, None).await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account_0.sync(None).await?;
+    account_1.sync(None).await?;
+
+    // Account 0 gets the output back at the expiration
+    let timeline_0 = account_0.balance_timeline().await?;
+    assert_eq!(timeline_0.len(), 1);
+    assert_eq!(timeline_0[0].timestamp(), expiration_time);
+    assert_eq!(timeline_0[0].unlocked().base_coin(), amount);
+    assert!(timeline_0[0].expiring().outputs().is_empty());
+
+    // Account 1 can spend the output until the expiration
+    let timeline_1 = account_1.balance_timeline().await?;
+    assert_eq!(timeline_1.len(), 1);
+    assert_eq!(timeline_1[0].timestamp(), expiration_time);
+    assert_eq!(timeline_1[0].expiring().base_coin(), amount);
+    assert!(timeline_1[0].unlocked().outputs().is_empty());
+
+    tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn balance_owned_by_chains() -> Result<()> {
+    let storage_path = "test-storage/balance_owned_by_chains";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = &create_accounts_with_funds(&wallet, 1).await?[0];
+
+    let tx = account.create_alias_output(None, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    let alias_id = *balance.aliases().first().unwrap();
+    let alias_address = Bech32Address::new(account.client().get_bech32_hrp().await?, AliasAddress::new(alias_id));
+
+    // Send funds to the alias address
+    let amount = 1_000_000;
+    let tx = account.send(amount, alias_address, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    let chain_balance = balance.owned_by_chains().get(&alias_address).unwrap();
+    assert_eq!(chain_balance.base_coin(), amount);
+    assert_eq!(chain_balance.outputs().len(), 1);
+
+    // Spend part of them, the remainder stays with the alias
+    let token_supply = account.client().get_token_supply().await?;
+    let outputs = [BasicOutputBuilder::new_with_amount(amount / 2)
+        .add_unlock_condition(AddressUnlockCondition::new(*account.addresses().await?[0].address().as_ref()))
+        .finish_output(token_supply)?];
+    let tx = account.send_outputs_from_chain(alias_address, outputs, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    let chain_balance = balance.owned_by_chains().get(&alias_address).unwrap();
+    assert_eq!(chain_balance.base_coin(), amount / 2);
+
+    // Spending more than the alias owns fails
+    let outputs = [BasicOutputBuilder::new_with_amount(amount)
+        .add_unlock_condition(AddressUnlockCondition::new(*account.addresses().await?[0].address().as_ref()))
+        .finish_output(token_supply)?];
+    assert!(
+        account
+            .send_outputs_from_chain(alias_address, outputs, None)
+            .await
+            .is_err()
+    );
+
+    tear_down(storage_path)
+}
+
 #[ignore]
 #[tokio::test]
 async fn addresses_balance() -> Result<()> {
@@ -235,6 +347,46 @@ async fn addresses_balance() -> Result<()> {
     tear_down(storage_path)
 }
 
+#[ignore]
+#[tokio::test]
+async fn historic_balance() -> Result<()> {
+    let storage_path = "test-storage/historic_balance";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+
+    let account_0 = &create_accounts_with_funds(&wallet, 1).await?[0];
+    let account_1 = wallet.create_account().finish().await?;
+
+    let faucet_amount = account_0.balance().await?.base_coin().total();
+    let faucet_output = account_0.unspent_outputs(None).await?.remove(0);
+    let booked_index = faucet_output.metadata.milestone_index_booked();
+
+    let tx = account_0
+        .send(1_000_000, *account_1.addresses().await?[0].address(), None)
+        .await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().total(), faucet_amount - 1_000_000);
+
+    // Before the faucet output was booked
+    let historic_balance = account_0
+        .historic_balance(BalancePoint::MilestoneIndex(booked_index - 1))
+        .await?;
+    assert_eq!(historic_balance.balance().base_coin().total(), 0);
+
+    // Before the funds were sent
+    let historic_balance = account_0
+        .historic_balance(BalancePoint::MilestoneIndex(booked_index))
+        .await?;
+    assert_eq!(historic_balance.balance().base_coin().total(), faucet_amount);
+    assert!(historic_balance.unknown_outputs().is_empty());
+
+    tear_down(storage_path)
+}
+
 #[ignore]
 #[tokio::test]
 #[cfg(feature = "participation")]
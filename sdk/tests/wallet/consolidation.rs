@@ -1,6 +1,11 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "storage")]
+use std::time::Duration;
+
+#[cfg(feature = "storage")]
+use iota_sdk::wallet::account::ConsolidationPolicy;
 use iota_sdk::wallet::{account::ConsolidationParams, Result, SendParams};
 
 use crate::wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down};
@@ -48,3 +53,33 @@ async fn consolidation() -> Result<()> {
 
     tear_down(storage_path)
 }
+
+#[cfg(feature = "storage")]
+#[tokio::test]
+async fn consolidation_policy() -> Result<()> {
+    let storage_path = "test-storage/consolidation_policy";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = wallet.create_account().finish().await?;
+    assert_eq!(account.consolidation_policy().await, None);
+
+    let policy = ConsolidationPolicy::new()
+        .with_output_threshold(10)
+        .with_min_interval(Duration::from_secs(60))
+        .with_merge_native_tokens(true);
+    account.set_consolidation_policy(policy.clone()).await?;
+    assert_eq!(account.consolidation_policy().await, Some(policy.clone()));
+
+    // The policy is stored in the database and available the next time
+    drop(account);
+    drop(wallet);
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = wallet.get_account(0).await?;
+    assert_eq!(account.consolidation_policy().await, Some(policy));
+
+    account.set_consolidation_policy(None).await?;
+    assert_eq!(account.consolidation_policy().await, None);
+
+    tear_down(storage_path)
+}
@@ -15,7 +15,7 @@ use iota_sdk::{
         account::types::{InclusionState, OutputData, OutputDataDto},
         events::types::{
             AddressData, NewOutputEvent, SpentOutputEvent, TransactionInclusionEvent, TransactionProgressEvent,
-            WalletEvent,
+            TransactionReissueEvent, WalletEvent,
         },
     },
 };
@@ -66,6 +66,11 @@ fn wallet_events_serde() {
         inclusion_state: InclusionState::Conflicting,
     }));
 
+    assert_serde_eq(WalletEvent::TransactionReissue(TransactionReissueEvent {
+        transaction_id: TransactionId::null(),
+        reissued_transaction_id: TransactionId::new(prefix_hex::decode(TRANSACTION_ID).unwrap()),
+    }));
+
     assert_serde_eq(WalletEvent::TransactionProgress(
         TransactionProgressEvent::SelectingInputs,
     ));
@@ -4,6 +4,7 @@
 mod account_recovery;
 mod accounts;
 mod address_generation;
+mod airdrop;
 #[cfg(all(feature = "stronghold", feature = "storage"))]
 mod backup_restore;
 mod balance;
@@ -22,9 +23,12 @@ mod events;
 #[cfg(feature = "stronghold")]
 mod migrate_stronghold_snapshot_v2_to_v3;
 mod native_tokens;
+mod nft_collection;
 mod output_preparation;
+mod reclaim_storage_deposit;
 mod syncing;
 mod transactions;
+mod update_alias;
 #[cfg(not(target_os = "windows"))]
 #[cfg(feature = "rocksdb")]
 mod wallet_storage;
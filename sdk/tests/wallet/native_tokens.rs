@@ -125,3 +125,65 @@ async fn native_token_foundry_metadata() -> Result<()> {
 
     tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+async fn retire_native_token() -> Result<()> {
+    let storage_path = "test-storage/retire_native_token";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+
+    let account = &create_accounts_with_funds(&wallet, 1).await?[0];
+
+    let tx = account.create_alias_output(None, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let create_tx = account
+        .create_native_token(
+            CreateNativeTokenParams {
+                alias_id: None,
+                circulating_supply: U256::from(50),
+                maximum_supply: U256::from(100),
+                foundry_metadata: None,
+            },
+            None,
+        )
+        .await?;
+    account
+        .retry_transaction_until_included(&create_tx.transaction.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    let alias_amount = account
+        .unspent_alias_output(balance.aliases().first().unwrap())
+        .await?
+        .unwrap()
+        .output
+        .amount();
+    assert_eq!(balance.foundries().len(), 1);
+
+    let retired = account.retire_native_token(create_tx.token_id, None).await?;
+    assert!(retired.foundry_destroyed);
+    assert_eq!(retired.remaining_circulating_supply, U256::from(0));
+    account
+        .retry_transaction_until_included(&retired.transaction.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    assert!(balance.native_tokens().is_empty());
+    assert!(balance.foundries().is_empty());
+    // The storage deposit of the foundry was released to the alias
+    assert!(
+        account
+            .unspent_alias_output(balance.aliases().first().unwrap())
+            .await?
+            .unwrap()
+            .output
+            .amount()
+            > alias_amount
+    );
+
+    tear_down(storage_path)
+}
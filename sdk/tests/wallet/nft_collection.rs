@@ -0,0 +1,85 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    types::block::output::feature::Irc27Metadata,
+    wallet::{MintNftParams, Result},
+    Url,
+};
+
+use crate::wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down};
+
+#[test]
+fn collection_metadata_from_dir() -> Result<()> {
+    let path = "test-storage/collection_metadata_from_dir";
+    setup(path)?;
+    std::fs::create_dir_all(path)?;
+
+    let uri: Url = "https://mywebsite.com/my-nft-files-1.png".parse().unwrap();
+    for (file_name, name) in [("2.json", "My NFT #2"), ("1.json", "My NFT #1")] {
+        let metadata = Irc27Metadata::new("image/png", uri.clone(), name);
+        std::fs::write(format!("{path}/{file_name}"), metadata.to_bytes()?)?;
+    }
+    std::fs::write(format!("{path}/README.md"), "not metadata")?;
+
+    let params = MintNftParams::from_irc27_metadata_dir(path)?;
+    assert_eq!(params.len(), 2);
+    assert_eq!(
+        Irc27Metadata::from_bytes(params[0].immutable_metadata().as_ref().unwrap())?.name(),
+        "My NFT #1"
+    );
+
+    std::fs::write(format!("{path}/3.json"), "{}")?;
+    assert!(MintNftParams::from_irc27_metadata_dir(path).is_err());
+
+    tear_down(path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn mint_nft_collection() -> Result<()> {
+    let storage_path = "test-storage/mint_nft_collection";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = &create_accounts_with_funds(&wallet, 1).await?[0];
+
+    let uri: Url = "https://mywebsite.com/my-nft-files-1.png".parse().unwrap();
+    let collection = account
+        .mint_collection_nft(
+            MintNftParams::new().try_with_irc27_metadata(&Irc27Metadata::new(
+                "image/png",
+                uri.clone(),
+                "My Collection",
+            ))?,
+            None,
+        )
+        .await?;
+    account
+        .retry_transaction_until_included(&collection.transaction.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let items = (0..3)
+        .map(|i| {
+            MintNftParams::new().try_with_irc27_metadata(
+                &Irc27Metadata::new("image/png", uri.clone(), format!("My NFT #{i}"))
+                    .with_collection_name("My Collection".to_owned()),
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let transactions = account.mint_collection_items(collection.nft_id, items, None).await?;
+    assert_eq!(transactions.len(), 1);
+    account
+        .retry_transaction_until_included(&transactions[0].transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let collection_items = account.nft_collection_items(collection.nft_id).await?;
+    assert_eq!(collection_items.len(), 3);
+    assert!(collection_items
+        .iter()
+        .all(|item| item.issued() && item.held() && item.owner().is_some()));
+
+    tear_down(storage_path)
+}
@@ -0,0 +1,48 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    types::block::output::{unlock_condition::AddressUnlockCondition, NftId, NftOutputBuilder, Rent},
+    wallet::Result,
+};
+
+use crate::wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down};
+
+#[ignore]
+#[tokio::test]
+async fn reclaim_storage_deposit() -> Result<()> {
+    let storage_path = "test-storage/reclaim_storage_deposit";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = &create_accounts_with_funds(&wallet, 1).await?[0];
+    let address = *account.addresses().await?[0].address();
+    let rent_structure = account.client().get_rent_structure().await?;
+    let token_supply = account.client().get_token_supply().await?;
+
+    // Mint an NFT with more than its minimum storage deposit
+    let outputs = [NftOutputBuilder::new_with_amount(2_000_000, NftId::null())
+        .add_unlock_condition(AddressUnlockCondition::new(address))
+        .finish_output(token_supply)?];
+    let tx = account.send_outputs(outputs, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    let nft_id = *balance.nfts().first().unwrap();
+
+    let transactions = account.reclaim_storage_deposit(address, None).await?;
+    assert_eq!(transactions.len(), 1);
+    account
+        .retry_transaction_until_included(&transactions[0].transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let nft_output = account.unspent_nft_output(&nft_id).await?.unwrap().output;
+    assert_eq!(nft_output.amount(), nft_output.rent_cost(&rent_structure));
+
+    // Nothing left to reclaim
+    assert!(account.reclaim_storage_deposit(address, None).await?.is_empty());
+
+    tear_down(storage_path)
+}
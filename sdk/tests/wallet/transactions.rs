@@ -1,7 +1,10 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_sdk::wallet::{account::TransactionOptions, MintNftParams, Result, SendNftParams, SendParams};
+use iota_sdk::wallet::{
+    account::{types::InclusionState, TransactionOptions},
+    MintNftParams, Result, SendNftParams, SendParams,
+};
 
 use crate::wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down};
 
@@ -256,6 +259,56 @@ async fn conflicting_transaction() -> Result<()> {
     tear_down(storage_path_1)
 }
 
+#[ignore]
+#[tokio::test]
+async fn cancel_transaction() -> Result<()> {
+    let storage_path = "test-storage/cancel_transaction";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+
+    let account_0 = &create_accounts_with_funds(&wallet, 1).await?[0];
+    let account_1 = wallet.create_account().finish().await?;
+
+    let tx = account_0
+        .send(1_000_000, *account_1.addresses().await?[0].address(), None)
+        .await?;
+    let cancel_tx = account_0.cancel_transaction(&tx.transaction_id, None).await?;
+    assert_eq!(cancel_tx.replaced_transaction_id, Some(tx.transaction_id));
+
+    for _ in 0..30 {
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        account_0.sync(None).await?;
+        if account_0.pending_transactions().await.is_empty() {
+            break;
+        }
+    }
+
+    // Only one of both transactions got confirmed, the other one lost the double spend
+    let inclusion_states = [
+        account_0
+            .get_transaction(&tx.transaction_id)
+            .await
+            .unwrap()
+            .inclusion_state,
+        account_0
+            .get_transaction(&cancel_tx.transaction_id)
+            .await
+            .unwrap()
+            .inclusion_state,
+    ];
+    assert!(inclusion_states.contains(&InclusionState::Confirmed));
+    assert!(inclusion_states.contains(&InclusionState::Conflicting));
+
+    // Resolved transactions can't be cancelled anymore
+    assert!(matches!(
+        account_0.cancel_transaction(&tx.transaction_id, None).await,
+        Err(iota_sdk::wallet::Error::TransactionNotPending(_))
+    ));
+
+    tear_down(storage_path)
+}
+
 #[tokio::test]
 #[cfg(all(feature = "ledger_nano", feature = "events"))]
 #[ignore = "requires ledger nano instance"]
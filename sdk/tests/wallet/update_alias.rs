@@ -0,0 +1,61 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    types::block::output::Output,
+    wallet::{account::AliasGovernanceParams, Result},
+};
+
+use crate::wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down};
+
+#[ignore]
+#[tokio::test]
+async fn update_alias() -> Result<()> {
+    let storage_path = "test-storage/update_alias";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = &create_accounts_with_funds(&wallet, 1).await?[0];
+
+    let tx = account.create_alias_output(None, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    let alias_id = *balance.aliases().first().unwrap();
+
+    let tx = account
+        .update_alias_state_metadata(alias_id, b"some alias state metadata".to_vec(), None)
+        .await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let new_controller_address = *account.generate_ed25519_addresses(1, None).await?[0].address();
+    let tx = account
+        .update_alias_governance(
+            alias_id,
+            AliasGovernanceParams::new()
+                .with_state_controller_address(new_controller_address)
+                .with_metadata(b"some alias metadata".to_vec()),
+            None,
+        )
+        .await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let output_data = account.unspent_alias_output(&alias_id).await?.unwrap();
+    if let Output::Alias(alias_output) = &output_data.output {
+        assert_eq!(alias_output.state_index(), 1);
+        assert_eq!(alias_output.state_metadata(), b"some alias state metadata");
+        assert_eq!(alias_output.state_controller_address(), new_controller_address.inner());
+        assert!(alias_output.features().metadata().is_some());
+    } else {
+        panic!("alias output expected");
+    }
+
+    tear_down(storage_path)
+}